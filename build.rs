@@ -0,0 +1,45 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Embeds `default_config` into the binary as `DEFAULT_CONFIG_FILES`.
+fn main() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.join("default_config");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut files = vec![];
+    collect_files(&root, &mut files);
+    files.sort();
+
+    let mut generated =
+        String::from("pub(crate) static DEFAULT_CONFIG_FILES: &[(&str, &[u8])] = &[\n");
+    for file in files {
+        let rel_path = file.strip_prefix(&root).unwrap();
+        let rel_path = rel_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        writeln!(
+            generated,
+            "    ({rel_path:?}, include_bytes!({:?})),",
+            file.display().to_string()
+        )
+        .unwrap();
+    }
+    generated.push_str("];\n");
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    std::fs::write(out_dir.join("default_config.rs"), generated).unwrap();
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
-use cursive::reexports::log::{error, info};
+use cursive::reexports::log::{error, info, warn};
 use futures::StreamExt;
 use kanal::AsyncReceiver;
 use kube::Client;
@@ -17,7 +17,7 @@ use crate::backend::remove_manager::RemoveManager;
 use crate::model::resource::resource_view::{register_any_gvk, ResourceView};
 use crate::traits::ext::kube_config::KubeConfigExt;
 use crate::ui::signals::{FromBackendSignal, ToBackendSignal};
-use crate::util::k8s::discover_gvk;
+use crate::util::k8s::{discover_gvk, fetch_metrics};
 use crate::util::panics::ResultExt;
 
 pub(crate) struct K8sBackend {
@@ -137,6 +137,35 @@ impl K8sBackend {
         });
     }
 
+    pub(crate) fn spawn_metrics_task(&self, poll_interval: std::time::Duration) {
+        let sender = self.from_backend_sender.clone_async();
+        let client = self.client.clone();
+        self.runtime.spawn(async move {
+            // the metrics API is optional; report it missing once instead of on every poll
+            let mut is_available = true;
+            loop {
+                match fetch_metrics(client.clone()).await {
+                    Ok(snapshot) => {
+                        if !is_available {
+                            info!("Metrics API became available");
+                        }
+                        is_available = true;
+                        sender
+                            .send(FromBackendSignal::MetricsUpdated(snapshot))
+                            .await
+                            .unwrap_or_log();
+                    }
+                    Err(err) if is_available => {
+                        warn!("Failed to fetch metrics: {}", err);
+                        is_available = false;
+                    }
+                    Err(_) => {}
+                }
+                tokio::time::sleep(poll_interval).await;
+            }
+        });
+    }
+
     pub(crate) fn spawn_watcher_exchange_task(&self) {
         let resource_watch_receiver = self.resource_watcher_receiver.clone();
         let ui_signal_sender = self.from_backend_sender.clone_async();
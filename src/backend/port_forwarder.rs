@@ -1,24 +1,41 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::Poll;
+use std::time::{Duration, Instant};
 
+use anyhow::Context;
 use cursive::reexports::log::{error, info, warn};
 use futures::{StreamExt, TryStreamExt};
-use k8s_openapi::api::core::v1::Pod;
-use kube::{Api, Client};
+use itertools::Itertools;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::ListParams;
+use kube::{Api, Client, ResourceExt};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tokio::{
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncWrite, ReadBuf},
     net::TcpListener,
 };
 use tokio_stream::wrappers::TcpListenerStream;
 
-use crate::model::port_forward_request::PortForwardRequest;
+use crate::model::port_forward_request::{PortForwardRequest, PortForwardTarget};
+use crate::traits::ext::pod::PodExt;
 use crate::ui::signals::FromBackendSignal;
 use crate::util::error::LogErrorOptionExt;
 
+/// The first restart delay of a failed listener, doubled on every consecutive failure.
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// Consecutive listener failures after which forwarding is given up; a listener that served
+/// longer than `MAX_RESTART_DELAY` starts the count again.
+const MAX_RESTARTS: usize = 10;
+
 pub(crate) struct PortForwarder {
     client: Client,
     from_backend_sender: kanal::AsyncSender<FromBackendSignal>,
@@ -51,39 +68,54 @@ impl PortForwarder {
         let mut handles_map = self.handles_map.write().await;
         info!("Forwarding {:?}", request);
 
-        let api: Api<Pod> = Api::namespaced(self.client.clone(), &request.namespace);
+        let resolver = Arc::new(TargetResolver::new(&self.client, Arc::clone(&request)));
+        if let Err(err) = resolver.get().await {
+            // the listener is started anyway; the target is resolved again on connection
+            warn!("Failed to resolve {}: {err}", request.target);
+            request.stats.set_last_error(&err);
+        }
+
         let addr = SocketAddr::from_str(&format!("{}:{}", request.host, request.host_port))?;
 
         let map = Arc::clone(&self.handles_map);
         let handle: JoinHandle<anyhow::Result<()>> = tokio::spawn(async move {
-            let server = TcpListenerStream::new(TcpListener::bind(addr).await?)
-                .take_until(tokio::signal::ctrl_c())
-                .try_for_each(|client_conn| async {
-                    if let Ok(peer_addr) = client_conn.peer_addr() {
-                        info!(
-                            "New connection with {}:{} - {peer_addr}",
-                            request.pod_name, request.host_port
-                        );
-                    }
-                    let api = api.clone();
-                    let request = Arc::clone(&request);
-                    tokio::spawn(async move {
-                        if let Err(e) = forward_connection(
-                            &api,
-                            &request.pod_name,
-                            request.pod_port,
-                            client_conn,
-                        )
-                        .await
-                        {
-                            error!("failed to forward connection: {e}");
-                        }
-                    });
-                    // keep the server running
-                    Ok(())
-                });
-
-            server.await?;
+            let mut delay = RESTART_DELAY;
+            let mut num_failures = 0;
+            loop {
+                let started_at = Instant::now();
+                let err = match serve(addr, Arc::clone(&resolver)).await {
+                    Ok(()) => break,
+                    Err(err) => err,
+                };
+                request.stats.set_last_error(&err);
+
+                if started_at.elapsed() > MAX_RESTART_DELAY {
+                    delay = RESTART_DELAY;
+                    num_failures = 0;
+                }
+                num_failures += 1;
+                if num_failures > MAX_RESTARTS {
+                    error!(
+                        "Port forwarding {}:{} -> {} failed after {MAX_RESTARTS} restarts: {err}; \
+                         giving up",
+                        request.host, request.host_port, request.target,
+                    );
+                    request.stats.failed.store(true, Ordering::Relaxed);
+                    break;
+                }
+
+                error!(
+                    "Port forwarding {}:{} -> {} failed: {err}; restarting in {}s",
+                    request.host,
+                    request.host_port,
+                    request.target,
+                    delay.as_secs()
+                );
+                request.stats.num_restarts.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RESTART_DELAY);
+            }
+
             map.write().await.remove(&request.id);
             Ok(())
         });
@@ -101,20 +133,253 @@ impl PortForwarder {
     }
 }
 
-async fn forward_connection(
-    pods: &Api<Pod>,
-    pod_name: &str,
-    port: u16,
-    mut client_conn: impl AsyncRead + AsyncWrite + Unpin,
-) -> anyhow::Result<()> {
-    let mut forwarder = pods.portforward(pod_name, &[port]).await?;
-    let mut upstream_conn = forwarder
-        .take_stream(port)
-        .to_log_error(|| format!("For pod {pod_name}:{port} port not found in forwarder"))?;
-    tokio::io::copy_bidirectional(&mut client_conn, &mut upstream_conn).await?;
-    drop(upstream_conn);
-    forwarder.join().await?;
-
-    warn!("Connection with pod {pod_name}:{port} closed");
+/// Accepts connections until ctrl+c is received; returns an error if the listener fails.
+async fn serve(addr: SocketAddr, resolver: Arc<TargetResolver>) -> anyhow::Result<()> {
+    TcpListenerStream::new(TcpListener::bind(addr).await?)
+        .take_until(tokio::signal::ctrl_c())
+        .try_for_each(|client_conn| async {
+            let request = Arc::clone(&resolver.request);
+            if let Ok(peer_addr) = client_conn.peer_addr() {
+                info!(
+                    "New connection with {}:{} - {peer_addr}",
+                    request.target, request.host_port
+                );
+            }
+
+            let resolver = Arc::clone(&resolver);
+            tokio::spawn(async move {
+                let stats = &request.stats;
+                stats.num_connections.fetch_add(1, Ordering::Relaxed);
+                stats.num_active_connections.fetch_add(1, Ordering::Relaxed);
+
+                if let Err(e) = resolver.forward_connection(client_conn).await {
+                    error!("failed to forward connection: {e}");
+                    stats.set_last_error(&e);
+                }
+
+                stats.num_active_connections.fetch_sub(1, Ordering::Relaxed);
+            });
+            // keep the server running
+            Ok(())
+        })
+        .await?;
+
     Ok(())
 }
+
+#[derive(Debug, Clone)]
+struct ResolvedTarget {
+    pod_name: String,
+    port: u16,
+}
+
+/// Resolves a port forwarding target to a pod and its port. The resolved pod is cached and
+/// re-resolved when a connection to it cannot be established (i.e. the pod died).
+struct TargetResolver {
+    client: Client,
+    request: Arc<PortForwardRequest>,
+    resolved: RwLock<Option<ResolvedTarget>>,
+}
+
+impl TargetResolver {
+    fn new(client: &Client, request: Arc<PortForwardRequest>) -> Self {
+        Self {
+            client: client.clone(),
+            request,
+            resolved: RwLock::new(None),
+        }
+    }
+
+    async fn get(&self) -> anyhow::Result<ResolvedTarget> {
+        if let Some(resolved) = self.resolved.read().await.clone() {
+            return Ok(resolved);
+        }
+
+        let resolved = self.resolve().await?;
+        info!(
+            "Resolved {} to pod {}:{}",
+            self.request.target, resolved.pod_name, resolved.port
+        );
+        *self.resolved.write().await = Some(resolved.clone());
+        Ok(resolved)
+    }
+
+    async fn resolve(&self) -> anyhow::Result<ResolvedTarget> {
+        let namespace = &self.request.namespace;
+        let remote_port = self.request.remote_port;
+
+        match &self.request.target {
+            PortForwardTarget::Pod(name) => Ok(ResolvedTarget {
+                pod_name: name.clone(),
+                port: remote_port,
+            }),
+            PortForwardTarget::Service(name) => {
+                let api: Api<Service> = Api::namespaced(self.client.clone(), namespace);
+                let spec = api
+                    .get(name)
+                    .await?
+                    .spec
+                    .with_context(|| format!("Service {name} has no spec"))?;
+
+                let selector = spec
+                    .selector
+                    .filter(|selector| !selector.is_empty())
+                    .with_context(|| format!("Service {name} has no selector"))?;
+
+                let target_port = spec
+                    .ports
+                    .into_iter()
+                    .flatten()
+                    .find(|port| port.port == remote_port as i32)
+                    .map(|port| port.target_port.unwrap_or(IntOrString::Int(port.port)))
+                    .unwrap_or(IntOrString::Int(remote_port as i32));
+
+                let pod = self.find_ready_pod(&selector).await?;
+                let port = resolve_target_port(&pod, &target_port)?;
+
+                Ok(ResolvedTarget {
+                    pod_name: pod.name_any(),
+                    port,
+                })
+            }
+            PortForwardTarget::Deployment(name) => {
+                let api: Api<Deployment> = Api::namespaced(self.client.clone(), namespace);
+                let selector = api
+                    .get(name)
+                    .await?
+                    .spec
+                    .and_then(|spec| spec.selector.match_labels)
+                    .filter(|selector| !selector.is_empty())
+                    .with_context(|| format!("Deployment {name} has no selector labels"))?;
+
+                let pod = self.find_ready_pod(&selector).await?;
+
+                Ok(ResolvedTarget {
+                    pod_name: pod.name_any(),
+                    port: remote_port,
+                })
+            }
+        }
+    }
+
+    async fn find_ready_pod(&self, selector: &BTreeMap<String, String>) -> anyhow::Result<Pod> {
+        let label_selector = selector
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .join(",");
+
+        let api: Api<Pod> = Api::namespaced(self.client.clone(), &self.request.namespace);
+        let pods = api
+            .list(&ListParams::default().labels(&label_selector))
+            .await?;
+
+        pods.items
+            .into_iter()
+            .find(|pod| pod.is_ready())
+            .with_context(|| format!("No ready pods found for selector {label_selector}"))
+    }
+
+    async fn forward_connection(
+        &self,
+        mut client_conn: impl AsyncRead + AsyncWrite + Unpin,
+    ) -> anyhow::Result<()> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.request.namespace);
+
+        let mut target = self.get().await?;
+        let mut forwarder = match pods.portforward(&target.pod_name, &[target.port]).await {
+            Ok(forwarder) => forwarder,
+            Err(err) if !matches!(self.request.target, PortForwardTarget::Pod(_)) => {
+                warn!(
+                    "Failed to connect to pod {} of {}: {err}; re-resolving",
+                    target.pod_name, self.request.target
+                );
+                *self.resolved.write().await = None;
+                target = self.get().await?;
+                pods.portforward(&target.pod_name, &[target.port]).await?
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let ResolvedTarget { pod_name, port } = target;
+
+        let upstream_conn = forwarder
+            .take_stream(port)
+            .to_log_error(|| format!("For pod {pod_name}:{port} port not found in forwarder"))?;
+        // bytes are counted as they are read, so the stats are live and include the bytes of
+        // connections that end with an error
+        let stats = &self.request.stats;
+        let mut client_conn = CountingStream::new(&mut client_conn, &stats.bytes_sent);
+        let mut upstream_conn = CountingStream::new(upstream_conn, &stats.bytes_received);
+        tokio::io::copy_bidirectional(&mut client_conn, &mut upstream_conn).await?;
+        drop(upstream_conn);
+        forwarder.join().await?;
+
+        warn!("Connection with pod {pod_name}:{port} closed");
+        Ok(())
+    }
+}
+
+/// Adds the number of bytes read from the inner stream to a counter.
+struct CountingStream<'a, S> {
+    inner: S,
+    num_read: &'a AtomicU64,
+}
+
+impl<'a, S> CountingStream<'a, S> {
+    fn new(inner: S, num_read: &'a AtomicU64) -> Self {
+        Self { inner, num_read }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for CountingStream<'_, S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let num_filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        let num_read = buf.filled().len() - num_filled;
+        self.num_read.fetch_add(num_read as u64, Ordering::Relaxed);
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for CountingStream<'_, S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+fn resolve_target_port(pod: &Pod, target_port: &IntOrString) -> anyhow::Result<u16> {
+    match target_port {
+        IntOrString::Int(port) => Ok(*port as u16),
+        IntOrString::String(name) => pod
+            .spec
+            .as_ref()
+            .into_iter()
+            .flat_map(|spec| spec.containers.iter())
+            .flat_map(|container| container.ports.iter().flatten())
+            .find(|port| port.name.as_deref() == Some(name.as_str()))
+            .map(|port| port.container_port as u16)
+            .with_context(|| format!("Port {name} not found in pod {}", pod.name_any())),
+    }
+}
@@ -3,7 +3,10 @@ use std::path::{Path, PathBuf};
 use clap::Parser;
 use cursive::reexports::log::{error, info};
 
-use crate::config::kgv_configuration::KgvConfiguration;
+use crate::config::default_config::materialize_default_config;
+use crate::config::kgv_configuration::{KgvConfiguration, LogDefaults};
+use crate::config::settings::{ConfigFile, Settings};
+use crate::eval::engine_factory::ScriptLimits;
 use crate::util::error::KgvError;
 use crate::util::paths::SELF_NAME;
 
@@ -32,42 +35,80 @@ pub(crate) struct Args {
     logs_dir: Option<PathBuf>,
 
     /// A directory where kgv rhai modules are located. If not specified, it will be set to
-    /// [<kgv-home-dir>/modules].
+    /// [<kgv-home-dir>/modules] and <project>/.kgv/modules. Modules from later dirs take
+    /// precedence over the earlier ones and the embedded defaults.
     #[arg(long)]
     module_dirs: Option<Vec<PathBuf>>,
 
     /// A list of directories where kgv list view column definitions are described. If not specified,
-    /// it will be set to <kgv-home-dir>/views/list.
+    /// it will be set to <kgv-home-dir>/views/list and <project>/.kgv/views/list. Configs from
+    /// later dirs override the earlier ones and the embedded defaults.
     #[arg(long)]
     extractor_dirs: Option<Vec<PathBuf>>,
 
-    /// A list of directories where kgv detail views are described. If not specified,
-    /// it will be set to <kgv-home-dir>/views/detail.
+    /// Do not load the default views and modules embedded into kgv, which are written to
+    /// <kgv-home-dir>/defaults otherwise.
+    #[arg(long, default_value_t = false)]
+    no_default_config: bool,
+
+    /// A list of directories where kgv detail view templates, their partials and helpers are
+    /// looked up. If not specified, it will be set to <kgv-home-dir>/views/detail and
+    /// <project>/.kgv/views/detail. Files from later dirs override the earlier ones and the
+    /// embedded defaults.
     #[arg(long)]
     detail_template_dirs: Option<Vec<PathBuf>>,
 
-    /// Number of tokio worker threads used to communicate with k8s cluster.
-    #[arg(long, default_value_t = 4)]
-    num_tokio_backend_threads: usize,
+    /// A profile of <kgv-home-dir>/config.yaml overriding its top-level settings. Flags given on
+    /// the command line override both.
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Number of tokio worker threads used to communicate with k8s cluster. Defaults to 4.
+    #[arg(long)]
+    num_tokio_backend_threads: Option<usize>,
 
     /// Number of rhai engine evaluator threads. Each engine instance created per thread.
-    #[arg(long, default_value_t = 8)]
-    num_evaluator_threads: usize,
+    /// Defaults to 8.
+    #[arg(long)]
+    num_evaluator_threads: Option<usize>,
 
-    /// Number of dispatcher threads. Each thread handles a signal
-    #[arg(long, default_value_t = 4)]
-    num_dispatcher_threads: usize,
+    /// Number of dispatcher threads. Each thread handles a signal. Defaults to 4.
+    #[arg(long)]
+    num_dispatcher_threads: Option<usize>,
 
-    /// Accept invalid certs
-    #[arg(long, default_value_t = false)]
-    accept_invalid_certs: bool,
+    /// Accept invalid certs, `--accept-invalid-certs=false` overrides the config file.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    accept_invalid_certs: Option<bool>,
+
+    /// Port forward profiles from <kgv-home-dir>/port-forwards.yaml to start on launch.
+    #[arg(long = "forward")]
+    forward_profiles: Vec<String>,
+
+    /// How long deleted resources stay visible in list views, in minutes. Defaults to 5.
+    #[arg(long)]
+    tombstone_retention_minutes: Option<i64>,
+
+    /// Number of revisions kept per resource for the diff view, 0 disables the history.
+    /// Defaults to 0.
+    #[arg(long)]
+    revision_history_size: Option<usize>,
+
+    /// How often pod and node usage is polled from metrics.k8s.io, in seconds, 0 disables
+    /// polling. Defaults to 30.
+    #[arg(long)]
+    metrics_poll_interval_seconds: Option<u64>,
 
     #[command(subcommand)]
     action: Option<Action>,
 }
 
-#[derive(clap::Subcommand, Debug)]
-enum Action {
+#[derive(clap::Subcommand, Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Action {
     /// Generate a configuration for a Group Version Kind with defaults
     Generate {},
     /// Run extraction on a given entity using current configuration
@@ -76,6 +117,12 @@ enum Action {
         #[arg(long)]
         fixture: PathBuf,
     },
+    /// Validate extractor configs and their scripts, templates and helpers, exit with a
+    /// non-zero code if any errors are found
+    Check {},
+    /// Run `tests` of extractor configs and `*.test.yaml` files against their fixtures,
+    /// exit with a non-zero code if any of them fail
+    Test {},
 }
 
 fn get_home_dir() -> PathBuf {
@@ -92,6 +139,36 @@ pub(crate) fn get_cache_dir(kgv_home_dir: &Path) -> PathBuf {
     kgv_home_dir.join("cache")
 }
 
+pub(crate) fn get_port_forward_profiles_path(kgv_home_dir: &Path) -> PathBuf {
+    kgv_home_dir.join("port-forwards.yaml")
+}
+
+pub(crate) fn get_render_profiles_path(kgv_home_dir: &Path) -> PathBuf {
+    kgv_home_dir.join("render-profiles.yaml")
+}
+
+pub(crate) fn get_sort_state_path(kgv_home_dir: &Path) -> PathBuf {
+    kgv_home_dir.join("sort-state.yaml")
+}
+
+pub(crate) fn get_config_file_path(kgv_home_dir: &Path) -> PathBuf {
+    kgv_home_dir.join("config.yaml")
+}
+
+pub(crate) fn get_default_config_dir(kgv_home_dir: &Path) -> PathBuf {
+    kgv_home_dir.join("defaults")
+}
+
+/// The closest `.kgv` directory of the current directory or its ancestors, other than the
+/// kgv home dir.
+fn find_project_dir(kgv_home_dir: &Path) -> Option<PathBuf> {
+    let current_dir = std::env::current_dir().ok()?;
+    current_dir
+        .ancestors()
+        .map(|dir| dir.join(".kgv"))
+        .find(|dir| dir.is_dir() && dir != kgv_home_dir)
+}
+
 pub(crate) fn get_module_dirs(kgv_home_dir: &Path) -> Vec<PathBuf> {
     vec![kgv_home_dir.join("modules")]
 }
@@ -100,22 +177,91 @@ pub(crate) fn get_col_def_dirs(kgv_home_dir: &Path) -> Vec<PathBuf> {
     vec![kgv_home_dir.join("views").join("list")]
 }
 
+pub(crate) fn get_detail_template_dirs(kgv_home_dir: &Path) -> Vec<PathBuf> {
+    vec![kgv_home_dir.join("views").join("detail")]
+}
+
+pub(crate) fn get_theme_dirs(kgv_home_dir: &Path) -> Vec<PathBuf> {
+    vec![kgv_home_dir.join("themes")]
+}
+
 impl TryFrom<Args> for KgvConfiguration {
     type Error = KgvError;
 
     fn try_from(value: Args) -> Result<Self, Self::Error> {
         let home_dir = value.home_dir;
+        let port_forward_profiles_path = get_port_forward_profiles_path(&home_dir);
+        let render_profiles_path = get_render_profiles_path(&home_dir);
+        let sort_state_path = get_sort_state_path(&home_dir);
         let cache_dir = value.cache_dir.unwrap_or_else(|| get_cache_dir(&home_dir));
         let logs_dir = value.logs_dir.unwrap_or_else(|| get_logs_dir(&home_dir));
-        let module_dirs = value
-            .module_dirs
-            .unwrap_or_else(|| get_module_dirs(&home_dir));
-        let extractor_dirs = value
-            .extractor_dirs
-            .unwrap_or_else(|| get_col_def_dirs(&home_dir));
-        // let detail_template_dirs = value
-        //     .detail_template_dirs
-        //     .unwrap_or_else(|| get_detail_templates_dirs(&home_dir));
+
+        let cli_settings = Settings {
+            num_tokio_backend_threads: value.num_tokio_backend_threads,
+            num_evaluator_threads: value.num_evaluator_threads,
+            num_dispatcher_threads: value.num_dispatcher_threads,
+            accept_invalid_certs: value.accept_invalid_certs,
+            tombstone_retention_minutes: value.tombstone_retention_minutes,
+            revision_history_size: value.revision_history_size,
+            metrics_poll_interval_seconds: value.metrics_poll_interval_seconds,
+            ..Settings::default()
+        };
+        // every setting is set by the defaults
+        let settings = ConfigFile::load(&get_config_file_path(&home_dir))?
+            .resolve(value.profile.as_deref())?
+            .merge(cli_settings);
+        let metrics_poll_interval_seconds =
+            settings.metrics_poll_interval_seconds.unwrap_or_default();
+
+        let default_config_dir = get_default_config_dir(&home_dir);
+        let default_config_dir = if value.no_default_config {
+            None
+        } else {
+            match materialize_default_config(&default_config_dir) {
+                Ok(()) => Some(default_config_dir),
+                Err(err) => {
+                    error!(
+                        "Failed to write the default config to {}: {err}",
+                        default_config_dir.display()
+                    );
+                    None
+                }
+            }
+        };
+        let project_dir = find_project_dir(&home_dir);
+        if let Some(project_dir) = &project_dir {
+            info!("Using project config dir {}", project_dir.display());
+        }
+
+        let module_dirs = layered_dirs(
+            default_config_dir.as_deref(),
+            value.module_dirs,
+            &home_dir,
+            project_dir.as_deref(),
+            get_module_dirs,
+        );
+        let extractor_dirs = layered_dirs(
+            default_config_dir.as_deref(),
+            value.extractor_dirs,
+            &home_dir,
+            project_dir.as_deref(),
+            get_col_def_dirs,
+        );
+        let detail_template_dirs = layered_dirs(
+            default_config_dir.as_deref(),
+            value.detail_template_dirs,
+            &home_dir,
+            project_dir.as_deref(),
+            get_detail_template_dirs,
+        );
+
+        let theme_dirs = layered_dirs(
+            default_config_dir.as_deref(),
+            None,
+            &home_dir,
+            project_dir.as_deref(),
+            get_theme_dirs,
+        );
 
         // let home_dir = wrap_opt(home_dir, "home dir");
         let logs_dir = wrap_opt(logs_dir, "logs dir");
@@ -123,21 +269,74 @@ impl TryFrom<Args> for KgvConfiguration {
 
         let module_dirs = create_dirs(module_dirs, "module dirs");
         let extractor_dirs = create_dirs(extractor_dirs, "extractor dirs");
-        // let detail_template_dirs = create_dirs(detail_template_dirs, "detail template dirs");
+        let detail_template_dirs = create_dirs(detail_template_dirs, "detail template dirs");
+        let theme_dirs = create_dirs(theme_dirs, "theme dirs");
 
         Ok(Self {
             cache_dir,
             logs_dir,
             module_dirs,
             extractor_dirs,
-            num_tokio_backend_threads: value.num_tokio_backend_threads,
-            num_evaluator_threads: value.num_evaluator_threads,
-            accept_invalid_certs: value.accept_invalid_certs,
-            num_dispatcher_threads: value.num_dispatcher_threads,
+            detail_template_dirs,
+            num_tokio_backend_threads: settings.num_tokio_backend_threads.unwrap_or_default(),
+            num_evaluator_threads: settings.num_evaluator_threads.unwrap_or_default(),
+            accept_invalid_certs: settings.accept_invalid_certs.unwrap_or_default(),
+            num_dispatcher_threads: settings.num_dispatcher_threads.unwrap_or_default(),
+            port_forward_profiles_path,
+            forward_profiles: value.forward_profiles,
+            tombstone_retention: chrono::Duration::minutes(
+                settings.tombstone_retention_minutes.unwrap_or_default(),
+            ),
+            revision_history_size: settings.revision_history_size.unwrap_or_default(),
+            render_profiles_path,
+            sort_state_path,
+            metrics_poll_interval: (metrics_poll_interval_seconds > 0)
+                .then(|| std::time::Duration::from_secs(metrics_poll_interval_seconds)),
+            fps: settings.fps.unwrap_or_default(),
+            theme_dirs,
+            theme: settings.theme.unwrap_or_default(),
+            script_limits: ScriptLimits {
+                max_operations: settings.script_max_operations.unwrap_or_default(),
+                max_call_levels: settings.script_max_call_depth.unwrap_or_default(),
+                max_string_size: settings.script_max_string_size.unwrap_or_default(),
+                max_array_size: settings.script_max_array_size.unwrap_or_default(),
+                max_map_size: settings.script_max_map_size.unwrap_or_default(),
+                timeout: settings
+                    .script_timeout_millis
+                    .filter(|millis| *millis > 0)
+                    .map(std::time::Duration::from_millis),
+            },
+            log_defaults: LogDefaults {
+                since_seconds: settings.log_since_seconds.filter(|seconds| *seconds > 0),
+                tail_lines: settings.log_tail_lines.filter(|lines| *lines > 0),
+            },
+            action: value.action,
         })
     }
 }
 
+/// Lower layers come first: the embedded defaults, the user dirs (or the dirs given on the
+/// command line instead of the user and project dirs) and the project ones.
+fn layered_dirs(
+    default_config_dir: Option<&Path>,
+    dirs: Option<Vec<PathBuf>>,
+    home_dir: &Path,
+    project_dir: Option<&Path>,
+    get_dirs: fn(&Path) -> Vec<PathBuf>,
+) -> Vec<PathBuf> {
+    let mut layers = default_config_dir.map(get_dirs).unwrap_or_default();
+    match dirs {
+        Some(dirs) => layers.extend(dirs),
+        None => {
+            layers.extend(get_dirs(home_dir));
+            if let Some(project_dir) = project_dir {
+                layers.extend(get_dirs(project_dir).into_iter().filter(|dir| dir.is_dir()));
+            }
+        }
+    }
+    layers
+}
+
 fn wrap_opt(dir: PathBuf, name: &str) -> Option<PathBuf> {
     if create_dir(&dir, name) {
         Some(dir)
@@ -0,0 +1,658 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use kube::api::GroupVersionKind;
+use rhai::{Engine, Scope};
+
+use crate::config::detail_templates::DetailTemplates;
+use crate::config::extractor::{
+    is_test_suite_file, ActionType, ColumnConfigProps, EvalConfigProps, EventHandlerType,
+    PseudoResourceExtractorConfigPros, ResourceConfigProps,
+};
+use crate::eval::engine_factory::{build_engine, ScriptLimits};
+use crate::model::metrics::SharedMetricsStore;
+use crate::model::pseudo_resource::PSEUDO_RESOURCE_JOIN_SEQ;
+use crate::traits::ext::engine::{locate_script_error, prepend_imports};
+use crate::traits::ext::gvk::GvkNameExt;
+use crate::util::fs::scan_files;
+use crate::util::paths::resolve_path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Diagnostic {
+    pub(crate) path: PathBuf,
+    /// 1-based.
+    pub(crate) line: Option<usize>,
+    /// 1-based.
+    pub(crate) column: Option<usize>,
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+            if let Some(column) = self.column {
+                write!(f, ":{column}")?;
+            }
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, ": {severity}: {}", self.message)
+    }
+}
+
+/// Finds the lines YAML keys are declared on. serde_yaml does not keep the positions of
+/// parsed values, so keys are looked up in the source text in the order they are declared.
+struct SourceLines<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl<'a> SourceLines<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().collect(),
+        }
+    }
+
+    /// Returns the 1-based line of the first `key:` (or `key: value`) at or after the
+    /// given line.
+    fn find(&self, key: &str, value: Option<&str>, from: usize) -> Option<usize> {
+        let prefix = format!("{key}:");
+        self.lines
+            .iter()
+            .enumerate()
+            .skip(from.saturating_sub(1))
+            .find(|(_, line)| {
+                let line = line.trim_start().trim_start_matches("- ").trim_start();
+                let rest = match line.strip_prefix(&prefix) {
+                    Some(rest) => rest.trim(),
+                    None => return false,
+                };
+                match value {
+                    Some(value) => rest.trim_matches(|c| c == '"' || c == '\'') == value,
+                    None => true,
+                }
+            })
+            .map(|(index, _)| index + 1)
+    }
+
+    fn find_top_level(&self, key: &str) -> Option<usize> {
+        let prefix = format!("{key}:");
+        self.lines
+            .iter()
+            .position(|line| line.starts_with(&prefix))
+            .map(|index| index + 1)
+    }
+
+    /// The line the value of a `key: value` scalar starts on; block scalars start on the
+    /// next line.
+    fn value_start(&self, key_line: usize) -> usize {
+        let line = self.lines.get(key_line - 1).copied().unwrap_or_default();
+        let value = line.split_once(':').map(|(_, value)| value.trim());
+        match value {
+            Some(value) if value.is_empty() || value.starts_with('|') || value.starts_with('>') => {
+                key_line + 1
+            }
+            _ => key_line,
+        }
+    }
+}
+
+struct Location {
+    path: PathBuf,
+    line: Option<usize>,
+    /// The index of the extractor dir, configs of later dirs override the earlier ones.
+    layer: usize,
+}
+
+/// Validates extractor configs the way `ExtractorConfig::new` loads them, but reports
+/// every problem instead of logging and skipping it.
+pub(crate) struct ConfigChecker {
+    engine: Engine,
+    detail_templates: DetailTemplates,
+    diagnostics: Vec<Diagnostic>,
+    columns_origins: HashMap<GroupVersionKind, Location>,
+    num_files: usize,
+    layer: usize,
+}
+
+impl ConfigChecker {
+    pub(crate) fn new(module_dirs: &[PathBuf], detail_template_dirs: &[PathBuf]) -> Self {
+        Self {
+            engine: build_engine(
+                module_dirs,
+                &SharedMetricsStore::default(),
+                &ScriptLimits::default(),
+            ),
+            detail_templates: DetailTemplates::new(detail_template_dirs),
+            diagnostics: vec![],
+            columns_origins: HashMap::default(),
+            num_files: 0,
+            layer: 0,
+        }
+    }
+
+    pub(crate) fn check(mut self, extractor_dirs: &[PathBuf]) -> (usize, Vec<Diagnostic>) {
+        for error in std::mem::take(&mut self.detail_templates.errors) {
+            self.report(Severity::Error, &error.path, None, None, error.message);
+        }
+
+        for (layer, dir) in extractor_dirs.iter().enumerate() {
+            self.layer = layer;
+            let mut files = scan_files(&[dir.clone()])
+                .into_iter()
+                .filter(|file| {
+                    file.extension()
+                        .map(|ext| ext == "yaml" || ext == "yml")
+                        .unwrap_or(false)
+                        && !is_test_suite_file(file)
+                })
+                .collect::<Vec<_>>();
+            // the last registration wins in `ExtractorConfig`, make the report stable
+            files.sort();
+
+            for file in files {
+                self.num_files += 1;
+                self.check_file(&file);
+            }
+        }
+
+        (self.num_files, self.diagnostics)
+    }
+
+    fn report(
+        &mut self,
+        severity: Severity,
+        path: &Path,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            column,
+            severity,
+            message: message.into(),
+        });
+    }
+
+    fn check_file(&mut self, path: &Path) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                self.report(Severity::Error, path, None, None, format!("{err}"));
+                return;
+            }
+        };
+
+        let props: ResourceConfigProps = match serde_yaml::from_str(&content) {
+            Ok(props) => props,
+            Err(err) => {
+                let location = err.location();
+                self.report(
+                    Severity::Error,
+                    path,
+                    location.as_ref().map(|location| location.line()),
+                    location.as_ref().map(|location| location.column()),
+                    format!("{err}"),
+                );
+                return;
+            }
+        };
+
+        let lines = SourceLines::new(&content);
+        let resource_line = lines.find_top_level("resource");
+        if props.overlay {
+            self.check_overlay(path, resource_line, &props);
+        } else {
+            self.register_columns_origin(&props.resource, path, resource_line);
+            if !props.remove_columns.is_empty() {
+                self.report(
+                    Severity::Warning,
+                    path,
+                    lines.find_top_level("remove_columns"),
+                    None,
+                    "remove_columns is only used by overlays",
+                );
+            }
+        }
+
+        let columns_line = lines.find_top_level("columns").unwrap_or(1);
+        self.check_columns(path, &lines, &props.columns, &props.imports, columns_line);
+
+        let pseudo_line = lines.find_top_level("pseudo_resources").unwrap_or(1);
+        self.check_pseudo_resources(
+            path,
+            &lines,
+            &props.resource,
+            &props.pseudo_resources,
+            &props.imports,
+            pseudo_line,
+        );
+        self.check_events(
+            path,
+            lines.find_top_level("events"),
+            &props.events,
+            &props.pseudo_resources,
+        );
+
+        let mut line = lines.find_top_level("related").unwrap_or(1);
+        for related in &props.related {
+            line = lines
+                .find("name", Some(&related.name), line)
+                .unwrap_or(line);
+            let script_line = lines.find("script_content", None, line);
+            self.check_script(
+                path,
+                &lines,
+                script_line,
+                &related.script_content,
+                &props.imports,
+                &format!("related `{}`", related.name),
+            );
+        }
+
+        if let Some(default_sort) = &props.default_sort {
+            if !props
+                .columns
+                .iter()
+                .any(|column| column.name == default_sort.column)
+            {
+                self.report(
+                    Severity::Warning,
+                    path,
+                    lines.find_top_level("default_sort"),
+                    None,
+                    format!(
+                        "default sort column `{}` does not exist",
+                        default_sort.column
+                    ),
+                );
+            }
+        }
+
+        if let Some(details) = &props.details {
+            let line = lines.find(
+                "template",
+                None,
+                lines.find_top_level("details").unwrap_or(1),
+            );
+            let template_path = self.detail_templates.resolve(path, &details.template);
+            if !template_path.is_file() {
+                self.report(
+                    Severity::Error,
+                    path,
+                    line,
+                    None,
+                    format!(
+                        "detail template {} is neither in the detail template dirs nor at {}",
+                        details.template.display(),
+                        template_path.display()
+                    ),
+                );
+            }
+            for helper in &details.helpers {
+                let helper_path = self.detail_templates.resolve(&template_path, &helper.path);
+                if !helper_path.is_file() {
+                    let line = lines.find("name", Some(&helper.name), line.unwrap_or(1));
+                    self.report(
+                        Severity::Error,
+                        path,
+                        line,
+                        None,
+                        format!(
+                            "helper `{}` script {} does not exist",
+                            helper.name,
+                            helper_path.display()
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut line = lines.find_top_level("tests").unwrap_or(1);
+        for test in &props.tests {
+            line = lines.find("name", Some(&test.name), line).unwrap_or(line);
+            let fixture = resolve_path(path, &test.fixture);
+            if !fixture.is_file() {
+                self.report(
+                    Severity::Error,
+                    path,
+                    Some(line),
+                    None,
+                    format!(
+                        "test `{}` fixture {} does not exist",
+                        test.name,
+                        fixture.display()
+                    ),
+                );
+            }
+        }
+    }
+
+    fn register_columns_origin(
+        &mut self,
+        gvk: &GroupVersionKind,
+        path: &Path,
+        line: Option<usize>,
+    ) {
+        if let Some(origin) = self
+            .columns_origins
+            .get(gvk)
+            .filter(|origin| origin.layer == self.layer)
+        {
+            let origin_line = origin
+                .line
+                .map(|line| format!(":{line}"))
+                .unwrap_or_default();
+            let message = format!(
+                "{} is already registered in {}{origin_line} and replaces it",
+                gvk.full_name(),
+                origin.path.display()
+            );
+            self.report(Severity::Error, path, line, None, message);
+            return;
+        }
+        self.columns_origins.insert(
+            gvk.clone(),
+            Location {
+                path: path.to_path_buf(),
+                line,
+                layer: self.layer,
+            },
+        );
+    }
+
+    /// An overlay needs a lower layer config to merge into.
+    fn check_overlay(&mut self, path: &Path, line: Option<usize>, props: &ResourceConfigProps) {
+        if !self.columns_origins.contains_key(&props.resource) {
+            let message = format!(
+                "overlay of {} has no config in a lower layer to merge into",
+                props.resource.full_name()
+            );
+            self.report(Severity::Warning, path, line, None, message);
+        }
+    }
+
+    fn check_columns(
+        &mut self,
+        path: &Path,
+        lines: &SourceLines,
+        columns: &[ColumnConfigProps],
+        imports: &[String],
+        from_line: usize,
+    ) {
+        let mut line = from_line;
+        for column in columns {
+            line = lines.find("name", Some(&column.name), line).unwrap_or(line);
+            let context = format!("column `{}`", column.name);
+            match &column.evaluator {
+                EvalConfigProps::ScriptContent { content } => {
+                    let content_line = lines.find("content", None, line);
+                    self.check_script(path, lines, content_line, content, imports, &context);
+                }
+                EvalConfigProps::ScriptPath { path: script_path } => {
+                    let path_line = lines.find("path", None, line);
+                    let script_path = resolve_path(path, script_path);
+                    match std::fs::read_to_string(&script_path) {
+                        Ok(script) => self.check_script_file(&script_path, &script, imports),
+                        Err(err) => self.report(
+                            Severity::Error,
+                            path,
+                            path_line,
+                            None,
+                            format!("{context}: cannot read {}: {err}", script_path.display()),
+                        ),
+                    }
+                }
+                EvalConfigProps::Embedded { .. } => {}
+            }
+        }
+    }
+
+    fn check_pseudo_resources(
+        &mut self,
+        path: &Path,
+        lines: &SourceLines,
+        parent_gvk: &GroupVersionKind,
+        pseudo_resources: &[PseudoResourceExtractorConfigPros],
+        imports: &[String],
+        from_line: usize,
+    ) {
+        let mut line = from_line;
+        for pseudo_resource in pseudo_resources {
+            line = lines
+                .find("name", Some(&pseudo_resource.name), line)
+                .unwrap_or(line);
+            let context = format!("pseudo resource `{}`", pseudo_resource.name);
+
+            let script_line = lines.find("script_content", None, line);
+            self.check_script(
+                path,
+                lines,
+                script_line,
+                &pseudo_resource.script_content,
+                imports,
+                &context,
+            );
+            if let Some(key) = &pseudo_resource.key {
+                let key_line = lines.find("key", None, line);
+                self.check_script(
+                    path,
+                    lines,
+                    key_line,
+                    key,
+                    imports,
+                    &format!("{context} key"),
+                );
+            }
+
+            let mut gvk = parent_gvk.clone();
+            gvk.kind =
+                [parent_gvk.kind.as_str(), &pseudo_resource.name].join(PSEUDO_RESOURCE_JOIN_SEQ);
+            if !pseudo_resource.columns.is_empty() {
+                self.register_columns_origin(&gvk, path, Some(line));
+                let columns_line = lines.find("columns", None, line).unwrap_or(line);
+                self.check_columns(path, lines, &pseudo_resource.columns, imports, columns_line);
+            }
+            self.check_events(
+                path,
+                lines.find("events", None, line),
+                &pseudo_resource.events,
+                &pseudo_resource.pseudo_resources,
+            );
+            let children_line = lines.find("pseudo_resources", None, line).unwrap_or(line);
+            self.check_pseudo_resources(
+                path,
+                lines,
+                &gvk,
+                &pseudo_resource.pseudo_resources,
+                imports,
+                children_line,
+            );
+        }
+    }
+
+    fn check_events(
+        &mut self,
+        path: &Path,
+        line: Option<usize>,
+        events: &[EventHandlerType],
+        pseudo_resources: &[PseudoResourceExtractorConfigPros],
+    ) {
+        for event in events {
+            let action = match event {
+                EventHandlerType::Submit { action } | EventHandlerType::Delete { action } => action,
+            };
+            if let ActionType::ShowDetailsTable(name) = action {
+                if !pseudo_resources.iter().any(|pseudo| &pseudo.name == name) {
+                    self.report(
+                        Severity::Error,
+                        path,
+                        line,
+                        None,
+                        format!("event refers to an unknown pseudo resource `{name}`"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Compiles a script embedded into the YAML file resolving its imports. `key_line` is
+    /// the line of the key the script is the value of.
+    fn check_script(
+        &mut self,
+        path: &Path,
+        lines: &SourceLines,
+        key_line: Option<usize>,
+        script: &str,
+        imports: &[String],
+        context: &str,
+    ) {
+        let (script_line, script_column, message) = match self.compile(script, imports) {
+            Ok(()) => return,
+            Err(err) => err,
+        };
+        let line = match (key_line, script_line) {
+            (Some(key_line), Some(script_line)) => {
+                Some(lines.value_start(key_line) + script_line - 1)
+            }
+            (key_line, _) => key_line,
+        };
+        let position = match (script_line, script_column) {
+            (Some(line), Some(column)) => format!(" (script line {line}, position {column})"),
+            _ => String::new(),
+        };
+        self.report(
+            Severity::Error,
+            path,
+            line,
+            None,
+            format!("{context}: {message}{position}"),
+        );
+    }
+
+    fn check_script_file(&mut self, path: &Path, script: &str, imports: &[String]) {
+        if let Err((line, column, message)) = self.compile(script, imports) {
+            self.report(Severity::Error, path, line, column, message);
+        }
+    }
+
+    /// Returns the position of an error relative to the script; `None` if the error is
+    /// in the imports.
+    fn compile(
+        &self,
+        script: &str,
+        imports: &[String],
+    ) -> Result<(), (Option<usize>, Option<usize>, String)> {
+        let content = prepend_imports(script, imports);
+        let err = match self
+            .engine
+            .compile_into_self_contained(&Scope::new(), &content)
+        {
+            Ok(_) => return Ok(()),
+            Err(err) => err,
+        };
+
+        let (position, message) = locate_script_error(&err, imports);
+        Err((position.line(), position.position(), message))
+    }
+}
+
+/// Prints the diagnostics and returns the process exit code.
+pub(crate) fn run_check(
+    extractor_dirs: &[PathBuf],
+    module_dirs: &[PathBuf],
+    detail_template_dirs: &[PathBuf],
+) -> i32 {
+    let (num_files, diagnostics) =
+        ConfigChecker::new(module_dirs, detail_template_dirs).check(extractor_dirs);
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+
+    let num_errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    let num_warnings = diagnostics.len() - num_errors;
+    println!("Checked {num_files} files: {num_errors} errors, {num_warnings} warnings");
+
+    if num_errors > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_lines() {
+        let content = "resource:\n  kind: Pod\ncolumns:\n  - name: a\n    evaluator: !ScriptContent\n      content: |\n        x\n  - name: \"b\"\n";
+        let lines = SourceLines::new(content);
+        assert_eq!(lines.find_top_level("columns"), Some(3));
+        assert_eq!(lines.find("name", Some("b"), 3), Some(8));
+        assert_eq!(lines.find("content", None, 4), Some(6));
+        assert_eq!(lines.value_start(6), 7);
+        assert_eq!(lines.value_start(2), 2);
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = r#"resource:
+  group: ''
+  version: v1
+  kind: Pod
+events:
+  - !Submit
+    action: !ShowDetailsTable "missing"
+columns:
+  - name: ok
+    display_name: Ok
+    width: 0
+    evaluator: !ScriptContent
+      content: resource.metadata.name
+  - name: broken
+    display_name: Broken
+    width: 0
+    evaluator: !ScriptContent
+      content: "resource.metadata.name +"
+"#;
+        std::fs::write(dir.path().join("a.yaml"), content).unwrap();
+        std::fs::write(dir.path().join("b.yaml"), content.replace("broken", "fine")).unwrap();
+
+        let (num_files, diagnostics) =
+            ConfigChecker::new(&[], &[]).check(&[dir.path().to_path_buf()]);
+        assert_eq!(num_files, 2);
+
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+            .collect::<Vec<_>>();
+        assert!(messages
+            .iter()
+            .any(|(line, message)| *line == Some(18) && message.starts_with("column `broken`")));
+        assert!(messages
+            .iter()
+            .any(|(line, message)| *line == Some(1) && message.contains("already registered")));
+        assert!(messages
+            .iter()
+            .any(|(_, message)| message.contains("unknown pseudo resource `missing`")));
+    }
+}
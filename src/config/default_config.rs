@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::util::fs::scan_files;
+
+include!(concat!(env!("OUT_DIR"), "/default_config.rs"));
+
+/// Writes the embedded `default_config` into `dir`, the lowest config layer. Files are
+/// only rewritten if they differ, files the binary does not embed are removed.
+pub(crate) fn materialize_default_config(dir: &Path) -> std::io::Result<()> {
+    let mut expected: HashSet<PathBuf> = HashSet::default();
+    for (rel_path, content) in DEFAULT_CONFIG_FILES {
+        let path = dir.join(rel_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if std::fs::read(&path).ok().as_deref() != Some(*content) {
+            std::fs::write(&path, content)?;
+        }
+        expected.insert(path);
+    }
+
+    for path in scan_files(&[dir.to_path_buf()]) {
+        if !expected.contains(&path) {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_materialize() {
+        let dir = tempfile::tempdir().unwrap();
+        let stale = dir.path().join("views").join("list").join("stale.yaml");
+        std::fs::create_dir_all(stale.parent().unwrap()).unwrap();
+        std::fs::write(&stale, "").unwrap();
+
+        materialize_default_config(dir.path()).unwrap();
+
+        assert!(!stale.exists());
+        assert!(dir.path().join("views/list/pod.yaml").is_file());
+        assert!(dir.path().join("modules/pod.rhai").is_file());
+    }
+}
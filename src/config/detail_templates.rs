@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use handlebars::Handlebars;
+use rhai::Position;
+
+use crate::config::extractor::{ConfigError, DetailsTemplate};
+use crate::util::fs::scan_files;
+use crate::util::paths::resolve_path;
+
+/// Templates, partials and helpers of the detail template dirs by their path relative to the
+/// dir, i.e. `partials/kv_map.hbs`. Files of later dirs override the ones of earlier dirs.
+#[derive(Debug, Default)]
+pub(crate) struct DetailTemplates {
+    files: BTreeMap<String, PathBuf>,
+    /// Templates that failed to compile.
+    pub(crate) errors: Vec<ConfigError>,
+}
+
+impl DetailTemplates {
+    pub(crate) fn new(roots: &[PathBuf]) -> Self {
+        let mut instance = Self::default();
+        for root in roots {
+            let mut files = scan_files(std::slice::from_ref(root));
+            files.sort();
+            for file in files {
+                let name = file.strip_prefix(root).ok().and_then(relative_name);
+                if let Some(name) = name {
+                    instance.files.insert(name, file);
+                }
+            }
+        }
+
+        let mut errors = vec![];
+        for (name, path) in instance.templates() {
+            // partials are not resolved here, they are only looked up when rendering
+            if let Err(err) = Handlebars::new().register_template_file(name, path) {
+                ConfigError::report(
+                    &mut errors,
+                    path,
+                    format!("detail template {name}"),
+                    Position::NONE,
+                    err.to_string(),
+                );
+            }
+        }
+        instance.errors = errors;
+
+        instance
+    }
+
+    /// Looks up a path relative to the template dirs, `None` for absolute paths or paths
+    /// leaving the dirs.
+    pub(crate) fn find(&self, rel_path: &Path) -> Option<&Path> {
+        let name = relative_name(rel_path)?;
+        self.files.get(&name).map(PathBuf::as_path)
+    }
+
+    /// Resolves a path from the template dirs or, if none of them has it, relative to the
+    /// origin file.
+    pub(crate) fn resolve(&self, origin: &Path, rel_path: &Path) -> PathBuf {
+        match self.find(rel_path) {
+            Some(path) => path.to_path_buf(),
+            None => resolve_path(origin, rel_path),
+        }
+    }
+
+    pub(crate) fn resolve_template(&self, details: &DetailsTemplate) -> PathBuf {
+        match self.find(&details.name) {
+            Some(path) => path.to_path_buf(),
+            None => details.template.clone(),
+        }
+    }
+
+    /// Whether the template failed to compile.
+    pub(crate) fn is_broken(&self, path: &Path) -> bool {
+        self.errors.iter().any(|err| err.path == path)
+    }
+
+    /// All `.hbs` files by their partial names, i.e. `partials/kv_map`.
+    pub(crate) fn templates(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.files.iter().filter_map(|(name, path)| {
+            let name = name.strip_suffix(".hbs")?;
+            Some((name, path.as_path()))
+        })
+    }
+}
+
+fn relative_name(rel_path: &Path) -> Option<String> {
+    let mut parts = vec![];
+    for component in rel_path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers() {
+        let defaults = tempfile::tempdir().unwrap();
+        let user = tempfile::tempdir().unwrap();
+        std::fs::create_dir(defaults.path().join("partials")).unwrap();
+        std::fs::write(defaults.path().join("pod.hbs"), "{{> partials/meta }}").unwrap();
+        std::fs::write(defaults.path().join("partials/meta.hbs"), "default").unwrap();
+        std::fs::create_dir(user.path().join("partials")).unwrap();
+        std::fs::write(user.path().join("partials/meta.hbs"), "user").unwrap();
+        std::fs::write(user.path().join("broken.hbs"), "{{#if ready}}").unwrap();
+
+        let templates =
+            DetailTemplates::new(&[defaults.path().to_path_buf(), user.path().to_path_buf()]);
+
+        assert_eq!(
+            templates.find(Path::new("./pod.hbs")),
+            Some(defaults.path().join("pod.hbs").as_path())
+        );
+        assert_eq!(
+            templates.find(Path::new("partials/meta.hbs")),
+            Some(user.path().join("partials/meta.hbs").as_path())
+        );
+        assert_eq!(templates.find(Path::new("../pod.hbs")), None);
+        assert_eq!(
+            templates
+                .templates()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["broken", "partials/meta", "pod"]
+        );
+        assert_eq!(templates.errors.len(), 1);
+        assert_eq!(templates.errors[0].path, user.path().join("broken.hbs"));
+        assert!(templates.is_broken(&user.path().join("broken.hbs")));
+        assert!(!templates.is_broken(&defaults.path().join("pod.hbs")));
+    }
+}
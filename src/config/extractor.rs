@@ -1,15 +1,21 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use cursive::reexports::log::{error, info, warn};
+use handlebars::Handlebars;
 use kube::api::GroupVersionKind;
-use rhai::{Engine, AST};
+use rhai::{Engine, EvalAltResult, Position, AST};
 use serde::{Deserialize, Serialize};
 
-use crate::traits::ext::engine::EngineExt;
-use crate::traits::ext::gvk::GvkNameExt;
+use crate::config::detail_templates::DetailTemplates;
+use crate::eval::cell_style::ColumnStyleRule;
+use crate::model::pseudo_resource::PSEUDO_RESOURCE_JOIN_SEQ;
+use crate::traits::ext::engine::{locate_script_error, EngineExt};
+use crate::traits::ext::gvk::{GvkNameExt, PseudoResourceGvkExt};
 use crate::util::error::KgvError;
 use crate::util::fs::scan_files;
 use crate::util::paths::resolve_path;
@@ -21,7 +27,7 @@ pub(crate) enum ActionType {
     ShowDetailsTemplate,
 }
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub(crate) enum EventHandlerType {
     Submit { action: ActionType },
     Delete { action: ActionType },
@@ -33,13 +39,100 @@ pub(crate) struct ExtractorConfig {
     pub(crate) detail_templates_map: HashMap<GroupVersionKind, Arc<DetailsTemplate>>,
     pub(crate) pseudo_resources_map: HashMap<GroupVersionKind, Arc<Vec<PseudoResourceConf>>>,
     pub(crate) event_handler_types_map: HashMap<GroupVersionKind, Arc<Vec<EventHandlerType>>>,
+    pub(crate) default_sort_map: HashMap<GroupVersionKind, Arc<DefaultSort>>,
+    pub(crate) related_map: HashMap<GroupVersionKind, Arc<Vec<RelatedConf>>>,
+    /// Parts of the configs that failed to load and were skipped.
+    pub(crate) errors: Vec<ConfigError>,
+}
+
+/// A file, column, pseudo resource or template that failed to load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ConfigError {
+    pub(crate) path: PathBuf,
+    /// What failed, i.e. "column age".
+    pub(crate) context: String,
+    /// The position in the script (or in the YAML file for parse errors); `Position::NONE`
+    /// if unknown.
+    pub(crate) position: Position,
+    pub(crate) message: String,
+}
+
+impl ConfigError {
+    pub(crate) fn report(
+        errors: &mut Vec<ConfigError>,
+        path: &Path,
+        context: String,
+        position: Position,
+        message: String,
+    ) {
+        error!(
+            "Failed to process {context} in file {}: {message}",
+            path.display()
+        );
+        errors.push(ConfigError {
+            path: path.to_path_buf(),
+            context,
+            position,
+            message,
+        });
+    }
+
+    fn report_script(
+        errors: &mut Vec<ConfigError>,
+        path: &Path,
+        context: String,
+        err: &anyhow::Error,
+        imports: &[String],
+    ) {
+        let (position, message) = match err.downcast_ref::<EvalAltResult>() {
+            Some(err) => locate_script_error(err, imports),
+            None => (Position::NONE, err.to_string()),
+        };
+        Self::report(errors, path, context, position, message);
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.position.line() {
+            write!(f, ":{line}")?;
+            if let Some(position) = self.position.position() {
+                write!(f, ":{position}")?;
+            }
+        }
+        write!(f, ": {}: {}", self.context, self.message)
+    }
 }
 
 impl ExtractorConfig {
+    /// Helper scripts that exist neither in the detail template dirs nor next to their
+    /// templates. Checked separately as the template dirs are reloaded on their own.
+    pub(crate) fn missing_helpers(&self, detail_templates: &DetailTemplates) -> Vec<ConfigError> {
+        let mut errors = vec![];
+        for details in self.detail_templates_map.values() {
+            let template_path = detail_templates.resolve_template(details);
+            for helper in &details.helpers {
+                let helper_path = detail_templates.resolve(&template_path, &helper.path);
+                if !helper_path.is_file() {
+                    ConfigError::report(
+                        &mut errors,
+                        &details.config_path,
+                        format!("helper {}", helper.name),
+                        Position::NONE,
+                        format!("{} does not exist", helper_path.display()),
+                    );
+                }
+            }
+        }
+        errors.sort_by(|a, b| (&a.path, &a.context).cmp(&(&b.path, &b.context)));
+        errors
+    }
+
     pub(crate) fn new(roots: &[PathBuf]) -> Self {
         let mut instance = Self::default();
         let now = std::time::Instant::now();
-        let parsed_resources = parse_resource_dirs(roots);
+        let parsed_resources = parse_resource_dirs(roots, &mut instance.errors);
         let elapsed = chrono::Duration::from_std(now.elapsed())
             .unwrap_or_else(|_| chrono::Duration::seconds(0));
 
@@ -55,18 +148,80 @@ impl ExtractorConfig {
 
         for (path, mut resource_config_props) in parsed_resources {
             let detail_config = resource_config_props.details.take();
-            let columns = parse_resource_columns(&engine, &path, &resource_config_props);
-            let pseudo_resources = parse_pseudo_resources(&engine, &path, &resource_config_props);
+            let columns = parse_resource_columns(
+                &engine,
+                &path,
+                &resource_config_props.columns,
+                &resource_config_props.imports,
+                &mut instance.errors,
+            );
+            let pseudo_resources = parse_pseudo_resources(
+                &engine,
+                &path,
+                &resource_config_props.pseudo_resources,
+                &resource_config_props.imports,
+                &mut instance.errors,
+            );
+            let related =
+                parse_related(&engine, &path, &resource_config_props, &mut instance.errors);
             let gvk = resource_config_props.resource.clone();
 
+            // overlays only replace the sections they set
+            let overlay = resource_config_props.overlay;
+
             if let Some(details) = detail_config {
-                let (template_path, template) = parse_detail_templates(&path, details);
+                let (template_path, template) =
+                    parse_detail_templates(&path, details, &mut instance.errors);
                 instance.register_detail_template(gvk.clone(), template, &template_path);
+            } else if !overlay {
+                Self::unregister(
+                    "detail template",
+                    &mut instance.detail_templates_map,
+                    &gvk,
+                    &path,
+                );
+            }
+            if overlay {
+                instance.overlay_gvk_columns(
+                    gvk.clone(),
+                    columns,
+                    &resource_config_props.remove_columns,
+                    &path,
+                );
+            } else {
+                instance.register_gvk_columns(gvk.clone(), columns, &path);
+            }
+            if !overlay || !pseudo_resources.is_empty() {
+                instance.register_gvk_pseudo_resource_extractors(
+                    gvk.clone(),
+                    pseudo_resources,
+                    &path,
+                );
+            }
+            instance.register_inline_pseudo_resources(
+                &engine,
+                &path,
+                &gvk,
+                &resource_config_props.pseudo_resources,
+                &resource_config_props.imports,
+            );
+            if !overlay || !resource_config_props.events.is_empty() {
+                instance.register_event_handler_type(
+                    gvk.clone(),
+                    resource_config_props.events,
+                    &path,
+                );
+            }
+            if !related.is_empty() {
+                instance.register_gvk_related(gvk.clone(), related, &path);
+            } else if !overlay {
+                Self::unregister("related", &mut instance.related_map, &gvk, &path);
+            }
+            if let Some(default_sort) = resource_config_props.default_sort {
+                instance.register_default_sort(gvk.clone(), default_sort, &path);
+            } else if !overlay {
+                Self::unregister("default sort", &mut instance.default_sort_map, &gvk, &path);
             }
-
-            instance.register_gvk_columns(gvk.clone(), columns, &path);
-            instance.register_gvk_pseudo_resource_extractors(gvk.clone(), pseudo_resources, &path);
-            instance.register_event_handler_type(gvk.clone(), resource_config_props.events, &path);
         }
 
         let elapsed = chrono::Duration::from_std(now.elapsed())
@@ -83,10 +238,68 @@ impl ExtractorConfig {
             num_columns,
             ago(elapsed)
         );
+        if !instance.errors.is_empty() {
+            warn!("{} config errors", instance.errors.len());
+        }
 
         instance
     }
 
+    /// Finds a value by the exact GVK or, for pseudo resources, by their config GVK.
+    pub(crate) fn find<'a, V>(
+        map: &'a HashMap<GroupVersionKind, Arc<V>>,
+        gvk: &GroupVersionKind,
+    ) -> Option<&'a Arc<V>> {
+        map.get(gvk).or_else(|| map.get(&gvk.get_config_gvk()))
+    }
+
+    /// Registers columns, events and child extractors declared inline in pseudo resource
+    /// configs under their config GVK, i.e. `ClusterRole/rule/resource`.
+    fn register_inline_pseudo_resources(
+        &mut self,
+        engine: &Engine,
+        path: &Path,
+        parent_gvk: &GroupVersionKind,
+        configs: &[PseudoResourceExtractorConfigPros],
+        imports: &[String],
+    ) {
+        for config in configs {
+            let mut gvk = parent_gvk.clone();
+            gvk.kind = [parent_gvk.kind.as_str(), &config.name].join(PSEUDO_RESOURCE_JOIN_SEQ);
+
+            if !config.columns.is_empty() {
+                let columns = parse_resource_columns(
+                    engine,
+                    path,
+                    &config.columns,
+                    imports,
+                    &mut self.errors,
+                );
+                self.register_gvk_columns(gvk.clone(), columns, path);
+            }
+            if !config.events.is_empty() {
+                self.register_event_handler_type(gvk.clone(), config.events.clone(), path);
+            }
+            if !config.pseudo_resources.is_empty() {
+                let children = parse_pseudo_resources(
+                    engine,
+                    path,
+                    &config.pseudo_resources,
+                    imports,
+                    &mut self.errors,
+                );
+                self.register_gvk_pseudo_resource_extractors(gvk.clone(), children, path);
+                self.register_inline_pseudo_resources(
+                    engine,
+                    path,
+                    &gvk,
+                    &config.pseudo_resources,
+                    imports,
+                );
+            }
+        }
+    }
+
     fn register<V>(
         name: &str,
         container: &mut HashMap<GroupVersionKind, Arc<V>>,
@@ -96,16 +309,77 @@ impl ExtractorConfig {
     ) {
         let gvk_full_name = gvk.full_name();
         if container.insert(gvk, value.into()).is_some() {
-            warn!("{gvk_full_name}: Replaced {name} from {}", origin.display());
+            info!("{gvk_full_name}: Replaced {name} from {}", origin.display());
         } else {
             info!("{gvk_full_name}: Loaded {name} from {}", origin.display());
         }
     }
 
+    /// A config that is not an overlay replaces the lower layers entirely, sections it
+    /// doesn't set are dropped.
+    fn unregister<V>(
+        name: &str,
+        container: &mut HashMap<GroupVersionKind, Arc<V>>,
+        gvk: &GroupVersionKind,
+        origin: &Path,
+    ) {
+        if container.remove(gvk).is_some() {
+            info!(
+                "{}: Dropped {name}, {} does not set it",
+                gvk.full_name(),
+                origin.display()
+            );
+        }
+    }
+
+    fn register_default_sort(
+        &mut self,
+        gvk: GroupVersionKind,
+        default_sort: DefaultSort,
+        origin: &Path,
+    ) {
+        Self::register(
+            "default sort",
+            &mut self.default_sort_map,
+            gvk,
+            default_sort,
+            origin,
+        );
+    }
+
     fn register_gvk_columns(&mut self, gvk: GroupVersionKind, columns: Vec<Column>, origin: &Path) {
         Self::register("columns", &mut self.columns_map, gvk, columns, origin);
     }
 
+    /// Replaces the columns of a lower layer with the same names, appends the new ones.
+    fn overlay_gvk_columns(
+        &mut self,
+        gvk: GroupVersionKind,
+        columns: Vec<Column>,
+        remove_columns: &[String],
+        origin: &Path,
+    ) {
+        let mut merged = self
+            .columns_map
+            .get(&gvk)
+            .map(|columns| columns.as_ref().clone())
+            .unwrap_or_default();
+        merged.retain(|column| !remove_columns.contains(&column.name));
+        for column in columns {
+            match merged.iter_mut().find(|merged| merged.name == column.name) {
+                Some(merged) => *merged = column,
+                None => merged.push(column),
+            }
+        }
+
+        info!(
+            "{}: Overlaid columns from {}",
+            gvk.full_name(),
+            origin.display()
+        );
+        self.columns_map.insert(gvk, Arc::new(merged));
+    }
+
     fn register_gvk_pseudo_resource_extractors(
         &mut self,
         gvk: GroupVersionKind,
@@ -121,6 +395,15 @@ impl ExtractorConfig {
         );
     }
 
+    fn register_gvk_related(
+        &mut self,
+        gvk: GroupVersionKind,
+        related: Vec<RelatedConf>,
+        origin: &Path,
+    ) {
+        Self::register("related", &mut self.related_map, gvk, related, origin);
+    }
+
     fn register_detail_template(
         &mut self,
         gvk: GroupVersionKind,
@@ -158,10 +441,64 @@ pub(crate) enum EmbeddedExtractor {
     Name,
     Status,
     Age,
+    /// The reason of the latest warning event for the resource; filled by `ResourceManager`
+    /// from its event cache.
+    Events,
+}
+
+/// How column values are compared when the table is sorted by the column.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SortType {
+    /// Compares values of the same kind, falls back to natural order otherwise.
+    #[default]
+    Auto,
+    String,
+    /// Digit runs are compared as numbers: `pod-2` < `pod-10`.
+    Natural,
+    Int,
+    /// Kubernetes quantities like `500Mi` or `100m`.
+    Quantity,
+    /// Durations like `5m` or `2h30m`.
+    Duration,
+    /// RFC 3339 timestamps.
+    Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub(crate) fn to_ordering(self) -> Ordering {
+        match self {
+            SortOrder::Asc => Ordering::Less,
+            SortOrder::Desc => Ordering::Greater,
+        }
+    }
+
+    pub(crate) fn from_ordering(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => SortOrder::Desc,
+            _ => SortOrder::Asc,
+        }
+    }
 }
 
+/// The sort column and direction a list view starts with.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-enum EvalConfigProps {
+pub(crate) struct DefaultSort {
+    pub(crate) column: String,
+    #[serde(default)]
+    pub(crate) order: SortOrder,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub(crate) enum EvalConfigProps {
     ScriptPath { path: PathBuf },
     ScriptContent { content: String },
     Embedded { name: EmbeddedExtractor },
@@ -174,40 +511,117 @@ pub(crate) struct HbsHelper {
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
-struct DetailsTemplateConfigProps {
-    template: PathBuf,
+pub(crate) struct DetailsTemplateConfigProps {
+    pub(crate) template: PathBuf,
 
     #[serde(default)]
-    helpers: Vec<HbsHelper>,
+    pub(crate) helpers: Vec<HbsHelper>,
 }
 
 #[derive(Debug)]
 pub(crate) struct DetailsTemplate {
+    /// As configured, looked up in the detail template dirs first.
+    pub(crate) name: PathBuf,
+    /// `name` resolved relative to the extractor config.
     pub(crate) template: PathBuf,
+    /// Paths are resolved relative to the template when rendering.
     pub(crate) helpers: Vec<HbsHelper>,
+    /// The extractor config the template is set in.
+    pub(crate) config_path: PathBuf,
 }
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub(crate) struct PseudoResourceExtractorConfigPros {
-    name: String,
-    script_content: String,
+    pub(crate) name: String,
+    pub(crate) script_content: String,
+    /// A script returning a stable key of a pseudo resource. Keyed pseudo resources are
+    /// diffed on updates, and the missing ones are shown as deleted.
+    #[serde(default)]
+    pub(crate) key: Option<String>,
+
+    /// Columns of the pseudo resource table; an alternative to a separate file for the
+    /// `Kind/name` resource.
+    #[serde(default)]
+    pub(crate) columns: Vec<ColumnConfigProps>,
+    #[serde(default)]
+    pub(crate) events: Vec<EventHandlerType>,
+    /// Pseudo resources extracted from this pseudo resource.
+    #[serde(default)]
+    pub(crate) pseudo_resources: Vec<PseudoResourceExtractorConfigPros>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
-struct ResourceConfigProps {
-    resource: GroupVersionKind,
+pub(crate) struct RelatedExtractorConfigProps {
+    pub(crate) name: String,
+    pub(crate) script_content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct ResourceConfigProps {
+    pub(crate) resource: GroupVersionKind,
+    #[serde(default)]
+    pub(crate) imports: Vec<String>,
+
+    #[serde(default)]
+    pub(crate) pseudo_resources: Vec<PseudoResourceExtractorConfigPros>,
+
+    pub(crate) details: Option<DetailsTemplateConfigProps>,
+
+    #[serde(default)]
+    pub(crate) events: Vec<EventHandlerType>,
+
+    pub(crate) default_sort: Option<DefaultSort>,
+
     #[serde(default)]
-    imports: Vec<String>,
+    pub(crate) related: Vec<RelatedExtractorConfigProps>,
 
     #[serde(default)]
-    pseudo_resources: Vec<PseudoResourceExtractorConfigPros>,
+    pub(crate) columns: Vec<ColumnConfigProps>,
 
-    details: Option<DetailsTemplateConfigProps>,
+    /// Merges the config into the one of a lower layer instead of replacing it: columns
+    /// replace the columns with the same names or are appended, other sections replace
+    /// the lower ones only if set.
+    #[serde(default)]
+    pub(crate) overlay: bool,
+
+    /// Columns of a lower layer to drop, only used by overlays.
+    #[serde(default)]
+    pub(crate) remove_columns: Vec<String>,
+
+    #[serde(default)]
+    pub(crate) tests: Vec<TestCaseProps>,
+}
 
+/// A fixture resource with the expected values of its columns and ids of its pseudo
+/// resources, run by `kgv test`.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct TestCaseProps {
+    pub(crate) name: String,
+    /// A YAML file with the resource, relative to the file declaring the test.
+    pub(crate) fixture: PathBuf,
+    /// Expected rendered values by column name.
+    #[serde(default)]
+    pub(crate) columns: BTreeMap<String, String>,
+    /// Expected pseudo resource ids by extractor name.
     #[serde(default)]
-    events: Vec<EventHandlerType>,
+    pub(crate) pseudo_resources: BTreeMap<String, Vec<String>>,
+}
+
+/// `*.test.yaml` files next to extractor configs only contain `tests`.
+pub(crate) fn is_test_suite_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.ends_with(".test.yaml") || name.ends_with(".test.yml"))
+        .unwrap_or(false)
+        || is_fixture_file(path)
+}
 
-    columns: Vec<ColumnConfigProps>,
+/// Test fixtures are kept in `fixtures` directories among extractor configs.
+pub(crate) fn is_fixture_file(path: &Path) -> bool {
+    path.parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name == "fixtures")
+        .unwrap_or(false)
 }
 
 impl TryFrom<&PathBuf> for ResourceConfigProps {
@@ -220,18 +634,23 @@ impl TryFrom<&PathBuf> for ResourceConfigProps {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-struct ColumnConfigProps {
-    name: String,
-    display_name: String,
-    width: usize,
-    evaluator: EvalConfigProps,
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct ColumnConfigProps {
+    pub(crate) name: String,
+    pub(crate) display_name: String,
+    pub(crate) width: usize,
+    pub(crate) evaluator: EvalConfigProps,
+    #[serde(default)]
+    pub(crate) sort: SortType,
+    #[serde(default)]
+    pub(crate) styles: Vec<ColumnStyleRule>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct PseudoResourceConf {
     pub(crate) name: String,
     pub(crate) ast: AST,
+    pub(crate) key: Option<AST>,
 }
 
 impl PseudoResourceConf {
@@ -239,6 +658,33 @@ impl PseudoResourceConf {
         config: &PseudoResourceExtractorConfigPros,
         engine: &Engine,
         imports: &[String],
+    ) -> anyhow::Result<Self> {
+        let key = config
+            .key
+            .as_ref()
+            .map(|key| engine.compile_content_with_imports(key, imports))
+            .transpose()?;
+        Ok(Self {
+            name: config.name.clone(),
+            ast: engine.compile_content_with_imports(&config.script_content, imports)?,
+            key,
+        })
+    }
+}
+
+/// A script returning `[gvk, namespace, target]` references to the resources related to
+/// the current one.
+#[derive(Debug, Clone)]
+pub(crate) struct RelatedConf {
+    pub(crate) name: String,
+    pub(crate) ast: AST,
+}
+
+impl RelatedConf {
+    fn try_from_config(
+        config: &RelatedExtractorConfigProps,
+        engine: &Engine,
+        imports: &[String],
     ) -> anyhow::Result<Self> {
         Ok(Self {
             name: config.name.clone(),
@@ -281,11 +727,23 @@ pub(crate) struct Column {
     pub(crate) display_name: String,
     pub(crate) width: usize,
     pub(crate) evaluator_type: EvaluatorType,
+    pub(crate) sort_type: SortType,
+    pub(crate) styles: Vec<ColumnStyleRule>,
 }
 
-fn parse_resource_dirs(roots: &[PathBuf]) -> Vec<(PathBuf, ResourceConfigProps)> {
+fn parse_resource_dirs(
+    roots: &[PathBuf],
+    errors: &mut Vec<ConfigError>,
+) -> Vec<(PathBuf, ResourceConfigProps)> {
+    // configs of later roots override the earlier ones, even if in subdirectories
+    let files = roots.iter().flat_map(|root| {
+        let mut files = scan_files(&[root.clone()]);
+        files.sort();
+        files
+    });
+
     let mut parsed_props = vec![];
-    for file in scan_files(roots) {
+    for file in files {
         if let Some(ext) = file.extension() {
             if ext != "yaml" && ext != "yml" {
                 continue;
@@ -293,15 +751,29 @@ fn parse_resource_dirs(roots: &[PathBuf]) -> Vec<(PathBuf, ResourceConfigProps)>
         } else {
             continue;
         }
+        if is_test_suite_file(&file) {
+            continue;
+        }
         match ResourceConfigProps::try_from(&file) {
             Ok(resource_config_props) => {
                 parsed_props.push((file, resource_config_props));
             }
             Err(err) => {
-                error!(
-                    "Failed to parse file {} into resource configuration properties: {}",
-                    file.display(),
-                    err
+                let position = match &err {
+                    KgvError::SerdeYamlError(err) => err
+                        .location()
+                        .map(|location| {
+                            Position::new(location.line() as u16, location.column() as u16)
+                        })
+                        .unwrap_or(Position::NONE),
+                    _ => Position::NONE,
+                };
+                ConfigError::report(
+                    errors,
+                    &file,
+                    "resource configuration".to_string(),
+                    position,
+                    err.to_string(),
                 );
             }
         }
@@ -313,27 +785,29 @@ fn parse_resource_dirs(roots: &[PathBuf]) -> Vec<(PathBuf, ResourceConfigProps)>
 fn parse_resource_columns(
     engine: &Engine,
     source_path: &Path,
-    resource_config_props: &ResourceConfigProps,
+    column_configs: &[ColumnConfigProps],
+    imports: &[String],
+    errors: &mut Vec<ConfigError>,
 ) -> Vec<Column> {
     let mut columns: Vec<Column> = vec![];
-    for column_config in &resource_config_props.columns {
+    for column_config in column_configs {
         let column_name = column_config.name.clone();
 
-        let evaluator_type = EvaluatorType::try_from_config(
-            &column_config.evaluator,
-            engine,
-            source_path,
-            &resource_config_props.imports,
-        );
+        let evaluator_type =
+            EvaluatorType::try_from_config(&column_config.evaluator, engine, source_path, imports);
 
         let evaluator_type = match evaluator_type {
             Ok(col) => col,
             Err(err) => {
-                error!(
-                    "Failed to process column {} in file {}: {err}",
-                    column_name,
-                    source_path.display()
-                );
+                // errors in script files are positioned in the script file
+                let (path, context) = match &column_config.evaluator {
+                    EvalConfigProps::ScriptPath { path } => (
+                        resolve_path(source_path, path),
+                        format!("column {column_name} of {}", source_path.display()),
+                    ),
+                    _ => (source_path.to_path_buf(), format!("column {column_name}")),
+                };
+                ConfigError::report_script(errors, &path, context, &err, imports);
                 continue;
             }
         };
@@ -343,6 +817,8 @@ fn parse_resource_columns(
             display_name: column_config.display_name.clone(),
             width: column_config.width,
             evaluator_type,
+            sort_type: column_config.sort,
+            styles: column_config.styles.clone(),
         };
 
         columns.push(column);
@@ -354,25 +830,26 @@ fn parse_resource_columns(
 fn parse_pseudo_resources(
     engine: &Engine,
     source_path: &Path,
-    resource_config_props: &ResourceConfigProps,
+    pseudo_resource_configs: &[PseudoResourceExtractorConfigPros],
+    imports: &[String],
+    errors: &mut Vec<ConfigError>,
 ) -> Vec<PseudoResourceConf> {
     let mut pseudo_resources: Vec<PseudoResourceConf> = vec![];
-    for pseudo_resource_config in &resource_config_props.pseudo_resources {
+    for pseudo_resource_config in pseudo_resource_configs {
         let pseudo_resource_name = pseudo_resource_config.name.clone();
 
-        let pseudo_resource = PseudoResourceConf::try_from_config(
-            pseudo_resource_config,
-            engine,
-            &resource_config_props.imports,
-        );
+        let pseudo_resource =
+            PseudoResourceConf::try_from_config(pseudo_resource_config, engine, imports);
 
         let pseudo_resource = match pseudo_resource {
             Ok(pseudo_resource) => pseudo_resource,
             Err(err) => {
-                error!(
-                    "Failed to process pseudo resource {} in file {}: {err}",
-                    pseudo_resource_name,
-                    source_path.display()
+                ConfigError::report_script(
+                    errors,
+                    source_path,
+                    format!("pseudo resource {pseudo_resource_name}"),
+                    &err,
+                    imports,
                 );
                 continue;
             }
@@ -384,21 +861,55 @@ fn parse_pseudo_resources(
     pseudo_resources
 }
 
+fn parse_related(
+    engine: &Engine,
+    source_path: &Path,
+    resource_config_props: &ResourceConfigProps,
+    errors: &mut Vec<ConfigError>,
+) -> Vec<RelatedConf> {
+    let mut related: Vec<RelatedConf> = vec![];
+    for related_config in &resource_config_props.related {
+        match RelatedConf::try_from_config(related_config, engine, &resource_config_props.imports) {
+            Ok(conf) => related.push(conf),
+            Err(err) => {
+                ConfigError::report_script(
+                    errors,
+                    source_path,
+                    format!("related resources {}", related_config.name),
+                    &err,
+                    &resource_config_props.imports,
+                );
+            }
+        }
+    }
+
+    related
+}
+
 fn parse_detail_templates(
     path: &Path,
     details: DetailsTemplateConfigProps,
+    errors: &mut Vec<ConfigError>,
 ) -> (PathBuf, DetailsTemplate) {
     let template_path = resolve_path(path, &details.template);
+    // templates of the detail template dirs are compiled by `DetailTemplates`, only
+    // templates next to the config are compiled here
+    if template_path.is_file() {
+        if let Err(err) = Handlebars::new().register_template_file("template", &template_path) {
+            ConfigError::report(
+                errors,
+                &template_path,
+                format!("detail template of {}", path.display()),
+                Position::NONE,
+                err.to_string(),
+            );
+        }
+    }
     let template = DetailsTemplate {
+        name: details.template,
         template: template_path.clone(),
-        helpers: details
-            .helpers
-            .into_iter()
-            .map(|mut helper| {
-                helper.path = resolve_path(&template_path, &helper.path);
-                helper
-            })
-            .collect(),
+        helpers: details.helpers,
+        config_path: path.to_path_buf(),
     };
     (template_path, template)
 }
@@ -442,8 +953,36 @@ mod tests {
             pseudo_resources: vec![PseudoResourceExtractorConfigPros {
                 name: "sample".to_string(),
                 script_content: "sample".to_string(),
+                key: Some("resource.name".to_string()),
+                columns: vec![],
+                events: vec![],
+                pseudo_resources: vec![PseudoResourceExtractorConfigPros {
+                    name: "nested".to_string(),
+                    script_content: "[]".to_string(),
+                    key: None,
+                    columns: vec![ColumnConfigProps {
+                        name: "name".to_string(),
+                        display_name: "name".to_string(),
+                        width: 0,
+                        evaluator: EvalConfigProps::Embedded {
+                            name: EmbeddedExtractor::Name,
+                        },
+                        sort: SortType::default(),
+                        styles: vec![],
+                    }],
+                    events: vec![],
+                    pseudo_resources: vec![],
+                }],
             }],
             imports: vec![r##"import "pod" as pod;"##.to_string()],
+            default_sort: None,
+            related: vec![RelatedExtractorConfigProps {
+                name: "node".to_string(),
+                script_content: r#"[["v1/Node", "", resource.spec.nodeName]]"#.to_string(),
+            }],
+            tests: vec![],
+            overlay: false,
+            remove_columns: vec![],
             columns: vec![
                 ColumnConfigProps {
                     name: "name".to_string(),
@@ -452,12 +991,16 @@ mod tests {
                     evaluator: EvalConfigProps::Embedded {
                         name: EmbeddedExtractor::Name,
                     },
+                    sort: SortType::Natural,
+                    styles: vec![],
                 },
                 ColumnConfigProps {
                     name: "sample".to_string(),
                     display_name: "sample".to_string(),
                     width: 0,
                     evaluator: EvalConfigProps::ScriptPath { path: script_path },
+                    sort: SortType::default(),
+                    styles: vec![],
                 },
                 ColumnConfigProps {
                     name: "sample2".to_string(),
@@ -466,6 +1009,8 @@ mod tests {
                     evaluator: EvalConfigProps::ScriptContent {
                         content: "resource".to_string(),
                     },
+                    sort: SortType::default(),
+                    styles: vec![],
                 },
             ],
         };
@@ -476,7 +1021,170 @@ mod tests {
         std::fs::write(extractor_path, data).unwrap();
 
         let deserialized = ExtractorConfig::new(&[extractor_dir.into_path()]);
-        assert_eq!(deserialized.columns_map.len(), 1);
-        assert_eq!(deserialized.columns_map.values().next().unwrap().len(), 3);
+        assert_eq!(deserialized.columns_map.len(), 2);
+        assert_eq!(deserialized.columns_map[&Pod::gvk_for_type()].len(), 3);
+
+        let mut nested_gvk = Pod::gvk_for_type();
+        nested_gvk.kind = "Pod/sample/nested".to_string();
+        assert_eq!(deserialized.columns_map[&nested_gvk].len(), 1);
+        assert_eq!(deserialized.related_map.values().next().unwrap().len(), 1);
+        let pseudo_resources = &deserialized.pseudo_resources_map[&Pod::gvk_for_type()];
+        assert!(pseudo_resources[0].key.is_some());
+        nested_gvk.kind = "Pod/sample".to_string();
+        assert_eq!(deserialized.pseudo_resources_map[&nested_gvk].len(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let extractor_dir = tempfile::tempdir().unwrap();
+        std::fs::write(extractor_dir.path().join("broken.yaml"), "resource: [").unwrap();
+        std::fs::write(
+            extractor_dir.path().join("pod.yaml"),
+            r#"resource:
+  group: ''
+  version: v1
+  kind: Pod
+imports:
+  - import "pod" as pod;
+columns:
+  - name: broken
+    display_name: Broken
+    width: 0
+    evaluator: !ScriptContent
+      content: "let x = ;"
+"#,
+        )
+        .unwrap();
+
+        let config = ExtractorConfig::new(&[extractor_dir.into_path()]);
+        assert_eq!(config.errors.len(), 2);
+
+        let column_error = config
+            .errors
+            .iter()
+            .find(|error| error.context == "column broken")
+            .unwrap();
+        assert_eq!(column_error.position.line(), Some(1));
+
+        let yaml_error = config
+            .errors
+            .iter()
+            .find(|error| error.path.ends_with("broken.yaml"))
+            .unwrap();
+        assert!(yaml_error.position.line().is_some());
+    }
+
+    #[test]
+    fn test_missing_helpers() {
+        let extractor_dir = tempfile::tempdir().unwrap();
+        let template_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            extractor_dir.path().join("pod.yaml"),
+            r#"resource:
+  group: ''
+  version: v1
+  kind: Pod
+details:
+  template: pod.hbs
+  helpers:
+    - name: found
+      path: helpers/found.rhai
+    - name: missing
+      path: helpers/missing.rhai
+"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("pod.hbs"), "{{found}}").unwrap();
+        std::fs::create_dir(template_dir.path().join("helpers")).unwrap();
+        std::fs::write(template_dir.path().join("helpers/found.rhai"), "1").unwrap();
+
+        let config = ExtractorConfig::new(&[extractor_dir.path().to_path_buf()]);
+        let detail_templates = DetailTemplates::new(&[template_dir.path().to_path_buf()]);
+        let errors = config.missing_helpers(&detail_templates);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].context, "helper missing");
+        assert_eq!(errors[0].path, extractor_dir.path().join("pod.yaml"));
+    }
+
+    #[test]
+    fn test_overlay() {
+        let column = |name: &str, content: &str| {
+            format!(
+                "  - name: {name}\n    display_name: {name}\n    width: 0\n    evaluator: !ScriptContent\n      content: {content}\n"
+            )
+        };
+        let resource = "resource:\n  group: ''\n  version: v1\n  kind: Pod\n";
+
+        let defaults_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            defaults_dir.path().join("pod.yaml"),
+            format!(
+                "{resource}columns:\n{}{}{}",
+                column("a", "resource.a"),
+                column("b", "resource.b"),
+                column("c", "resource.c")
+            ),
+        )
+        .unwrap();
+
+        let user_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            user_dir.path().join("pod.overlay.yaml"),
+            format!(
+                "{resource}overlay: true\nremove_columns: [a]\ncolumns:\n{}{}",
+                column("b", "resource.b2"),
+                column("d", "resource.d")
+            ),
+        )
+        .unwrap();
+
+        let config = ExtractorConfig::new(&[
+            defaults_dir.path().to_path_buf(),
+            user_dir.path().to_path_buf(),
+        ]);
+        assert!(config.errors.is_empty());
+
+        let columns = &config.columns_map[&Pod::gvk_for_type()];
+        let names = columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["b", "c", "d"]);
+        match &columns[0].evaluator_type {
+            EvaluatorType::AST(ast) => assert!(ast.source().unwrap().ends_with("resource.b2")),
+            EvaluatorType::Embedded(_) => panic!("expected a script"),
+        }
+    }
+
+    #[test]
+    fn test_replace_drops_unset_sections() {
+        let resource = "resource:\n  group: ''\n  version: v1\n  kind: Pod\n";
+
+        let defaults_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            defaults_dir.path().join("pod.yaml"),
+            format!(
+                "{resource}default_sort: {{ column: a, order: desc }}\nrelated:\n  - name: node\n    script_content: '[]'\ndetails:\n  template: pod.hbs\n"
+            ),
+        )
+        .unwrap();
+
+        let user_dir = tempfile::tempdir().unwrap();
+        std::fs::write(user_dir.path().join("pod.yaml"), resource).unwrap();
+
+        let config = ExtractorConfig::new(&[defaults_dir.path().to_path_buf()]);
+        assert!(config.default_sort_map.contains_key(&Pod::gvk_for_type()));
+        assert!(config.related_map.contains_key(&Pod::gvk_for_type()));
+        assert!(config
+            .detail_templates_map
+            .contains_key(&Pod::gvk_for_type()));
+
+        let config = ExtractorConfig::new(&[
+            defaults_dir.path().to_path_buf(),
+            user_dir.path().to_path_buf(),
+        ]);
+        assert!(config.default_sort_map.is_empty());
+        assert!(config.related_map.is_empty());
+        assert!(config.detail_templates_map.is_empty());
     }
 }
@@ -1,13 +1,39 @@
 use std::path::PathBuf;
 
+use crate::config::args::Action;
+use crate::eval::engine_factory::ScriptLimits;
+
 #[derive(Debug)]
 pub(crate) struct KgvConfiguration {
     pub(crate) cache_dir: Option<PathBuf>,
     pub(crate) logs_dir: Option<PathBuf>,
     pub(crate) module_dirs: Vec<PathBuf>,
     pub(crate) extractor_dirs: Vec<PathBuf>,
+    pub(crate) detail_template_dirs: Vec<PathBuf>,
     pub(crate) num_tokio_backend_threads: usize,
     pub(crate) num_dispatcher_threads: usize,
     pub(crate) num_evaluator_threads: usize,
     pub(crate) accept_invalid_certs: bool,
+    pub(crate) port_forward_profiles_path: PathBuf,
+    pub(crate) forward_profiles: Vec<String>,
+    pub(crate) tombstone_retention: chrono::Duration,
+    pub(crate) revision_history_size: usize,
+    pub(crate) render_profiles_path: PathBuf,
+    pub(crate) sort_state_path: PathBuf,
+    /// Disabled if `None`.
+    pub(crate) metrics_poll_interval: Option<std::time::Duration>,
+    pub(crate) fps: u32,
+    pub(crate) theme_dirs: Vec<PathBuf>,
+    pub(crate) theme: String,
+    pub(crate) log_defaults: LogDefaults,
+    pub(crate) script_limits: ScriptLimits,
+    /// Runs instead of the UI if set.
+    pub(crate) action: Option<Action>,
+}
+
+/// Log params new log windows start with, `None` requests all logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LogDefaults {
+    pub(crate) since_seconds: Option<i64>,
+    pub(crate) tail_lines: Option<i64>,
 }
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+use kube::api::GroupVersionKind;
+use serde::{Deserialize, Serialize};
+
+use crate::config::extractor::DefaultSort;
+use crate::traits::ext::gvk::GvkNameExt;
+use crate::util::yaml::YamlFile;
+
+/// The last chosen sort of every list view stored in `<kgv-home-dir>/sort-state.yaml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ListSortState {
+    /// Sorts by the GVK full name.
+    #[serde(default)]
+    pub(crate) sorts: BTreeMap<String, DefaultSort>,
+}
+
+impl YamlFile for ListSortState {}
+
+impl ListSortState {
+    pub(crate) fn get(&self, gvk: &GroupVersionKind) -> Option<&DefaultSort> {
+        self.sorts.get(&gvk.full_name())
+    }
+
+    pub(crate) fn set(&mut self, gvk: &GroupVersionKind, sort: DefaultSort) {
+        self.sorts.insert(gvk.full_name(), sort);
+    }
+}
@@ -1,3 +1,10 @@
 pub(crate) mod args;
+pub(crate) mod check;
+pub(crate) mod default_config;
+pub(crate) mod detail_templates;
 pub(crate) mod extractor;
 pub(crate) mod kgv_configuration;
+pub(crate) mod list_sort_state;
+pub(crate) mod port_forward_profiles;
+pub(crate) mod render_profiles;
+pub(crate) mod settings;
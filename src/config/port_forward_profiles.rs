@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::port_forward_request::PortForwardSpec;
+use crate::util::yaml::YamlFile;
+
+/// Named groups of port forwards stored in `<kgv-home-dir>/port-forwards.yaml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct PortForwardProfiles {
+    #[serde(default)]
+    pub(crate) profiles: BTreeMap<String, Vec<PortForwardSpec>>,
+}
+
+impl YamlFile for PortForwardProfiles {}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::port_forward_request::PortForwardTarget;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profiles: PortForwardProfiles = serde_yaml::from_str(
+            r#"
+profiles:
+  db:
+    - namespace: default
+      target: !Service postgres
+      remote_port: 5432
+      host_port: 15432
+"#,
+        )
+        .unwrap();
+
+        let specs = &profiles.profiles["db"];
+        assert_eq!(specs.len(), 1);
+        assert_eq!(
+            specs[0].target,
+            PortForwardTarget::Service("postgres".to_string())
+        );
+        assert_eq!(specs[0].host, "127.0.0.1");
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::util::panics::ResultExt;
+use crate::util::yaml::{parse_json_pointer, remove_path, YamlFile};
+
+/// A named set of JSON pointers excluded from the rendered YAML.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RenderProfile {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) exclude: Vec<String>,
+}
+
+/// YAML render profiles stored in `<kgv-home-dir>/render-profiles.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RenderProfiles {
+    /// The profile new YAML windows start with.
+    pub(crate) default: String,
+    pub(crate) profiles: Vec<RenderProfile>,
+}
+
+/// The embedded `default_config/render-profiles.yaml`, used unless the user has their own.
+const DEFAULT_RENDER_PROFILES: &str = include_str!("../../default_config/render-profiles.yaml");
+
+impl Default for RenderProfiles {
+    fn default() -> Self {
+        serde_yaml::from_str(DEFAULT_RENDER_PROFILES).unwrap_or_log()
+    }
+}
+
+impl YamlFile for RenderProfiles {}
+
+impl RenderProfiles {
+    pub(crate) fn get(&self, name: &str) -> Option<&RenderProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+}
+
+impl RenderProfile {
+    /// Drops the excluded paths from a YAML document. The document is returned as is
+    /// if there is nothing to exclude.
+    pub(crate) fn apply(&self, yaml: String) -> anyhow::Result<String> {
+        if self.exclude.is_empty() {
+            return Ok(yaml);
+        }
+
+        let mut value: Value = serde_yaml::from_str(&yaml)?;
+        for pointer in self.exclude.iter() {
+            remove_path(&mut value, &parse_json_pointer(pointer));
+        }
+        Ok(serde_yaml::to_string(&value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_profiles() {
+        let profiles = RenderProfiles::default();
+        let yaml = r#"
+metadata:
+  name: example
+  managedFields:
+    - manager: kubectl
+  annotations:
+    kubectl.kubernetes.io/last-applied-configuration: "{}"
+spec:
+  replicas: 1
+status:
+  replicas: 1
+"#;
+
+        let clean = profiles
+            .get("clean")
+            .unwrap()
+            .apply(yaml.to_string())
+            .unwrap();
+        assert!(!clean.contains("managedFields"));
+        assert!(!clean.contains("last-applied-configuration"));
+        assert!(clean.contains("status"));
+
+        let spec_only = profiles
+            .get("spec-only")
+            .unwrap()
+            .apply(yaml.to_string())
+            .unwrap();
+        assert!(!spec_only.contains("status"));
+        assert!(spec_only.contains("replicas"));
+    }
+}
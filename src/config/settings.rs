@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::eval::engine_factory::ScriptLimits;
+use crate::theme::DEFAULT_THEME_NAME;
+use crate::util::error::KgvError;
+
+/// Runtime settings of `<kgv-home-dir>/config.yaml`. Every setting is optional, unset ones
+/// fall back to the lower layer: CLI flags, then the selected profile, then the top-level
+/// settings, then the built-in ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Settings {
+    pub(crate) num_tokio_backend_threads: Option<usize>,
+    pub(crate) num_evaluator_threads: Option<usize>,
+    pub(crate) num_dispatcher_threads: Option<usize>,
+    pub(crate) accept_invalid_certs: Option<bool>,
+    pub(crate) tombstone_retention_minutes: Option<i64>,
+    pub(crate) revision_history_size: Option<usize>,
+    /// 0 disables polling.
+    pub(crate) metrics_poll_interval_seconds: Option<u64>,
+    /// How often the UI is redrawn without input.
+    pub(crate) fps: Option<u32>,
+    /// A theme file of the theme dirs without the extension, i.e. `light`.
+    pub(crate) theme: Option<String>,
+    /// Logs of new log windows start this far back, 0 requests all of them.
+    pub(crate) log_since_seconds: Option<i64>,
+    /// New log windows request this many last lines, 0 requests all of them.
+    pub(crate) log_tail_lines: Option<i64>,
+    /// Limits of every rhai script evaluation, 0 disables a limit.
+    pub(crate) script_max_operations: Option<u64>,
+    pub(crate) script_max_call_depth: Option<usize>,
+    pub(crate) script_max_string_size: Option<usize>,
+    pub(crate) script_max_array_size: Option<usize>,
+    pub(crate) script_max_map_size: Option<usize>,
+    pub(crate) script_timeout_millis: Option<u64>,
+}
+
+impl Settings {
+    /// The value of every setting unless it's configured, unlike `Settings::default()`
+    /// where no setting is set.
+    pub(crate) fn built_in() -> Self {
+        let limits = ScriptLimits::default();
+        Self {
+            num_tokio_backend_threads: Some(4),
+            num_evaluator_threads: Some(8),
+            num_dispatcher_threads: Some(4),
+            accept_invalid_certs: Some(false),
+            tombstone_retention_minutes: Some(5),
+            revision_history_size: Some(0),
+            metrics_poll_interval_seconds: Some(30),
+            fps: Some(1),
+            theme: Some(DEFAULT_THEME_NAME.to_string()),
+            log_since_seconds: Some(60 * 60),
+            log_tail_lines: Some(1000),
+            script_max_operations: Some(limits.max_operations),
+            script_max_call_depth: Some(limits.max_call_levels),
+            script_max_string_size: Some(limits.max_string_size),
+            script_max_array_size: Some(limits.max_array_size),
+            script_max_map_size: Some(limits.max_map_size),
+            script_timeout_millis: Some(
+                limits
+                    .timeout
+                    .map(|timeout| timeout.as_millis() as u64)
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Settings set in `other` take precedence.
+    pub(crate) fn merge(self, other: Settings) -> Self {
+        Self {
+            num_tokio_backend_threads: other
+                .num_tokio_backend_threads
+                .or(self.num_tokio_backend_threads),
+            num_evaluator_threads: other.num_evaluator_threads.or(self.num_evaluator_threads),
+            num_dispatcher_threads: other.num_dispatcher_threads.or(self.num_dispatcher_threads),
+            accept_invalid_certs: other.accept_invalid_certs.or(self.accept_invalid_certs),
+            tombstone_retention_minutes: other
+                .tombstone_retention_minutes
+                .or(self.tombstone_retention_minutes),
+            revision_history_size: other.revision_history_size.or(self.revision_history_size),
+            metrics_poll_interval_seconds: other
+                .metrics_poll_interval_seconds
+                .or(self.metrics_poll_interval_seconds),
+            fps: other.fps.or(self.fps),
+            theme: other.theme.or(self.theme),
+            log_since_seconds: other.log_since_seconds.or(self.log_since_seconds),
+            log_tail_lines: other.log_tail_lines.or(self.log_tail_lines),
+            script_max_operations: other.script_max_operations.or(self.script_max_operations),
+            script_max_call_depth: other.script_max_call_depth.or(self.script_max_call_depth),
+            script_max_string_size: other.script_max_string_size.or(self.script_max_string_size),
+            script_max_array_size: other.script_max_array_size.or(self.script_max_array_size),
+            script_max_map_size: other.script_max_map_size.or(self.script_max_map_size),
+            script_timeout_millis: other.script_timeout_millis.or(self.script_timeout_millis),
+        }
+    }
+}
+
+/// The content of `<kgv-home-dir>/config.yaml`: top-level settings and named profiles
+/// selected with `--profile`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ConfigFile {
+    #[serde(flatten)]
+    pub(crate) settings: Settings,
+    pub(crate) profiles: BTreeMap<String, Settings>,
+}
+
+/// serde doesn't support `deny_unknown_fields` together with `flatten`, so the top-level
+/// settings are deserialized from the mapping left after taking out the profiles.
+impl<'de> Deserialize<'de> for ConfigFile {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut mapping = serde_yaml::Mapping::deserialize(deserializer)?;
+        let profiles = match mapping.remove("profiles") {
+            Some(profiles) => serde_yaml::from_value(profiles).map_err(D::Error::custom)?,
+            None => BTreeMap::new(),
+        };
+        let settings = serde_yaml::from_value(serde_yaml::Value::Mapping(mapping))
+            .map_err(D::Error::custom)?;
+        Ok(Self { settings, profiles })
+    }
+}
+
+impl ConfigFile {
+    pub(crate) fn load(path: &Path) -> Result<Self, KgvError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    /// The defaults overridden by the top-level settings and the given profile.
+    pub(crate) fn resolve(mut self, profile: Option<&str>) -> Result<Settings, KgvError> {
+        let settings = Settings::built_in().merge(self.settings);
+        match profile {
+            Some(profile) => {
+                let profile_settings = self
+                    .profiles
+                    .remove(profile)
+                    .ok_or_else(|| KgvError::UnknownProfile(profile.to_string()))?;
+                Ok(settings.merge(profile_settings))
+            }
+            None => Ok(settings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let config_file: ConfigFile = serde_yaml::from_str(
+            r#"
+num_evaluator_threads: 2
+fps: 5
+profiles:
+  prod:
+    fps: 2
+    accept_invalid_certs: true
+"#,
+        )
+        .unwrap();
+
+        let settings = config_file.clone().resolve(None).unwrap();
+        assert_eq!(settings.num_evaluator_threads, Some(2));
+        assert_eq!(settings.fps, Some(5));
+        assert_eq!(settings.accept_invalid_certs, Some(false));
+
+        let settings = config_file.clone().resolve(Some("prod")).unwrap();
+        assert_eq!(settings.num_evaluator_threads, Some(2));
+        assert_eq!(settings.fps, Some(2));
+        assert_eq!(settings.accept_invalid_certs, Some(true));
+        assert_eq!(settings.num_dispatcher_threads, Some(4));
+
+        assert!(config_file.resolve(Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        assert!(serde_yaml::from_str::<ConfigFile>("fsp: 5").is_err());
+        assert!(serde_yaml::from_str::<ConfigFile>("profiles: { prod: { fsp: 5 } }").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::eval::eval_result::EvalResult;
+use crate::eval::sort::parse_quantity;
+
+/// Markers of the colors, the table widget can't render colors. Keep in sync with the
+/// README.
+const COLOR_MARKERS: &[(&str, &str)] = &[
+    ("red", "‼ "),
+    ("light red", "✗ "),
+    ("yellow", "! "),
+    ("light yellow", "? "),
+    ("green", "✓ "),
+    ("light green", "+ "),
+    ("blue", "◆ "),
+    ("light blue", "◇ "),
+];
+
+/// The marker of a color missing from [COLOR_MARKERS].
+const DEFAULT_MARKER: &str = "• ";
+
+/// A style attached to a cell, either by a rhai column evaluator (`Result::Styled`) or by a
+/// column style rule.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct CellStyle {
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+    #[serde(default)]
+    pub(crate) icon: Option<String>,
+}
+
+impl CellStyle {
+    /// The table widget renders plain text only, so a style is shown as an icon in front of
+    /// the value and the color itself is never rendered; colors without an explicit icon get
+    /// the marker of the color.
+    pub(crate) fn marker(&self) -> Option<&str> {
+        if let Some(icon) = &self.icon {
+            return Some(icon);
+        }
+        let color = self.color.as_deref()?;
+        let marker = COLOR_MARKERS
+            .iter()
+            .find(|(name, _)| *name == color)
+            .map_or(DEFAULT_MARKER, |(_, marker)| marker);
+        Some(marker)
+    }
+}
+
+/// A condition on the cell value; numeric conditions also accept quantities like `500Mi`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) enum StyleCondition {
+    Gt(f64),
+    Ge(f64),
+    Lt(f64),
+    Le(f64),
+    Eq(String),
+    Ne(String),
+    Contains(String),
+}
+
+impl StyleCondition {
+    pub(crate) fn matches(&self, value: &EvalResult) -> bool {
+        let text = value.to_string();
+        let number = || match value {
+            EvalResult::Int(value) => Some(*value as f64),
+            _ => parse_quantity(&text),
+        };
+
+        match self {
+            StyleCondition::Gt(threshold) => number().map_or(false, |n| n > *threshold),
+            StyleCondition::Ge(threshold) => number().map_or(false, |n| n >= *threshold),
+            StyleCondition::Lt(threshold) => number().map_or(false, |n| n < *threshold),
+            StyleCondition::Le(threshold) => number().map_or(false, |n| n <= *threshold),
+            StyleCondition::Eq(expected) => &text == expected,
+            StyleCondition::Ne(expected) => &text != expected,
+            StyleCondition::Contains(needle) => text.contains(needle.as_str()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct ColumnStyleRule {
+    pub(crate) when: StyleCondition,
+    #[serde(flatten)]
+    pub(crate) style: CellStyle,
+}
+
+/// Wraps the value into a styled one using the first matching rule. Values styled by the
+/// evaluator itself are left as is.
+pub(crate) fn apply_style_rules(value: EvalResult, rules: &[ColumnStyleRule]) -> EvalResult {
+    if matches!(value, EvalResult::Styled(..) | EvalResult::Error(_)) {
+        return value;
+    }
+
+    match rules.iter().find(|rule| rule.when.matches(&value)) {
+        Some(rule) => EvalResult::Styled(Box::new(value), rule.style.clone()),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::resource::table_view_item::DELETED_MARK;
+
+    use super::*;
+
+    #[test]
+    fn test_apply_style_rules() {
+        let rules: Vec<ColumnStyleRule> = serde_yaml::from_str(
+            r#"
+- when: !Gt 5
+  color: red
+- when: !Eq Running
+  color: green
+  icon: "▶ "
+"#,
+        )
+        .unwrap();
+
+        let restarts = apply_style_rules(EvalResult::Int(7), &rules);
+        assert!(matches!(&restarts, EvalResult::Styled(_, style) if style.marker() == Some("‼ ")));
+        assert_eq!(restarts.to_string(), "7");
+
+        let phase = apply_style_rules(EvalResult::String("Running".to_string()), &rules);
+        assert!(matches!(&phase, EvalResult::Styled(_, style) if style.marker() == Some("▶ ")));
+
+        let plain = apply_style_rules(EvalResult::Int(1), &rules);
+        assert!(matches!(plain, EvalResult::Int(1)));
+    }
+
+    #[test]
+    fn test_marker() {
+        let marker = |color: &str| {
+            let style = CellStyle {
+                color: Some(color.to_string()),
+                icon: None,
+            };
+            style.marker().unwrap().to_string()
+        };
+        assert_ne!(marker("red"), marker("light red"));
+        assert_eq!(marker("magenta"), DEFAULT_MARKER);
+
+        let markers: std::collections::HashSet<_> =
+            COLOR_MARKERS.iter().map(|(_, marker)| marker).collect();
+        assert_eq!(markers.len(), COLOR_MARKERS.len());
+        assert!(!markers.contains(&DELETED_MARK));
+        assert_ne!(DEFAULT_MARKER, DELETED_MARK);
+    }
+}
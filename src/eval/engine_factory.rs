@@ -1,15 +1,94 @@
+use std::cell::Cell;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use cursive::reexports::log::warn;
 use rhai::module_resolvers::{FileModuleResolver, ModuleResolversCollection};
-use rhai::{exported_module, Engine, OptimizationLevel};
+use rhai::{exported_module, Dynamic, Engine, EvalAltResult, OptimizationLevel, Position};
 
-use crate::eval::eval_result::{EvalResult, PseudoResource, RhaiPseudoResource};
+use crate::config::extractor::ConfigError;
+use crate::eval::eval_result::{selecting, EvalResult, PseudoResource, RhaiPseudoResource};
 use crate::eval::helpers::*;
+use crate::eval::metrics_module::build_metrics_module;
+use crate::model::metrics::SharedMetricsStore;
+use crate::model::related::RelatedTarget;
+use crate::util::fs::scan_files;
 use crate::util::ui::compute_age;
 
-pub(crate) fn build_engine(paths: &[PathBuf]) -> Engine {
+/// Limits applied to every script evaluated by the engine. Violations fail the evaluation, so
+/// a runaway script shows up as an error in its cell instead of blocking an evaluator thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScriptLimits {
+    /// 0 disables the limit.
+    pub(crate) max_operations: u64,
+    /// Nested function calls, 0 disables the limit.
+    pub(crate) max_call_levels: usize,
+    /// 0 disables the limit.
+    pub(crate) max_string_size: usize,
+    /// 0 disables the limit.
+    pub(crate) max_array_size: usize,
+    /// 0 disables the limit.
+    pub(crate) max_map_size: usize,
+    /// Wall-clock time of a single evaluation guarded by `start_evaluation`.
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 1_000_000,
+            max_call_levels: 64,
+            max_string_size: 1024 * 1024,
+            max_array_size: 100_000,
+            max_map_size: 100_000,
+            timeout: Some(Duration::from_millis(500)),
+        }
+    }
+}
+
+/// The progress callback is only called every this many operations.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+thread_local! {
+    static EVALUATION_STARTED_AT: Cell<Option<Instant>> = Cell::new(None);
+}
+
+/// Times out the evaluations on the current thread until dropped, then restores the
+/// timeout of an enclosing evaluation if any.
+#[must_use]
+pub(crate) struct EvaluationGuard {
+    previous: Option<Instant>,
+}
+
+impl Drop for EvaluationGuard {
+    fn drop(&mut self) {
+        EVALUATION_STARTED_AT.with(|started_at| started_at.set(self.previous));
+    }
+}
+
+/// Starts the timeout of the evaluations on the current thread, evaluations outside of the
+/// returned guard are not timed out.
+pub(crate) fn start_evaluation() -> EvaluationGuard {
+    let previous =
+        EVALUATION_STARTED_AT.with(|started_at| started_at.replace(Some(Instant::now())));
+    EvaluationGuard { previous }
+}
+
+/// Like `EvalAltResult` display, but with the reason a script was terminated.
+pub(crate) fn describe_eval_error(err: &EvalAltResult) -> String {
+    match err {
+        EvalAltResult::ErrorTerminated(reason, _) => reason.to_string(),
+        err => err.to_string(),
+    }
+}
+
+pub(crate) fn build_engine(
+    paths: &[PathBuf],
+    metrics: &SharedMetricsStore,
+    limits: &ScriptLimits,
+) -> Engine {
     let mut engine = Engine::new();
+    apply_limits(&mut engine, limits);
     let collection_resolver = prepare_resolvers(paths);
     engine
         .set_optimization_level(OptimizationLevel::Full)
@@ -18,12 +97,15 @@ pub(crate) fn build_engine(paths: &[PathBuf]) -> Engine {
         .register_fn("compute_age", compute_age)
         .register_fn("PseudoResource", PseudoResource)
         .register_fn("pretty_any", pretty_any)
+        .register_fn("selecting", selecting)
         .register_type_with_name::<EvalResult>("Result")
         .register_type_with_name::<RhaiPseudoResource>("PseudoResource")
+        .register_type_with_name::<RelatedTarget>("RelatedTarget")
         .register_static_module(
             "Result",
             exported_module!(crate::eval::eval_result::eval_result_module).into(),
         )
+        .register_static_module("metrics", build_metrics_module(metrics).into())
         .on_debug(|x, src, pos| {
             let src = src.unwrap_or("unknown");
             warn!("ENGINE: {src} at {pos:?}: {x}");
@@ -33,10 +115,60 @@ pub(crate) fn build_engine(paths: &[PathBuf]) -> Engine {
     engine
 }
 
+fn apply_limits(engine: &mut Engine, limits: &ScriptLimits) {
+    engine
+        .set_max_operations(limits.max_operations)
+        // rhai disallows function calls with 0
+        .set_max_call_levels(match limits.max_call_levels {
+            0 => usize::MAX,
+            max_call_levels => max_call_levels,
+        })
+        .set_max_string_size(limits.max_string_size)
+        .set_max_array_size(limits.max_array_size)
+        .set_max_map_size(limits.max_map_size);
+
+    if let Some(timeout) = limits.timeout {
+        engine.on_progress(move |operations| {
+            if operations % TIMEOUT_CHECK_INTERVAL != 0 {
+                return None;
+            }
+            let started_at = EVALUATION_STARTED_AT.with(Cell::get)?;
+            (started_at.elapsed() > timeout)
+                .then(|| Dynamic::from(format!("Script timed out after {}ms", timeout.as_millis())))
+        });
+    }
+}
+
+/// Compiles every module to find syntax errors, which otherwise only show up when a script
+/// importing the module is evaluated.
+pub(crate) fn compile_modules(paths: &[PathBuf]) -> Vec<ConfigError> {
+    let engine = Engine::new();
+    scan_files(paths)
+        .into_iter()
+        .filter(|path| path.extension().map(|ext| ext == "rhai").unwrap_or(false))
+        .filter_map(|path| {
+            let (position, message) = match std::fs::read_to_string(&path) {
+                Ok(content) => {
+                    let err = engine.compile(content).err()?;
+                    (err.position(), err.err_type().to_string())
+                }
+                Err(err) => (Position::NONE, err.to_string()),
+            };
+            Some(ConfigError {
+                path,
+                context: "module".to_string(),
+                position,
+                message,
+            })
+        })
+        .collect()
+}
+
 fn prepare_resolvers(paths: &[PathBuf]) -> ModuleResolversCollection {
     let mut collection = ModuleResolversCollection::new();
 
-    for path in paths {
+    // the first resolver finding a module wins, later dirs are higher config layers
+    for path in paths.iter().rev() {
         let mut module_resolver = FileModuleResolver::new();
         module_resolver.set_base_path(path).set_extension("rhai");
         collection.push(module_resolver);
@@ -55,7 +187,11 @@ mod tests {
     fn test_modules_loading() {
         let dir = tempfile::tempdir().unwrap();
         let dir = dir.into_path();
-        let engine = build_engine(&[dir.clone()]);
+        let engine = build_engine(
+            &[dir.clone()],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
 
         std::fs::write(
             dir.join("pod.rhai"),
@@ -81,4 +217,27 @@ mod tests {
         let result: i64 = engine.eval_ast_with_scope(&mut scope, &ast).unwrap();
         assert_eq!(result, 10);
     }
+
+    #[test]
+    fn test_limits() {
+        let limits = ScriptLimits {
+            max_operations: 0,
+            timeout: Some(Duration::from_millis(50)),
+            ..ScriptLimits::default()
+        };
+        let engine = build_engine(&[], &SharedMetricsStore::default(), &limits);
+        let evaluation = start_evaluation();
+        let err = engine.eval::<Dynamic>("loop {}").unwrap_err();
+        assert_eq!(describe_eval_error(&err), "Script timed out after 50ms");
+        drop(evaluation);
+        assert!(EVALUATION_STARTED_AT.with(Cell::get).is_none());
+
+        let engine = build_engine(
+            &[],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
+        let err = engine.eval::<Dynamic>("loop {}").unwrap_err();
+        assert!(matches!(*err, EvalAltResult::ErrorTooManyOperations(_)));
+    }
 }
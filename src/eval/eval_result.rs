@@ -4,6 +4,8 @@ use chrono::Utc;
 use rhai::plugin::*;
 use strum_macros::AsRefStr;
 
+use crate::eval::cell_style::CellStyle;
+use crate::model::related::RelatedTarget;
 use crate::util::error::{EvalError, KgvError};
 use crate::util::ui::{ago, compute_age, duration_since};
 
@@ -22,6 +24,7 @@ pub(crate) enum EvalResult {
     Ago(String),
     MaybeString(Result<String, EvalError>),
     Vec(Vec<Dynamic>),
+    Styled(Box<EvalResult>, CellStyle),
 }
 
 impl TryFrom<Dynamic> for EvalResult {
@@ -50,6 +53,8 @@ impl Eq for EvalResult {}
 impl PartialEq<Self> for EvalResult {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
+            (Self::Styled(left, _), right) => left.as_ref() == right,
+            (left, Self::Styled(right, _)) => left == right.as_ref(),
             (Self::Error(left), Self::Error(right)) => left == right,
             (Self::AgoSince(left), Self::AgoSince(right)) => left == right,
             (Self::String(left), Self::String(right)) => left == right,
@@ -66,6 +71,8 @@ impl PartialEq<Self> for EvalResult {
 impl PartialOrd<Self> for EvalResult {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
+            (Self::Styled(left, _), right) => left.as_ref().partial_cmp(right),
+            (left, Self::Styled(right, _)) => left.partial_cmp(right.as_ref()),
             (Self::Error(left), Self::Error(right)) => left.partial_cmp(right),
             (Self::AgoSince(left), Self::AgoSince(right)) => left.partial_cmp(right),
             (Self::String(left), Self::String(right)) => left.partial_cmp(right),
@@ -107,12 +114,16 @@ impl ToString for EvalResult {
             },
             EvalResult::Vec(v) => format!("{:?}", v),
             EvalResult::Ago(ts) => compute_age(ts),
+            EvalResult::Styled(value, _) => value.to_string(),
         }
     }
 }
 
 #[export_module]
 pub(crate) mod eval_result_module {
+    use rhai::Dynamic;
+
+    use crate::eval::cell_style::CellStyle;
     use crate::eval::eval_result::EvalResult;
     use crate::util::error::EvalError;
 
@@ -130,9 +141,40 @@ pub(crate) mod eval_result_module {
     pub(crate) fn Ago(value: String) -> EvalResult {
         EvalResult::Ago(value)
     }
+
+    /// `Result::Styled(value, "red")` or `Result::styled(value, "red")`
+    #[allow(non_snake_case)]
+    #[rhai_fn(name = "Styled", name = "styled")]
+    pub(crate) fn Styled(value: Dynamic, color: String) -> EvalResult {
+        styled(value, Some(color), None)
+    }
+
+    /// `Result::Styled(value, "red", "⚠ ")` or `Result::styled(value, "red", "⚠ ")`
+    #[allow(non_snake_case)]
+    #[rhai_fn(name = "Styled", name = "styled")]
+    pub(crate) fn StyledWithIcon(value: Dynamic, color: String, icon: String) -> EvalResult {
+        styled(value, Some(color), Some(icon))
+    }
+
+    #[rhai_fn(skip)]
+    pub(crate) fn styled(
+        value: Dynamic,
+        color: Option<String>,
+        icon: Option<String>,
+    ) -> EvalResult {
+        let value =
+            EvalResult::try_from(value).unwrap_or_else(|err| EvalResult::Error(format!("{err}")));
+        EvalResult::Styled(Box::new(value), CellStyle { color, icon })
+    }
 }
 
 #[allow(non_snake_case)]
 pub(crate) fn PseudoResource(id: String, resource: Dynamic) -> RhaiPseudoResource {
     RhaiPseudoResource { id, resource }
 }
+
+/// The target of `[gvk, namespace, selecting()]` in `related` scripts: resources whose
+/// `spec.selector` matches the labels of the current resource.
+pub(crate) fn selecting() -> RelatedTarget {
+    RelatedTarget::Selecting
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -7,11 +8,19 @@ use rayon::prelude::IntoParallelRefIterator;
 use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
 use rhai::{Dynamic, Engine, Scope};
 
-use crate::config::extractor::{Column, EmbeddedExtractor, EvaluatorType, PseudoResourceConf};
+use crate::config::extractor::{
+    Column, EmbeddedExtractor, EvaluatorType, PseudoResourceConf, RelatedConf,
+};
+use crate::eval::cell_style::apply_style_rules;
+use crate::eval::engine_factory::{describe_eval_error, start_evaluation};
 use crate::eval::eval_result::{EvalResult, RhaiPseudoResource};
+use crate::eval::metrics_module::take_metrics_read;
+use crate::eval::profiler::{ProfileTarget, SharedEvalProfiler};
+use crate::eval::rhai_objects::SharedRhaiObjects;
 use crate::model::pseudo_resource::PseudoResource;
+use crate::model::related::{parse_gvk, RelatedRef, RelatedTarget};
 use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
-use crate::model::traits::SerializeExt;
+use crate::traits::ext::gvk::GvkExt;
 use crate::util::error::KgvError;
 use crate::util::panics::ResultExt;
 use crate::util::watcher::LazyWatcher;
@@ -19,6 +28,8 @@ use crate::util::watcher::LazyWatcher;
 pub(crate) struct Evaluator {
     pool: ThreadPool,
     watcher: Arc<LazyWatcher<Engine>>,
+    profiler: SharedEvalProfiler,
+    rhai_objects: SharedRhaiObjects,
 }
 
 impl Evaluator {
@@ -34,38 +45,66 @@ impl Evaluator {
         Ok(Self {
             watcher: Arc::clone(watcher),
             pool,
+            profiler: SharedEvalProfiler::default(),
+            rhai_objects: SharedRhaiObjects::default(),
         })
     }
 
+    pub(crate) fn profiler(&self) -> SharedEvalProfiler {
+        Arc::clone(&self.profiler)
+    }
+
+    pub(crate) fn rhai_objects(&self) -> SharedRhaiObjects {
+        Arc::clone(&self.rhai_objects)
+    }
+
     pub(crate) fn evaluate_pseudo_resources(
         &self,
         resource: &ResourceView,
         extractors: &[PseudoResourceConf],
     ) -> Vec<PseudoResource> {
+        let source = self.to_rhai_object(resource).unwrap_or_log();
         let mut scope = Scope::new();
-        scope.push("resource", self.to_rhai_object(resource).unwrap_or_log());
+        scope.push_constant("resource", source.clone());
 
-        let pseudo_resources: Vec<(String, Vec<RhaiPseudoResource>)> = self.pool.install(|| {
+        let gvk = resource.gvk();
+        type KeyedPseudoResources = Vec<(RhaiPseudoResource, Option<String>)>;
+        let pseudo_resources: Vec<(String, KeyedPseudoResources)> = self.pool.install(|| {
             let engine = self.watcher.value();
             extractors
                 .par_iter()
                 .filter_map(|extractor| {
-                    match Self::evaluate_pseudo_resource(
-                        engine.as_ref(),
-                        extractor,
-                        scope.clone_visible(),
-                    ) {
-                        Ok(pseudo_resources) => {
-                            Some((extractor.name.to_string(), pseudo_resources))
-                        }
-                        Err(err) => {
-                            error!(
-                                "Failed to evaluate pseudo resource {}: {err}",
-                                resource.full_unique_name()
-                            );
-                            None
+                    let target = || ProfileTarget::PseudoResources(extractor.name.clone());
+                    self.profiler.measure(&gvk, target, || {
+                        match Self::evaluate_pseudo_resource(
+                            engine.as_ref(),
+                            extractor,
+                            scope.clone_visible(),
+                        ) {
+                            Ok(pseudo_resources) => {
+                                let pseudo_resources = pseudo_resources
+                                    .into_iter()
+                                    .map(|pseudo_resource| {
+                                        let key = Self::evaluate_pseudo_resource_key(
+                                            engine.as_ref(),
+                                            extractor,
+                                            &source,
+                                            &pseudo_resource,
+                                        );
+                                        (pseudo_resource, key)
+                                    })
+                                    .collect();
+                                Some((extractor.name.to_string(), pseudo_resources))
+                            }
+                            Err(err) => {
+                                error!(
+                                    "Failed to evaluate pseudo resource {}: {err}",
+                                    resource.full_unique_name()
+                                );
+                                None
+                            }
                         }
-                    }
+                    })
                 })
                 .collect()
         });
@@ -76,57 +115,235 @@ impl Evaluator {
                 let extractor_name = std::iter::repeat(extractor_name);
                 extractor_name.zip(resources)
             })
-            .map(|(extractor_name, rhai_pseudo_resource)| PseudoResource {
-                id: rhai_pseudo_resource.id,
-                extractor_name,
-                resource: rhai_pseudo_resource.resource,
-                source: resource.clone(),
+            .map(
+                |(extractor_name, (rhai_pseudo_resource, key))| PseudoResource {
+                    id: rhai_pseudo_resource.id,
+                    extractor_name,
+                    resource: rhai_pseudo_resource.resource,
+                    source: resource.clone(),
+                    key,
+                    deleted_at: None,
+                },
+            )
+            .collect()
+    }
+
+    /// Evaluates `related` scripts of the resource. Malformed references are logged and
+    /// skipped.
+    pub(crate) fn evaluate_related(
+        &self,
+        resource: &ResourceView,
+        related: &[RelatedConf],
+    ) -> Vec<RelatedRef> {
+        let mut scope = Scope::new();
+        scope.push_constant("resource", self.to_rhai_object(resource).unwrap_or_log());
+
+        let engine = self.watcher.value();
+        related
+            .iter()
+            .flat_map(|conf| {
+                let items =
+                    match Self::evaluate_related_conf(engine.as_ref(), conf, scope.clone_visible())
+                    {
+                        Ok(items) => items,
+                        Err(err) => {
+                            error!(
+                                "Failed to evaluate related {} for {}: {err}",
+                                conf.name,
+                                resource.full_unique_name()
+                            );
+                            vec![]
+                        }
+                    };
+                items
+                    .into_iter()
+                    .filter_map(|item| match Self::parse_related_ref(&conf.name, item) {
+                        Ok(related_ref) => Some(related_ref),
+                        Err(err) => {
+                            error!(
+                                "Invalid related {} for {}: {err}",
+                                conf.name,
+                                resource.full_unique_name()
+                            );
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>()
             })
             .collect()
     }
 
-    fn to_rhai_object(&self, resource: &ResourceView) -> Result<rhai::Map, KgvError> {
-        self.pool.install(|| {
-            let engine = self.watcher.value();
+    fn evaluate_related_conf(
+        engine: &Engine,
+        conf: &RelatedConf,
+        mut scope: Scope,
+    ) -> Result<Vec<Dynamic>, KgvError> {
+        let _evaluation = start_evaluation();
+        let value: Dynamic = engine.eval_ast_with_scope(&mut scope, &conf.ast)?;
+        Ok(value.into_typed_array::<Dynamic>()?)
+    }
 
-            let json = match resource.to_json() {
-                Ok(json) => json,
-                Err(err) => return Err(KgvError::SerdeJsonError(err)),
-            };
+    /// Parses a `[gvk, namespace, target]` item, where the target is a name, a label
+    /// selector map or `selecting()`.
+    fn parse_related_ref(relation: &str, item: Dynamic) -> Result<RelatedRef, KgvError> {
+        let type_name = item.type_name();
+        let conversion_error = || KgvError::TypeConversionError(type_name.to_string());
 
-            match engine.parse_json(&json, true) {
-                Ok(parsed_json) => Ok(parsed_json),
-                Err(err) => Err(KgvError::EngineJsonParseError(json, *err)),
+        let mut parts = item.into_typed_array::<Dynamic>()?.into_iter();
+        let (gvk, namespace, target) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(gvk), Some(namespace), Some(target)) if parts.next().is_none() => {
+                (gvk, namespace, target)
             }
+            _ => return Err(conversion_error()),
+        };
+
+        let gvk = parse_gvk(&gvk.into_string()?).ok_or_else(conversion_error)?;
+        let namespace = if namespace.is_unit() {
+            String::new()
+        } else {
+            namespace.into_string()?
+        };
+        let target = if target.is_string() {
+            RelatedTarget::Name(target.into_string()?)
+        } else if target.is_map() {
+            let selector = target.cast::<rhai::Map>();
+            RelatedTarget::Selector(
+                selector
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            )
+        } else {
+            target
+                .try_cast::<RelatedTarget>()
+                .ok_or_else(conversion_error)?
+        };
+
+        Ok(RelatedRef {
+            relation: relation.to_string(),
+            gvk,
+            namespace,
+            target,
         })
     }
 
+    /// Scripts get the value as a constant, it may be shared with the cache.
+    fn to_rhai_object(&self, resource: &ResourceView) -> Result<Dynamic, KgvError> {
+        self.profiler.measure(
+            &resource.gvk(),
+            || ProfileTarget::ToRhaiObject,
+            || self.rhai_objects.get(resource),
+        )
+    }
+
     pub(crate) fn evaluate_columns(
         &self,
         resource: ResourceView,
-        columns: &[Column],
+        columns: &Arc<Vec<Column>>,
     ) -> Result<EvaluatedResource, KgvError> {
+        Ok(self.evaluate_columns_tracking_metrics(resource, columns)?.0)
+    }
+
+    /// Also returns the names of the columns that called the `metrics` module.
+    pub(crate) fn evaluate_columns_tracking_metrics(
+        &self,
+        resource: ResourceView,
+        columns: &Arc<Vec<Column>>,
+    ) -> Result<(EvaluatedResource, HashSet<String>), KgvError> {
+        let evaluated = self.evaluate_selected_columns(&resource, columns, |_| true)?;
+
+        let mut values = Vec::with_capacity(evaluated.len());
+        let mut metrics_columns = HashSet::new();
+        // every column is selected
+        for (col, (value, reads_metrics)) in columns.iter().zip(evaluated.into_iter().flatten()) {
+            if reads_metrics {
+                metrics_columns.insert(col.name.clone());
+            }
+            values.push(value);
+        }
+
+        let evaluated_resource = EvaluatedResource {
+            values: Arc::new(values),
+            resource,
+            columns: Arc::clone(columns),
+        };
+        Ok((evaluated_resource, metrics_columns))
+    }
+
+    /// Re-evaluates the named columns only, the values of the other columns are kept.
+    pub(crate) fn reevaluate_columns(
+        &self,
+        evaluated_resource: &EvaluatedResource,
+        names: &HashSet<String>,
+    ) -> Result<EvaluatedResource, KgvError> {
+        let evaluated = self.evaluate_selected_columns(
+            &evaluated_resource.resource,
+            &evaluated_resource.columns,
+            |col| names.contains(&col.name),
+        )?;
+
+        let values = evaluated
+            .into_iter()
+            .zip(evaluated_resource.values.iter())
+            .map(|(evaluated, old_value)| match evaluated {
+                Some((value, _)) => value,
+                None => old_value.clone(),
+            })
+            .collect();
+
+        Ok(EvaluatedResource {
+            values: Arc::new(values),
+            resource: evaluated_resource.resource.clone(),
+            columns: Arc::clone(&evaluated_resource.columns),
+        })
+    }
+
+    /// Values of the selected columns, `None` for the others, along with whether a column
+    /// called the `metrics` module.
+    fn evaluate_selected_columns(
+        &self,
+        resource: &ResourceView,
+        columns: &[Column],
+        selected: impl Fn(&Column) -> bool + Sync,
+    ) -> Result<Vec<Option<(EvalResult, bool)>>, KgvError> {
         let mut scope = Scope::new();
-        scope.push("resource", self.to_rhai_object(&resource)?);
-        if let ResourceView::PseudoResource(resource) = &resource {
-            scope.push("source", self.to_rhai_object(&resource.source)?);
+        scope.push_constant("resource", self.to_rhai_object(resource)?);
+        if let ResourceView::PseudoResource(resource) = resource {
+            scope.push_constant("source", self.to_rhai_object(&resource.source)?);
         }
 
+        let gvk = resource.gvk();
         let values = self.pool.install(|| {
             let engine = self.watcher.value();
 
             columns
                 .par_iter()
                 .map(|col| {
-                    Self::evaluate_column(engine.deref(), col, &resource, scope.clone_visible())
+                    if !selected(col) {
+                        return None;
+                    }
+                    // columns are evaluated on a single thread each, so the flag belongs to
+                    // this column
+                    take_metrics_read();
+                    let value = self.profiler.measure(
+                        &gvk,
+                        || ProfileTarget::Column(col.name.clone()),
+                        || {
+                            Self::evaluate_column(
+                                engine.deref(),
+                                col,
+                                resource,
+                                scope.clone_visible(),
+                            )
+                        },
+                    );
+                    let reads_metrics = take_metrics_read();
+                    Some((apply_style_rules(value, &col.styles), reads_metrics))
                 })
                 .collect::<Vec<_>>()
         });
 
-        Ok(EvaluatedResource {
-            values: Arc::new(values),
-            resource,
-        })
+        Ok(values)
     }
 
     fn evaluate_embedded(extractor: &EmbeddedExtractor, resource: &ResourceView) -> EvalResult {
@@ -135,6 +352,7 @@ impl Evaluator {
             EmbeddedExtractor::Name => EvalResult::String(resource.name()),
             EmbeddedExtractor::Status => EvalResult::String(resource.status()),
             EmbeddedExtractor::Age => EvalResult::AgoSince(resource.creation_timestamp()),
+            EmbeddedExtractor::Events => EvalResult::String("-".to_string()),
         }
     }
 
@@ -143,11 +361,45 @@ impl Evaluator {
         extractor: &PseudoResourceConf,
         mut scope: Scope,
     ) -> Result<Vec<RhaiPseudoResource>, KgvError> {
+        let _evaluation = start_evaluation();
         let value: Dynamic = engine.eval_ast_with_scope(&mut scope, &extractor.ast)?;
         let array = value.into_typed_array::<RhaiPseudoResource>()?;
         Ok(array)
     }
 
+    /// Evaluates the extractor `key` script with the pseudo resource as `resource` and its
+    /// source as `source`. Falls back to the pseudo resource id if the script fails.
+    fn evaluate_pseudo_resource_key(
+        engine: &Engine,
+        extractor: &PseudoResourceConf,
+        source: &Dynamic,
+        pseudo_resource: &RhaiPseudoResource,
+    ) -> Option<String> {
+        let ast = extractor.key.as_ref()?;
+        let mut scope = Scope::new();
+        scope.push("resource", pseudo_resource.resource.clone());
+        scope.push_constant("source", source.clone());
+
+        let _evaluation = start_evaluation();
+        match engine.eval_ast_with_scope::<Dynamic>(&mut scope, ast) {
+            Ok(key) if !key.is_unit() => Some(key.to_string()),
+            Ok(_) => {
+                error!(
+                    "Key of pseudo resource {}/{} is empty",
+                    extractor.name, pseudo_resource.id
+                );
+                None
+            }
+            Err(err) => {
+                error!(
+                    "Failed to evaluate key of pseudo resource {}/{}: {err}",
+                    extractor.name, pseudo_resource.id
+                );
+                None
+            }
+        }
+    }
+
     pub(crate) fn evaluate_column(
         engine: &Engine,
         column: &Column,
@@ -158,6 +410,7 @@ impl Evaluator {
             EvaluatorType::Embedded(embedded) => Self::evaluate_embedded(embedded, resource),
 
             EvaluatorType::AST(ast) => {
+                let _evaluation = start_evaluation();
                 let dynamic_result: Result<Dynamic, _> =
                     engine.eval_ast_with_scope(&mut scope, ast);
                 match dynamic_result {
@@ -180,13 +433,14 @@ impl Evaluator {
                         }
                     }
                     Err(err) => {
+                        let message = describe_eval_error(&err);
                         error!(
                             "Failed to evaluate column {} (code: {}): {}",
                             column.name,
                             ast.source().unwrap_or(""),
-                            err
+                            message
                         );
-                        EvalResult::Error(format!("{}", err))
+                        EvalResult::Error(message)
                     }
                 }
             }
@@ -202,7 +456,9 @@ mod tests {
     use k8s_openapi::serde_json;
     use k8s_openapi::serde_json::{json, Value};
 
-    use crate::eval::engine_factory::build_engine;
+    use crate::config::extractor::SortType;
+    use crate::eval::engine_factory::{build_engine, ScriptLimits};
+    use crate::model::metrics::SharedMetricsStore;
 
     use super::*;
 
@@ -232,13 +488,26 @@ mod tests {
     fn test() {
         let pod: Pod = serde_json::from_value(pod_json()).unwrap();
 
-        let watcher = Arc::new(LazyWatcher::new(vec![], build_engine).unwrap());
+        let watcher = Arc::new(
+            LazyWatcher::new(vec![], |paths| {
+                build_engine(
+                    paths,
+                    &SharedMetricsStore::default(),
+                    &ScriptLimits::default(),
+                )
+            })
+            .unwrap(),
+        );
         let evaluator = Evaluator::new(10, &watcher).unwrap();
 
-        let engine = build_engine(&[]);
+        let engine = build_engine(
+            &[],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
 
         let resource = ResourceView::Pod(Arc::new(pod));
-        let columns = [
+        let columns = Arc::new(vec![
             Column {
                 name: "a".to_string(),
                 display_name: "a".to_string(),
@@ -246,6 +515,8 @@ mod tests {
                 evaluator_type: EvaluatorType::AST(
                     engine.compile(r#"resource.metadata.name"#).unwrap(),
                 ),
+                sort_type: SortType::Auto,
+                styles: vec![],
             },
             Column {
                 name: "b".to_string(),
@@ -256,8 +527,10 @@ mod tests {
                         .compile(r#"Result::String(resource.spec.containers[0].name)"#)
                         .unwrap(),
                 ),
+                sort_type: SortType::Auto,
+                styles: vec![],
             },
-        ];
+        ]);
 
         let result = evaluator.evaluate_columns(resource, &columns);
         assert!(result.is_ok());
@@ -272,21 +545,36 @@ mod tests {
     fn test_extract_vec() {
         let pod: Pod = serde_json::from_value(pod_json()).unwrap();
 
-        let watcher = Arc::new(LazyWatcher::new(vec![], build_engine).unwrap());
+        let watcher = Arc::new(
+            LazyWatcher::new(vec![], |paths| {
+                build_engine(
+                    paths,
+                    &SharedMetricsStore::default(),
+                    &ScriptLimits::default(),
+                )
+            })
+            .unwrap(),
+        );
         let evaluator = Evaluator::new(10, &watcher).unwrap();
 
-        let engine = build_engine(&[]);
+        let engine = build_engine(
+            &[],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
 
         let resource = ResourceView::Pod(Arc::new(pod));
 
-        let columns = [Column {
+        let columns = Arc::new(vec![Column {
             name: "extract_containers".to_string(),
             display_name: "extract_containers".to_string(),
             width: 0,
             evaluator_type: EvaluatorType::AST(
                 engine.compile(r#"resource.spec.containers"#).unwrap(),
             ),
-        }];
+            sort_type: SortType::Auto,
+            styles: vec![],
+        }]);
 
         let result = evaluator.evaluate_columns(resource, &columns);
         assert!(result.is_ok());
@@ -301,10 +589,23 @@ mod tests {
     fn test_extract_pseudo() {
         let pod: Pod = serde_json::from_value(pod_json()).unwrap();
 
-        let watcher = Arc::new(LazyWatcher::new(vec![], build_engine).unwrap());
+        let watcher = Arc::new(
+            LazyWatcher::new(vec![], |paths| {
+                build_engine(
+                    paths,
+                    &SharedMetricsStore::default(),
+                    &ScriptLimits::default(),
+                )
+            })
+            .unwrap(),
+        );
         let evaluator = Evaluator::new(10, &watcher).unwrap();
 
-        let engine = build_engine(&[]);
+        let engine = build_engine(
+            &[],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
 
         let resource = ResourceView::Pod(Arc::new(pod));
 
@@ -324,10 +625,66 @@ mod tests {
             "#,
                 )
                 .unwrap(),
+            key: Some(
+                engine
+                    .compile(r#"source.metadata.name + ":" + resource.name"#)
+                    .unwrap(),
+            ),
         }];
 
         let pseudo_resources =
             evaluator.evaluate_pseudo_resources(&resource, &pseudo_col_extractor_configs);
         assert_eq!(pseudo_resources.len(), 2);
+        assert_eq!(pseudo_resources[0].key.as_deref(), Some("example:example1"));
+    }
+
+    #[test]
+    fn test_evaluate_related() {
+        let mut pod_json = pod_json();
+        pod_json["metadata"]["namespace"] = json!("default");
+        pod_json["spec"]["nodeName"] = json!("node-1");
+        let pod: Pod = serde_json::from_value(pod_json).unwrap();
+
+        let watcher = Arc::new(
+            LazyWatcher::new(vec![], |paths| {
+                build_engine(
+                    paths,
+                    &SharedMetricsStore::default(),
+                    &ScriptLimits::default(),
+                )
+            })
+            .unwrap(),
+        );
+        let evaluator = Evaluator::new(10, &watcher).unwrap();
+        let engine = build_engine(
+            &[],
+            &SharedMetricsStore::default(),
+            &ScriptLimits::default(),
+        );
+
+        let related = [RelatedConf {
+            name: "related".to_string(),
+            ast: engine
+                .compile(
+                    r#"
+            let ns = resource.metadata.namespace;
+            [
+                ["v1/Node", (), resource.spec.nodeName],
+                ["v1/Service", ns, selecting()],
+                ["discovery.k8s.io/v1/EndpointSlice", ns, #{"kubernetes.io/service-name": "web"}],
+                ["Node", (), "invalid"],
+            ]
+            "#,
+                )
+                .unwrap(),
+        }];
+
+        let resource = ResourceView::Pod(Arc::new(pod));
+        let related = evaluator.evaluate_related(&resource, &related);
+        assert_eq!(related.len(), 3);
+        assert_eq!(related[0].target, RelatedTarget::Name("node-1".to_string()));
+        assert_eq!(related[1].namespace, "default");
+        assert_eq!(related[1].target, RelatedTarget::Selecting);
+        assert_eq!(related[2].gvk.group, "discovery.k8s.io");
     }
 }
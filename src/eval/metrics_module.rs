@@ -0,0 +1,189 @@
+use std::cell::Cell;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use rhai::{Dynamic, ImmutableString, Map, Module};
+
+use crate::model::metrics::{
+    format_cpu, format_memory, percent, pod_key, AllocationKind, MetricsStore, ResourceUsage,
+    SharedMetricsStore,
+};
+use crate::traits::ext::rw_lock::RwLockExt;
+
+const NO_VALUE: &str = "-";
+
+thread_local! {
+    static METRICS_READ: Cell<bool> = Cell::new(false);
+}
+
+/// Whether a `metrics` function was called on this thread since the last call; columns
+/// that read metrics are re-evaluated when the usage changes.
+pub(crate) fn take_metrics_read() -> bool {
+    METRICS_READ.with(|read| read.replace(false))
+}
+
+fn mark_metrics_read() {
+    METRICS_READ.with(|read| read.set(true));
+}
+
+/// Builds the `metrics` module. Functions return `-` until the usage of a resource is known,
+/// i.e. if `metrics-server` is not installed.
+pub(crate) fn build_metrics_module(store: &SharedMetricsStore) -> Module {
+    let mut module = Module::new();
+
+    register(&mut module, store, "pod_cpu", |store, resource| {
+        let usage = store.pod_usage(&resource_pod_key(resource));
+        usage.map(|usage| format_cpu(usage.cpu)).into_dynamic()
+    });
+    register(&mut module, store, "pod_memory", |store, resource| {
+        let usage = store.pod_usage(&resource_pod_key(resource));
+        usage
+            .map(|usage| format_memory(usage.memory))
+            .into_dynamic()
+    });
+    register(&mut module, store, "pod_cpu_rl", |store, resource| {
+        let containers = store.pod_allocations(&resource_pod_key(resource));
+        containers
+            .iter()
+            .map(|c| c.cpu_rl.as_str())
+            .join(",")
+            .into()
+    });
+    register(&mut module, store, "pod_memory_rl", |store, resource| {
+        let containers = store.pod_allocations(&resource_pod_key(resource));
+        containers
+            .iter()
+            .map(|c| c.memory_rl.as_str())
+            .join(",")
+            .into()
+    });
+    register_with_arg(
+        &mut module,
+        store,
+        "pod_cpu_percent",
+        |store, resource, kind| pod_percent(store, resource, kind, |usage| usage.cpu),
+    );
+    register_with_arg(
+        &mut module,
+        store,
+        "pod_memory_percent",
+        |store, resource, kind| pod_percent(store, resource, kind, |usage| usage.memory),
+    );
+
+    register_with_arg(
+        &mut module,
+        store,
+        "container_cpu",
+        |store, pod, container| {
+            let usage = store.container_usage(&resource_pod_key(pod), container);
+            usage.map(|usage| format_cpu(usage.cpu)).into_dynamic()
+        },
+    );
+    register_with_arg(
+        &mut module,
+        store,
+        "container_memory",
+        |store, pod, container| {
+            let usage = store.container_usage(&resource_pod_key(pod), container);
+            usage
+                .map(|usage| format_memory(usage.memory))
+                .into_dynamic()
+        },
+    );
+
+    register(&mut module, store, "node_cpu", |store, resource| {
+        let usage = store.node_usage(&metadata_field(resource, "name"));
+        usage.map(|usage| format_cpu(usage.cpu)).into_dynamic()
+    });
+    register(&mut module, store, "node_memory", |store, resource| {
+        let usage = store.node_usage(&metadata_field(resource, "name"));
+        usage
+            .map(|usage| format_memory(usage.memory))
+            .into_dynamic()
+    });
+    register(&mut module, store, "node_cpu_percent", |store, resource| {
+        node_percent(store, resource, |usage| usage.cpu)
+    });
+    register(
+        &mut module,
+        store,
+        "node_memory_percent",
+        |store, resource| node_percent(store, resource, |usage| usage.memory),
+    );
+
+    module
+}
+
+fn register(
+    module: &mut Module,
+    store: &SharedMetricsStore,
+    name: &str,
+    func: impl Fn(&MetricsStore, &Map) -> Dynamic + Send + Sync + 'static,
+) {
+    let store = Arc::clone(store);
+    module.set_native_fn(name, move |resource: &mut Map| {
+        mark_metrics_read();
+        Ok(func(&store.read_unwrap(), resource))
+    });
+}
+
+fn register_with_arg(
+    module: &mut Module,
+    store: &SharedMetricsStore,
+    name: &str,
+    func: impl Fn(&MetricsStore, &Map, &str) -> Dynamic + Send + Sync + 'static,
+) {
+    let store = Arc::clone(store);
+    module.set_native_fn(name, move |resource: &mut Map, arg: ImmutableString| {
+        mark_metrics_read();
+        Ok(func(&store.read_unwrap(), resource, arg.as_str()))
+    });
+}
+
+fn pod_percent(
+    store: &MetricsStore,
+    resource: &Map,
+    kind: &str,
+    value: fn(&ResourceUsage) -> f64,
+) -> Dynamic {
+    let key = resource_pod_key(resource);
+    AllocationKind::parse(kind)
+        .and_then(|kind| store.pod_allocation(&key, kind))
+        .zip(store.pod_usage(&key))
+        .and_then(|(allocation, usage)| percent(value(&usage), value(&allocation)))
+        .into_dynamic()
+}
+
+fn node_percent(store: &MetricsStore, resource: &Map, value: fn(&ResourceUsage) -> f64) -> Dynamic {
+    let name = metadata_field(resource, "name");
+    store
+        .node_allocatable(&name)
+        .zip(store.node_usage(&name))
+        .and_then(|(allocatable, usage)| percent(value(&usage), value(&allocatable)))
+        .into_dynamic()
+}
+
+fn metadata_field(resource: &Map, field: &str) -> String {
+    resource
+        .get("metadata")
+        .and_then(|metadata| metadata.read_lock::<Map>())
+        .and_then(|metadata| metadata.get(field).map(|value| value.to_string()))
+        .unwrap_or_default()
+}
+
+fn resource_pod_key(resource: &Map) -> String {
+    pod_key(
+        &metadata_field(resource, "namespace"),
+        &metadata_field(resource, "name"),
+    )
+}
+
+trait IntoDynamicExt {
+    fn into_dynamic(self) -> Dynamic;
+}
+
+impl<T: Into<Dynamic>> IntoDynamicExt for Option<T> {
+    fn into_dynamic(self) -> Dynamic {
+        self.map(Into::into).unwrap_or_else(|| NO_VALUE.into())
+    }
+}
@@ -1,4 +1,10 @@
+pub(crate) mod cell_style;
 pub(crate) mod engine_factory;
 pub(crate) mod eval_result;
 pub(crate) mod evaluator;
 pub(crate) mod helpers;
+pub(crate) mod metrics_module;
+pub(crate) mod profiler;
+pub(crate) mod rhai_objects;
+pub(crate) mod sort;
+pub(crate) mod test_runner;
@@ -0,0 +1,198 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kube::api::GroupVersionKind;
+
+use crate::reexports::sync::Mutex;
+use crate::traits::ext::gvk::GvkNameExt;
+use crate::traits::ext::mutex::MutexExt;
+
+pub(crate) type SharedEvalProfiler = Arc<EvalProfiler>;
+
+/// Percentiles are computed over this many last samples of each target.
+const MAX_SAMPLES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum ProfileTarget {
+    Column(String),
+    /// A pseudo resource extractor, including its `key` script.
+    PseudoResources(String),
+    /// Conversion of the resource into a `rhai::Map`, cache hits included.
+    ToRhaiObject,
+}
+
+impl Display for ProfileTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileTarget::Column(name) => write!(f, "column {name}"),
+            ProfileTarget::PseudoResources(name) => write!(f, "pseudo resources {name}"),
+            ProfileTarget::ToRhaiObject => write!(f, "rhai map conversion"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    count: u64,
+    total: Duration,
+    samples: VecDeque<Duration>,
+}
+
+impl Stats {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+    }
+
+    fn p99(&self) -> Duration {
+        let mut samples = self.samples.iter().copied().collect::<Vec<_>>();
+        samples.sort();
+        let index = (samples.len() * 99 / 100).min(samples.len().saturating_sub(1));
+        samples.get(index).copied().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProfileEntry {
+    pub(crate) gvk: String,
+    pub(crate) target: ProfileTarget,
+    pub(crate) count: u64,
+    pub(crate) total: Duration,
+    pub(crate) mean: Duration,
+    pub(crate) p99: Duration,
+}
+
+/// Time spent in rhai evaluations per GVK and per column or extractor, shown in the
+/// profiler window (`F12`). Evaluations are only recorded while the window is open.
+#[derive(Debug, Default)]
+pub(crate) struct EvalProfiler {
+    /// The number of open profiler windows.
+    sessions: AtomicUsize,
+    stats: Mutex<HashMap<(String, ProfileTarget), Stats>>,
+}
+
+/// Records evaluations until dropped.
+#[derive(Debug)]
+pub(crate) struct ProfilingSession(SharedEvalProfiler);
+
+impl Drop for ProfilingSession {
+    fn drop(&mut self) {
+        self.0.sessions.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl EvalProfiler {
+    pub(crate) fn start(self: &Arc<Self>) -> ProfilingSession {
+        self.sessions.fetch_add(1, Ordering::Relaxed);
+        ProfilingSession(Arc::clone(self))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.sessions.load(Ordering::Relaxed) > 0
+    }
+
+    pub(crate) fn measure<R>(
+        &self,
+        gvk: &GroupVersionKind,
+        target: impl FnOnce() -> ProfileTarget,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        if !self.is_enabled() {
+            return f();
+        }
+        let started_at = Instant::now();
+        let result = f();
+        self.record(gvk, target(), started_at.elapsed());
+        result
+    }
+
+    fn record(&self, gvk: &GroupVersionKind, target: ProfileTarget, elapsed: Duration) {
+        self.stats
+            .lock_unwrap()
+            .entry((gvk.full_name(), target))
+            .or_default()
+            .record(elapsed);
+    }
+
+    /// Sorted by the total time, the slowest first.
+    pub(crate) fn snapshot(&self) -> Vec<ProfileEntry> {
+        let mut entries = self
+            .stats
+            .lock_unwrap()
+            .iter()
+            .map(|((gvk, target), stats)| ProfileEntry {
+                gvk: gvk.clone(),
+                target: target.clone(),
+                count: stats.count,
+                total: stats.total,
+                mean: Duration::from_nanos(
+                    (stats.total.as_nanos() / stats.count.max(1) as u128) as u64,
+                ),
+                p99: stats.p99(),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| (&a.gvk, &a.target).cmp(&(&b.gvk, &b.target)))
+        });
+        entries
+    }
+
+    pub(crate) fn reset(&self) {
+        self.stats.lock_unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let gvk = GroupVersionKind::gvk("", "v1", "Pod");
+        let profiler = EvalProfiler::default();
+        for millis in 1..=100 {
+            profiler.record(
+                &gvk,
+                ProfileTarget::Column("age".to_string()),
+                Duration::from_millis(millis),
+            );
+        }
+        profiler.record(&gvk, ProfileTarget::ToRhaiObject, Duration::from_secs(10));
+
+        let entries = profiler.snapshot();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].target, ProfileTarget::ToRhaiObject);
+        assert_eq!(entries[1].count, 100);
+        assert_eq!(entries[1].total, Duration::from_millis(5050));
+        assert_eq!(entries[1].mean, Duration::from_micros(50500));
+        assert_eq!(entries[1].p99, Duration::from_millis(100));
+
+        profiler.reset();
+        assert!(profiler.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_session() {
+        let gvk = GroupVersionKind::gvk("", "v1", "Pod");
+        let profiler = SharedEvalProfiler::default();
+        profiler.measure(&gvk, || ProfileTarget::ToRhaiObject, || ());
+        assert!(profiler.snapshot().is_empty());
+
+        let session = profiler.start();
+        profiler.measure(&gvk, || ProfileTarget::ToRhaiObject, || ());
+        assert_eq!(profiler.snapshot().len(), 1);
+
+        drop(session);
+        profiler.measure(&gvk, || ProfileTarget::ToRhaiObject, || ());
+        assert_eq!(profiler.snapshot()[0].count, 1);
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::model::resource::resource_view::ResourceView;
+use crate::model::traits::SerializeExt;
+use crate::reexports::sync::Mutex;
+use crate::traits::ext::mutex::MutexExt;
+use crate::util::error::KgvError;
+
+pub(crate) type SharedRhaiObjects = Arc<RhaiObjects>;
+
+/// Converts a resource into the `rhai::Map` seen by scripts and templates.
+pub(crate) fn to_rhai_map(resource: &ResourceView) -> Result<rhai::Map, KgvError> {
+    let value = resource.to_rhai()?;
+    let type_name = value.type_name();
+    value.try_cast::<rhai::Map>().ok_or_else(|| {
+        KgvError::TypeConversionError(format!(
+            "Resource {} is converted into {type_name} instead of a map",
+            resource.full_unique_name()
+        ))
+    })
+}
+
+struct CachedObject {
+    resource_version: String,
+    /// A shared map, clones of it are cheap and point to the same map.
+    value: rhai::Dynamic,
+}
+
+/// Converted resources by uid, shared by columns, pseudo resources, related scripts and
+/// detail templates. An entry is replaced once a newer `resourceVersion` is converted.
+///
+/// Cached values are shared, scripts get them as constants so that they can't modify them.
+///
+/// Pseudo resources are not cached, they already hold a rhai value and their content
+/// depends on the extractor scripts rather than the `resourceVersion` of the source.
+#[derive(Default)]
+pub(crate) struct RhaiObjects {
+    cache: Mutex<HashMap<String, CachedObject>>,
+}
+
+impl RhaiObjects {
+    /// The resource as a `rhai::Map` value.
+    pub(crate) fn get(&self, resource: &ResourceView) -> Result<rhai::Dynamic, KgvError> {
+        let (uid, resource_version) = match (resource, resource.uid(), resource.resource_version())
+        {
+            (ResourceView::PseudoResource(_), _, _) => {
+                return Ok(rhai::Dynamic::from_map(to_rhai_map(resource)?))
+            }
+            (_, Some(uid), Some(resource_version)) => (uid, resource_version),
+            _ => return Ok(rhai::Dynamic::from_map(to_rhai_map(resource)?)),
+        };
+
+        if let Some(cached) = self.cache.lock_unwrap().get(&uid) {
+            if cached.resource_version == resource_version {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let value = rhai::Dynamic::from_map(to_rhai_map(resource)?).into_shared();
+        self.cache.lock_unwrap().insert(
+            uid,
+            CachedObject {
+                resource_version,
+                value: value.clone(),
+            },
+        );
+        Ok(value)
+    }
+
+    pub(crate) fn forget(&self, uid: &str) {
+        self.cache.lock_unwrap().remove(uid);
+    }
+
+    /// Drops the entries of resources that are not known anymore.
+    pub(crate) fn retain(&self, is_known: impl Fn(&str) -> bool) {
+        self.cache.lock_unwrap().retain(|uid, _| is_known(uid));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::Pod;
+    use kube::api::ObjectMeta;
+
+    use super::*;
+
+    fn pod(resource_version: &str, node_name: &str) -> ResourceView {
+        ResourceView::Pod(Arc::new(Pod {
+            metadata: ObjectMeta {
+                name: Some("pod".to_string()),
+                uid: Some("uid".to_string()),
+                resource_version: Some(resource_version.to_string()),
+                ..Default::default()
+            },
+            spec: Some(k8s_openapi::api::core::v1::PodSpec {
+                node_name: Some(node_name.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+    }
+
+    fn node_name(value: &rhai::Dynamic) -> String {
+        let map = value.read_lock::<rhai::Map>().unwrap();
+        let spec = map["spec"].clone_cast::<rhai::Map>();
+        spec["nodeName"].clone().into_string().unwrap()
+    }
+
+    #[test]
+    fn test_cache() {
+        let objects = RhaiObjects::default();
+
+        let map = objects.get(&pod("1", "a")).unwrap();
+        assert_eq!(node_name(&map), "a");
+        assert!(!map.read_lock::<rhai::Map>().unwrap().contains_key("status"));
+
+        // the same version is served from the cache
+        let map = objects.get(&pod("1", "b")).unwrap();
+        assert_eq!(node_name(&map), "a");
+        assert!(map.is_shared());
+
+        let map = objects.get(&pod("2", "b")).unwrap();
+        assert_eq!(node_name(&map), "b");
+        assert_eq!(objects.cache.lock_unwrap().len(), 1);
+
+        objects.forget("uid");
+        assert_eq!(objects.cache.lock_unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+
+use chrono::Utc;
+
+use crate::config::extractor::SortType;
+use crate::eval::eval_result::EvalResult;
+use crate::util::ui::duration_since;
+
+/// Compares two evaluated column values according to the column sort type. Values that
+/// can't be interpreted as the requested type are sorted after the rest.
+pub(crate) fn compare_eval_results(
+    left: &EvalResult,
+    right: &EvalResult,
+    sort_type: SortType,
+) -> Ordering {
+    match (left, right) {
+        (EvalResult::Styled(left, _), right) => {
+            return compare_eval_results(left, right, sort_type)
+        }
+        (left, EvalResult::Styled(right, _)) => {
+            return compare_eval_results(left, right, sort_type)
+        }
+        _ => {}
+    }
+
+    match sort_type {
+        SortType::Auto => left
+            .partial_cmp(right)
+            .unwrap_or_else(|| natural_cmp(&left.to_string(), &right.to_string())),
+        SortType::String => left.to_string().cmp(&right.to_string()),
+        SortType::Natural => natural_cmp(&left.to_string(), &right.to_string()),
+        SortType::Int => compare_parsed(left, right, to_int),
+        SortType::Quantity => {
+            compare_parsed(left, right, |value| parse_quantity(&value.to_string()))
+        }
+        SortType::Duration => compare_parsed(left, right, to_duration),
+        SortType::Timestamp => compare_parsed(left, right, to_timestamp),
+    }
+}
+
+fn compare_parsed<T: PartialOrd>(
+    left: &EvalResult,
+    right: &EvalResult,
+    parse: impl Fn(&EvalResult) -> Option<T>,
+) -> Ordering {
+    match (parse(left), parse(right)) {
+        (Some(left), Some(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => natural_cmp(&left.to_string(), &right.to_string()),
+    }
+}
+
+fn to_int(value: &EvalResult) -> Option<i64> {
+    match value {
+        EvalResult::Int(value) => Some(*value),
+        value => {
+            let value = value.to_string();
+            let digits = value
+                .trim()
+                .chars()
+                .enumerate()
+                .take_while(|(index, ch)| ch.is_ascii_digit() || (*index == 0 && *ch == '-'))
+                .map(|(_, ch)| ch)
+                .collect::<String>();
+            digits.parse().ok()
+        }
+    }
+}
+
+fn to_duration(value: &EvalResult) -> Option<chrono::Duration> {
+    match value {
+        EvalResult::AgoSince(ts) => Some(Utc::now().signed_duration_since(*ts)),
+        EvalResult::Ago(ts) => duration_since(ts).ok(),
+        value => parse_duration(&value.to_string()),
+    }
+}
+
+fn to_timestamp(value: &EvalResult) -> Option<chrono::DateTime<Utc>> {
+    match value {
+        EvalResult::AgoSince(ts) => Some(*ts),
+        value => chrono::DateTime::parse_from_rfc3339(value.to_string().trim())
+            .ok()
+            .map(|ts| ts.with_timezone(&Utc)),
+    }
+}
+
+/// Compares strings treating runs of digits as numbers, i.e. `pod-2` < `pod-10`.
+pub(crate) fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left = left.chars().peekable();
+    let mut right = right.chars().peekable();
+
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let take_number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut number = String::new();
+                    while let Some(ch) = chars.peek().filter(|ch| ch.is_ascii_digit()) {
+                        number.push(*ch);
+                        chars.next();
+                    }
+                    number
+                };
+                let l = take_number(&mut left);
+                let r = take_number(&mut right);
+                let (l_trimmed, r_trimmed) = (l.trim_start_matches('0'), r.trim_start_matches('0'));
+                let ordering = l_trimmed
+                    .len()
+                    .cmp(&r_trimmed.len())
+                    .then_with(|| l_trimmed.cmp(r_trimmed))
+                    .then_with(|| l.len().cmp(&r.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(l), Some(r)) => {
+                let ordering = l.cmp(&r);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left.next();
+                right.next();
+            }
+        }
+    }
+}
+
+/// Parses a Kubernetes quantity like `500Mi`, `1.5G`, `100m` or `1e3`.
+pub(crate) fn parse_quantity(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split_at = value
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.' || ch == '-' || ch == '+'))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split_at);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024f64,
+        "Mi" => 1024f64.powi(2),
+        "Gi" => 1024f64.powi(3),
+        "Ti" => 1024f64.powi(4),
+        "Pi" => 1024f64.powi(5),
+        "Ei" => 1024f64.powi(6),
+        suffix => {
+            let exponent: i32 = suffix
+                .strip_prefix('e')
+                .or_else(|| suffix.strip_prefix('E'))?
+                .parse()
+                .ok()?;
+            10f64.powi(exponent)
+        }
+    };
+
+    Some(number * multiplier)
+}
+
+/// Parses durations like `5m`, `2h30m`, `3d` or `1w` as rendered by age columns.
+pub(crate) fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let mut total = chrono::Duration::zero();
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split_at = rest.find(|ch: char| !ch.is_ascii_digit())?;
+        let (number, tail) = rest.split_at(split_at);
+        let number: i64 = number.parse().ok()?;
+        let unit_len = tail
+            .find(|ch: char| ch.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+
+        total = total
+            + match unit {
+                "ns" => chrono::Duration::nanoseconds(number),
+                "us" | "μs" => chrono::Duration::microseconds(number),
+                "ms" => chrono::Duration::milliseconds(number),
+                "s" => chrono::Duration::seconds(number),
+                "m" => chrono::Duration::minutes(number),
+                "h" => chrono::Duration::hours(number),
+                "d" => chrono::Duration::days(number),
+                "w" => chrono::Duration::weeks(number),
+                _ => return None,
+            };
+        rest = tail;
+    }
+
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("pod-2", "pod-10"), Ordering::Less);
+        assert_eq!(natural_cmp("pod-10", "pod-10"), Ordering::Equal);
+        assert_eq!(natural_cmp("pod-b", "pod-a"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("500Mi"), Some(500.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_quantity("100m"), Some(0.1));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("-"), None);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("5m"), Some(chrono::Duration::minutes(5)));
+        assert_eq!(
+            parse_duration("2h30m"),
+            Some(chrono::Duration::minutes(150))
+        );
+        assert_eq!(parse_duration("abc"), None);
+    }
+
+    #[test]
+    fn test_compare_mixed() {
+        let int = EvalResult::Int(3);
+        let string = EvalResult::String("10".to_string());
+        assert_eq!(
+            compare_eval_results(&int, &string, SortType::Int),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_eval_results(&int, &string, SortType::Auto),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use itertools::Itertools;
+use k8s_openapi::serde_json;
+use serde::Deserialize;
+
+use crate::config::extractor::{is_fixture_file, ExtractorConfig, TestCaseProps};
+use crate::eval::engine_factory::{build_engine, ScriptLimits};
+use crate::eval::evaluator::Evaluator;
+use crate::model::metrics::SharedMetricsStore;
+use crate::model::related::parse_gvk;
+use crate::model::resource::resource_view::ResourceView;
+use crate::traits::ext::gvk::GvkExt;
+use crate::util::fs::scan_files;
+use crate::util::paths::resolve_path;
+use crate::util::watcher::LazyWatcher;
+
+/// Both extractor configs and `*.test.yaml` files are read this way, other sections are
+/// ignored.
+#[derive(Deserialize, Debug, Default)]
+struct TestSuiteProps {
+    #[serde(default)]
+    tests: Vec<TestCaseProps>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Passed,
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub(crate) struct CheckResult {
+    /// i.e. `column ready` or `pseudo resources container`.
+    pub(crate) subject: String,
+    pub(crate) outcome: Outcome,
+}
+
+#[derive(Debug)]
+pub(crate) struct TestResult {
+    pub(crate) path: PathBuf,
+    pub(crate) name: String,
+    /// Set if the fixture could not be loaded.
+    pub(crate) error: Option<String>,
+    pub(crate) checks: Vec<CheckResult>,
+}
+
+impl TestResult {
+    pub(crate) fn passed(&self) -> bool {
+        self.error.is_none()
+            && self
+                .checks
+                .iter()
+                .all(|check| check.outcome == Outcome::Passed)
+    }
+}
+
+/// Runs `tests` declared in extractor configs and `*.test.yaml` files against the
+/// configs loaded the same way the UI loads them.
+pub(crate) struct TestRunner {
+    config: ExtractorConfig,
+    evaluator: Evaluator,
+}
+
+impl TestRunner {
+    pub(crate) fn new(
+        extractor_dirs: &[PathBuf],
+        module_dirs: &[PathBuf],
+        num_evaluator_threads: usize,
+        limits: &ScriptLimits,
+    ) -> anyhow::Result<Self> {
+        let limits = *limits;
+        let engine_watcher = LazyWatcher::new(module_dirs.to_vec(), move |paths| {
+            build_engine(paths, &SharedMetricsStore::default(), &limits)
+        })?;
+        let evaluator = Evaluator::new(num_evaluator_threads, &Arc::new(engine_watcher))?;
+
+        Ok(Self {
+            config: ExtractorConfig::new(extractor_dirs),
+            evaluator,
+        })
+    }
+
+    pub(crate) fn config(&self) -> &ExtractorConfig {
+        &self.config
+    }
+
+    pub(crate) fn run(&self, extractor_dirs: &[PathBuf]) -> Vec<TestResult> {
+        let mut files = scan_files(extractor_dirs)
+            .into_iter()
+            .filter(|file| {
+                file.extension()
+                    .map(|ext| ext == "yaml" || ext == "yml")
+                    .unwrap_or(false)
+                    && !is_fixture_file(file)
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+
+        let mut results = vec![];
+        for file in files {
+            let suite: TestSuiteProps = match std::fs::read_to_string(&file)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_yaml::from_str(&content)?))
+            {
+                Ok(suite) => suite,
+                Err(err) => {
+                    results.push(TestResult {
+                        path: file.clone(),
+                        name: "<parse>".to_string(),
+                        error: Some(err.to_string()),
+                        checks: vec![],
+                    });
+                    continue;
+                }
+            };
+
+            for test in suite.tests {
+                results.push(self.run_test(&file, test));
+            }
+        }
+
+        results
+    }
+
+    fn run_test(&self, path: &Path, test: TestCaseProps) -> TestResult {
+        let mut result = TestResult {
+            path: path.to_path_buf(),
+            name: test.name.clone(),
+            error: None,
+            checks: vec![],
+        };
+
+        let resource = match load_fixture(&resolve_path(path, &test.fixture)) {
+            Ok(resource) => resource,
+            Err(err) => {
+                result.error = Some(format!("{err:#}"));
+                return result;
+            }
+        };
+        let gvk = resource.gvk();
+
+        if !test.columns.is_empty() {
+            result
+                .checks
+                .extend(self.check_columns(&resource, &test.columns));
+        }
+        if !test.pseudo_resources.is_empty() {
+            let extractors = ExtractorConfig::find(&self.config.pseudo_resources_map, &gvk);
+            let pseudo_resources = extractors
+                .map(|extractors| {
+                    self.evaluator
+                        .evaluate_pseudo_resources(&resource, extractors)
+                })
+                .unwrap_or_default();
+            let ids_by_extractor = pseudo_resources
+                .into_iter()
+                .map(|pseudo_resource| (pseudo_resource.extractor_name, pseudo_resource.id))
+                .into_group_map();
+
+            for (extractor, expected) in test.pseudo_resources {
+                let actual = ids_by_extractor
+                    .get(&extractor)
+                    .cloned()
+                    .unwrap_or_default();
+                let outcome = if actual == expected {
+                    Outcome::Passed
+                } else {
+                    Outcome::Failed(format!("expected {expected:?}, got {actual:?}"))
+                };
+                result.checks.push(CheckResult {
+                    subject: format!("pseudo resources {extractor}"),
+                    outcome,
+                });
+            }
+        }
+
+        result
+    }
+
+    fn check_columns(
+        &self,
+        resource: &ResourceView,
+        expected: &BTreeMap<String, String>,
+    ) -> Vec<CheckResult> {
+        let gvk = resource.gvk();
+        let evaluated = ExtractorConfig::find(&self.config.columns_map, &gvk)
+            .map(|columns| self.evaluator.evaluate_columns(resource.clone(), columns));
+
+        expected
+            .iter()
+            .map(|(name, expected)| {
+                let outcome = match &evaluated {
+                    None => Outcome::Failed("no columns are configured for the GVK".to_string()),
+                    Some(Err(err)) => Outcome::Failed(err.to_string()),
+                    Some(Ok(evaluated)) => {
+                        let actual = evaluated
+                            .columns
+                            .iter()
+                            .position(|column| &column.name == name)
+                            .map(|index| evaluated.values[index].to_string());
+                        match actual {
+                            Some(actual) if &actual == expected => Outcome::Passed,
+                            Some(actual) => {
+                                Outcome::Failed(format!("expected {expected:?}, got {actual:?}"))
+                            }
+                            None => Outcome::Failed("the column does not exist".to_string()),
+                        }
+                    }
+                };
+                CheckResult {
+                    subject: format!("column {name}"),
+                    outcome,
+                }
+            })
+            .collect()
+    }
+}
+
+fn load_fixture(path: &Path) -> anyhow::Result<ResourceView> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read fixture {}", path.display()))?;
+    let value: serde_json::Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Cannot parse fixture {}", path.display()))?;
+
+    let api_version = value["apiVersion"].as_str().unwrap_or_default();
+    let kind = value["kind"].as_str().unwrap_or_default();
+    let gvk = parse_gvk(&format!("{api_version}/{kind}"))
+        .with_context(|| format!("Fixture {} has no apiVersion or kind", path.display()))?;
+
+    Ok(ResourceView::from_json(&gvk, value)?)
+}
+
+/// Prints the results and returns the process exit code.
+pub(crate) fn run_tests(
+    extractor_dirs: &[PathBuf],
+    module_dirs: &[PathBuf],
+    num_evaluator_threads: usize,
+    limits: &ScriptLimits,
+) -> i32 {
+    let runner = match TestRunner::new(extractor_dirs, module_dirs, num_evaluator_threads, limits) {
+        Ok(runner) => runner,
+        Err(err) => {
+            eprintln!("Failed to set up the evaluator: {err}");
+            return 1;
+        }
+    };
+    for error in &runner.config().errors {
+        eprintln!("warning: {error}");
+    }
+
+    let results = runner.run(extractor_dirs);
+    for result in &results {
+        let status = if result.passed() { "ok" } else { "FAIL" };
+        println!("{status: <4} {}: {}", result.path.display(), result.name);
+        if let Some(error) = &result.error {
+            println!("       {error}");
+        }
+        for check in &result.checks {
+            match &check.outcome {
+                Outcome::Passed => println!("  ok   {}", check.subject),
+                Outcome::Failed(message) => println!("  FAIL {}: {message}", check.subject),
+            }
+        }
+    }
+
+    let num_failed = results.iter().filter(|result| !result.passed()).count();
+    println!(
+        "Ran {} tests: {} passed, {num_failed} failed",
+        results.len(),
+        results.len() - num_failed
+    );
+
+    if num_failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pod.yaml"),
+            r#"resource:
+  group: ''
+  version: v1
+  kind: Pod
+pseudo_resources:
+  - name: container
+    script_content: |
+      let resources = [];
+      for container in resource.spec.containers {
+          resources.push(PseudoResource(container.name, container));
+      }
+      resources
+columns:
+  - name: name
+    display_name: Name
+    width: 0
+    evaluator: !Embedded
+      name: Name
+  - name: image
+    display_name: Image
+    width: 0
+    evaluator: !ScriptContent
+      content: resource.spec.containers[0].image
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("pod.test.yaml"),
+            r#"tests:
+  - name: single container
+    fixture: fixtures/pod.yaml
+    columns:
+      name: example
+      image: nginx
+    pseudo_resources:
+      container: [main]
+  - name: wrong image
+    fixture: fixtures/pod.yaml
+    columns:
+      image: alpine
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("fixtures")).unwrap();
+        std::fs::write(
+            dir.path().join("fixtures").join("pod.yaml"),
+            r#"apiVersion: v1
+kind: Pod
+metadata:
+  name: example
+spec:
+  containers:
+    - name: main
+      image: nginx
+"#,
+        )
+        .unwrap();
+
+        let dirs = [dir.path().to_path_buf()];
+        let runner = TestRunner::new(&dirs, &[], 2, &ScriptLimits::default()).unwrap();
+        assert!(runner.config().errors.is_empty());
+
+        let results = runner.run(&dirs);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].passed(), "{:?}", results[0]);
+        assert_eq!(results[0].checks.len(), 3);
+        assert!(!results[1].passed());
+    }
+}
@@ -2,20 +2,27 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use clap::Parser;
+use cursive::reexports::log::error;
 use cursive::CursiveRunnable;
 use k8s_openapi::api::core::v1::Pod;
 use kanal::Sender;
 use kube::api::GroupVersionKind;
 
 use crate::backend::k8s_backend::K8sBackend;
-use crate::config::args::Args;
+use crate::config::args::{Action, Args};
+use crate::config::check::run_check;
+use crate::config::detail_templates::DetailTemplates;
 use crate::config::extractor::ExtractorConfig;
 use crate::config::kgv_configuration::KgvConfiguration;
+use crate::config::list_sort_state::ListSortState;
+use crate::config::render_profiles::RenderProfiles;
 use crate::eval::engine_factory::build_engine;
 use crate::eval::evaluator::Evaluator;
+use crate::eval::test_runner::run_tests;
+use crate::model::metrics::SharedMetricsStore;
 use crate::reexports::sync::Mutex;
 use crate::reexports::sync::RwLock;
-use crate::theme::get_theme;
+use crate::theme::load_theme_or_default;
 use crate::traits::ext::cursive::SivLogExt;
 use crate::traits::ext::gvk::GvkStaticExt;
 use crate::traits::ext::kanal_sender::KanalSenderExt;
@@ -23,12 +30,14 @@ use crate::ui::command_handler_loop::enter_command_handler_loop;
 use crate::ui::components::gvk_switcher::build_gvk_show_chain;
 use crate::ui::detail_view_renderer::DetailViewRenderer;
 use crate::ui::dispatcher::Dispatcher;
+use crate::ui::highlighter::load_syntax_set;
 use crate::ui::hotkeys::register_hotkeys;
 use crate::ui::resource_manager::ResourceManager;
 use crate::ui::signals::{InterUiSignal, ToBackendSignal};
 use crate::ui::ui_store::{UiStore, UiStoreDispatcherExt};
 use crate::ui::view_stack::ViewStack;
 use crate::util::watcher::LazyWatcher;
+use crate::util::yaml::YamlFile;
 
 pub(crate) mod backend;
 pub(crate) mod config;
@@ -44,6 +53,21 @@ fn main() -> Result<()> {
     better_panic::install();
     let kgv_configuration = KgvConfiguration::try_from(Args::parse())?;
 
+    match kgv_configuration.action {
+        Some(Action::Check {}) => std::process::exit(run_check(
+            &kgv_configuration.extractor_dirs,
+            &kgv_configuration.module_dirs,
+            &kgv_configuration.detail_template_dirs,
+        )),
+        Some(Action::Test {}) => std::process::exit(run_tests(
+            &kgv_configuration.extractor_dirs,
+            &kgv_configuration.module_dirs,
+            kgv_configuration.num_evaluator_threads,
+            &kgv_configuration.script_limits,
+        )),
+        _ => {}
+    }
+
     // let raw_handle = std::io::stdout().into_raw_mode()?;
     // let orig_hook = panic::take_hook();
     // panic::set_hook(Box::new(move |panic_info| {
@@ -55,8 +79,13 @@ fn main() -> Result<()> {
 
     let mut ui = CursiveRunnable::default();
     ui.setup_logger(kgv_configuration.logs_dir)?;
-    ui.set_theme(get_theme());
-    ui.set_fps(1);
+    let (theme, highlighter) = load_theme_or_default(
+        &kgv_configuration.theme_dirs,
+        &kgv_configuration.theme,
+        &load_syntax_set(),
+    );
+    ui.set_theme(theme);
+    ui.set_fps(kgv_configuration.fps);
 
     let (from_backend_sender, from_backend_receiver) = kanal::unbounded();
     let (to_backend_sender, from_ui_receiver) = kanal::unbounded();
@@ -73,6 +102,9 @@ fn main() -> Result<()> {
     backend.spawn_watcher_exchange_task();
     backend.spawn_discovery_task();
     backend.spawn_from_ui_receiver_task();
+    if let Some(poll_interval) = kgv_configuration.metrics_poll_interval {
+        backend.spawn_metrics_task(poll_interval);
+    }
 
     register_hotkeys(&mut ui, inter_ui_sender.clone());
 
@@ -83,19 +115,58 @@ fn main() -> Result<()> {
     })?;
     let extractor_config_watcher = Arc::new(extractor_config_watcher);
 
-    let engine_watcher = LazyWatcher::new(kgv_configuration.module_dirs, build_engine)?;
+    let metrics = SharedMetricsStore::default();
+    let engine_watcher = LazyWatcher::new(kgv_configuration.module_dirs, {
+        let metrics = Arc::clone(&metrics);
+        let limits = kgv_configuration.script_limits;
+        move |paths| build_engine(paths, &metrics, &limits)
+    })?;
     let engine_watcher = Arc::new(engine_watcher);
 
-    let detail_view_renderer = DetailViewRenderer::new(&engine_watcher, &extractor_config_watcher);
+    let detail_templates_watcher =
+        LazyWatcher::new(kgv_configuration.detail_template_dirs, |paths| {
+            DetailTemplates::new(paths)
+        })?;
+    let detail_templates_watcher = Arc::new(detail_templates_watcher);
+
+    let evaluator = Evaluator::new(kgv_configuration.num_evaluator_threads, &engine_watcher)?;
+    let eval_profiler = evaluator.profiler();
+    let detail_view_renderer = DetailViewRenderer::new(
+        &engine_watcher,
+        &extractor_config_watcher,
+        &detail_templates_watcher,
+        &evaluator.rhai_objects(),
+    );
     let resource_manager = ResourceManager::new(
-        Evaluator::new(kgv_configuration.num_evaluator_threads, &engine_watcher)?,
+        evaluator,
         &extractor_config_watcher,
+        kgv_configuration.tombstone_retention,
+        kgv_configuration.revision_history_size,
+        &metrics,
     );
 
+    let render_profiles = RenderProfiles::load(&kgv_configuration.render_profiles_path)
+        .unwrap_or_else(|err| {
+            error!(
+                "Failed to load render profiles from {}: {err}",
+                kgv_configuration.render_profiles_path.display()
+            );
+            RenderProfiles::default()
+        });
+
+    let sort_state =
+        ListSortState::load(&kgv_configuration.sort_state_path).unwrap_or_else(|err| {
+            error!(
+                "Failed to load sort state from {}: {err}",
+                kgv_configuration.sort_state_path.display()
+            );
+            ListSortState::default()
+        });
+
     let store = Arc::new(Mutex::new(UiStore {
         counter: 0,
         view_stack: ViewStack::default(),
-        highlighter: Arc::new(ui::highlighter::Highlighter::new("base16-eighties.dark")?),
+        highlighter: Arc::new(highlighter),
         selected_gvk: GroupVersionKind::gvk("", "", ""),
         inter_ui_sender: inter_ui_sender.clone(),
         to_backend_sender,
@@ -105,6 +176,16 @@ fn main() -> Result<()> {
         resource_manager: Arc::new(RwLock::new(resource_manager)),
         detail_view_renderer,
         pf_requests: vec![],
+        pf_profiles_path: kgv_configuration.port_forward_profiles_path,
+        render_profiles: Arc::new(render_profiles),
+        sort_state,
+        sort_state_path: kgv_configuration.sort_state_path,
+        config_errors: Arc::new(vec![]),
+        log_defaults: kgv_configuration.log_defaults,
+        theme_dirs: kgv_configuration.theme_dirs,
+        theme_name: kgv_configuration.theme,
+        eval_profiler,
+        events_registered: false,
     }));
 
     {
@@ -118,6 +199,17 @@ fn main() -> Result<()> {
     }
 
     store.spawn_log_updater_thread();
+    store.spawn_tombstone_reaper_thread();
+    store.spawn_port_forward_stats_thread();
+    store.spawn_config_status_thread(
+        extractor_config_watcher,
+        engine_watcher,
+        detail_templates_watcher,
+    );
+
+    for profile in kgv_configuration.forward_profiles {
+        inter_ui_sender.send_unwrap(InterUiSignal::StartPortForwardProfile(profile));
+    }
 
     enter_command_handler_loop(&mut ui, store)?;
 
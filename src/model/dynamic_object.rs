@@ -38,4 +38,7 @@ impl SerializeExt for DynamicObjectWrapper {
     fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(&self.0)
     }
+    fn to_rhai(&self) -> Result<rhai::Dynamic, Box<rhai::EvalAltResult>> {
+        rhai::serde::to_dynamic(&self.0)
+    }
 }
@@ -0,0 +1,63 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub(crate) enum LogExportTarget {
+    File(PathBuf),
+    Clipboard,
+    Pipe(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LogExportRequest {
+    pub(crate) view_id: usize,
+    pub(crate) target: LogExportTarget,
+    /// Exports the lines as the log view renders them instead of the raw buffer.
+    pub(crate) as_shown: bool,
+    pub(crate) range: Option<Range<usize>>,
+}
+
+/// Parses a line range in the form of `from:to`, `from:` or `:to`. An empty string means the
+/// whole buffer.
+pub(crate) fn parse_line_range(text: &str) -> anyhow::Result<Option<Range<usize>>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let (from, to) = text
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Range {text} must be in the form of from:to"))?;
+
+    let from = if from.trim().is_empty() {
+        0
+    } else {
+        from.trim().parse::<usize>()?
+    };
+    let to = if to.trim().is_empty() {
+        usize::MAX
+    } else {
+        to.trim().parse::<usize>()?
+    };
+
+    if from > to {
+        anyhow::bail!("Range start {from} is greater than the end {to}");
+    }
+
+    Ok(Some(from..to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_range() {
+        assert_eq!(parse_line_range("").unwrap(), None);
+        assert_eq!(parse_line_range("1:5").unwrap(), Some(1..5));
+        assert_eq!(parse_line_range(":5").unwrap(), Some(0..5));
+        assert_eq!(parse_line_range("3:").unwrap(), Some(3..usize::MAX));
+        assert!(parse_line_range("5:1").is_err());
+        assert!(parse_line_range("5").is_err());
+    }
+}
@@ -45,6 +45,34 @@ macro_rules! mk_resource_enum {
             }
         }
 
+        // owner_references()
+        impl $name {
+            pub(crate) fn owner_references(
+                &self,
+            ) -> &[k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference] {
+                match self {
+                    $(
+                        Self::$opt_name(r) => r.owner_references(),
+                    )+
+                    Self::DynamicObject(r) => r.owner_references(),
+                    Self::PseudoResource(_) => &[],
+                }
+            }
+        }
+
+        // labels()
+        impl $name {
+            pub(crate) fn labels(&self) -> std::collections::BTreeMap<String, String> {
+                match self {
+                    $(
+                        Self::$opt_name(r) => r.labels().clone(),
+                    )+
+                    Self::DynamicObject(r) => r.labels().clone(),
+                    Self::PseudoResource(_) => Default::default(),
+                }
+            }
+        }
+
         // uid()
         impl $name {
             pub(crate) fn uid(&self) -> Option<String> {
@@ -122,6 +150,40 @@ macro_rules! mk_resource_enum {
                     Self::PseudoResource(r) => r.to_json(),
                 }
             }
+
+            fn to_rhai(&self) -> Result<rhai::Dynamic, Box<rhai::EvalAltResult>> {
+                match self {
+                    $(
+                        Self::$opt_name(r) => rhai::serde::to_dynamic(r.as_ref()),
+                    )+
+                    Self::DynamicObject(r) => r.to_rhai(),
+                    Self::PseudoResource(r) => r.to_rhai(),
+                }
+            }
+        }
+
+        // from_json()
+        impl $name {
+            /// Deserializes a resource into its typed variant if the GVK is a built-in one.
+            pub(crate) fn from_json(
+                gvk: &kube::api::GroupVersionKind,
+                value: k8s_openapi::serde_json::Value,
+            ) -> Result<Self, k8s_openapi::serde_json::Error> {
+                use k8s_openapi::Resource;
+                $(
+                    if gvk.group == $crate::reexports::k8s::$opt_name::GROUP
+                        && gvk.version == $crate::reexports::k8s::$opt_name::VERSION
+                        && gvk.kind == $crate::reexports::k8s::$opt_name::KIND
+                    {
+                        let resource = k8s_openapi::serde_json::from_value(value)?;
+                        return Ok(Self::$opt_name(std::sync::Arc::new(resource)));
+                    }
+                )+
+
+                let object: kube::api::DynamicObject = k8s_openapi::serde_json::from_value(value)?;
+                let wrapper = $crate::model::dynamic_object::DynamicObjectWrapper(object, gvk.clone());
+                Ok(Self::DynamicObject(std::sync::Arc::new(wrapper)))
+            }
         }
 
         $(
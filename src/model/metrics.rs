@@ -0,0 +1,264 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use k8s_openapi::api::core::v1::{Node, Pod};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::serde_json;
+use kube::ResourceExt;
+
+use crate::eval::sort::parse_quantity;
+use crate::reexports::sync::RwLock;
+use crate::traits::ext::container::ContainerExt;
+
+pub(crate) type SharedMetricsStore = Arc<RwLock<MetricsStore>>;
+
+/// CPU in cores and memory in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ResourceUsage {
+    pub(crate) cpu: f64,
+    pub(crate) memory: f64,
+}
+
+impl ResourceUsage {
+    /// Parses the `usage` object of `PodMetrics` containers and `NodeMetrics`.
+    pub(crate) fn from_json(usage: &serde_json::Value) -> Self {
+        let quantity = |name: &str| {
+            usage
+                .get(name)
+                .and_then(|value| value.as_str())
+                .and_then(parse_quantity)
+                .unwrap_or_default()
+        };
+        Self {
+            cpu: quantity("cpu"),
+            memory: quantity("memory"),
+        }
+    }
+}
+
+/// Usage reported by `metrics.k8s.io` in a single poll.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetricsSnapshot {
+    /// Container usage by the `namespace/name` pod key, then by the container name.
+    pub(crate) pods: HashMap<String, HashMap<String, ResourceUsage>>,
+    /// Usage by the node name.
+    pub(crate) nodes: HashMap<String, ResourceUsage>,
+}
+
+/// Requests and limits of a single container.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ContainerAllocation {
+    pub(crate) name: String,
+    pub(crate) cpu_request: Option<f64>,
+    pub(crate) cpu_limit: Option<f64>,
+    pub(crate) memory_request: Option<f64>,
+    pub(crate) memory_limit: Option<f64>,
+    pub(crate) cpu_rl: String,
+    pub(crate) memory_rl: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AllocationKind {
+    Requests,
+    Limits,
+}
+
+impl AllocationKind {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "requests" | "request" | "r" => Some(Self::Requests),
+            "limits" | "limit" | "l" => Some(Self::Limits),
+            _ => None,
+        }
+    }
+}
+
+/// The latest usage joined with requests and limits of the pods and allocatable resources
+/// of the nodes known to the resource manager. Shared with the rhai `metrics` module.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsStore {
+    pod_usage: HashMap<String, HashMap<String, ResourceUsage>>,
+    node_usage: HashMap<String, ResourceUsage>,
+    pod_allocations: HashMap<String, Vec<ContainerAllocation>>,
+    node_allocatable: HashMap<String, ResourceUsage>,
+}
+
+pub(crate) fn pod_key(namespace: &str, name: &str) -> String {
+    format!("{namespace}/{name}")
+}
+
+fn quantity_value(quantity: Option<&Quantity>) -> Option<f64> {
+    quantity.and_then(|quantity| parse_quantity(&quantity.0))
+}
+
+impl MetricsStore {
+    /// Replaces the usage and returns the keys of pods and names of nodes whose usage
+    /// has changed, appeared or disappeared.
+    pub(crate) fn replace_usage(
+        &mut self,
+        snapshot: MetricsSnapshot,
+    ) -> (HashSet<String>, HashSet<String>) {
+        let changed_pods = changed_keys(&self.pod_usage, &snapshot.pods);
+        let changed_nodes = changed_keys(&self.node_usage, &snapshot.nodes);
+        self.pod_usage = snapshot.pods;
+        self.node_usage = snapshot.nodes;
+        (changed_pods, changed_nodes)
+    }
+
+    pub(crate) fn set_pod(&mut self, pod: &Pod) {
+        let key = pod_key(&pod.namespace().unwrap_or_default(), &pod.name_any());
+        let containers = pod
+            .spec
+            .iter()
+            .flat_map(|spec| spec.containers.iter())
+            .map(|container| ContainerAllocation {
+                name: container.name.clone(),
+                cpu_request: quantity_value(container.cpu_request()),
+                cpu_limit: quantity_value(container.cpu_limit()),
+                memory_request: quantity_value(container.memory_request()),
+                memory_limit: quantity_value(container.memory_limit()),
+                cpu_rl: container.cpu_rl(),
+                memory_rl: container.memory_rl(),
+            })
+            .collect();
+        self.pod_allocations.insert(key, containers);
+    }
+
+    pub(crate) fn set_node(&mut self, node: &Node) {
+        let allocatable = node
+            .status
+            .as_ref()
+            .and_then(|status| status.allocatable.as_ref());
+        let allocatable = ResourceUsage {
+            cpu: quantity_value(allocatable.and_then(|a| a.get("cpu"))).unwrap_or_default(),
+            memory: quantity_value(allocatable.and_then(|a| a.get("memory"))).unwrap_or_default(),
+        };
+        self.node_allocatable.insert(node.name_any(), allocatable);
+    }
+
+    pub(crate) fn remove_pod(&mut self, key: &str) {
+        self.pod_allocations.remove(key);
+    }
+
+    pub(crate) fn remove_node(&mut self, name: &str) {
+        self.node_allocatable.remove(name);
+    }
+
+    /// Total usage of the pod containers.
+    pub(crate) fn pod_usage(&self, key: &str) -> Option<ResourceUsage> {
+        let containers = self.pod_usage.get(key)?;
+        Some(
+            containers
+                .values()
+                .fold(ResourceUsage::default(), |total, usage| ResourceUsage {
+                    cpu: total.cpu + usage.cpu,
+                    memory: total.memory + usage.memory,
+                }),
+        )
+    }
+
+    pub(crate) fn container_usage(&self, key: &str, container: &str) -> Option<ResourceUsage> {
+        self.pod_usage.get(key)?.get(container).copied()
+    }
+
+    pub(crate) fn node_usage(&self, name: &str) -> Option<ResourceUsage> {
+        self.node_usage.get(name).copied()
+    }
+
+    pub(crate) fn pod_allocations(&self, key: &str) -> &[ContainerAllocation] {
+        self.pod_allocations
+            .get(key)
+            .map(|containers| containers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Requests or limits summed over the containers. Returns `None` if none of the
+    /// containers specifies them.
+    pub(crate) fn pod_allocation(&self, key: &str, kind: AllocationKind) -> Option<ResourceUsage> {
+        let containers = self.pod_allocations.get(key)?;
+        let sum = |value: fn(&ContainerAllocation) -> Option<f64>| {
+            let values = containers.iter().filter_map(value).collect::<Vec<_>>();
+            (!values.is_empty()).then(|| values.iter().sum::<f64>())
+        };
+        let (cpu, memory) = match kind {
+            AllocationKind::Requests => (sum(|c| c.cpu_request), sum(|c| c.memory_request)),
+            AllocationKind::Limits => (sum(|c| c.cpu_limit), sum(|c| c.memory_limit)),
+        };
+        if cpu.is_none() && memory.is_none() {
+            return None;
+        }
+        Some(ResourceUsage {
+            cpu: cpu.unwrap_or_default(),
+            memory: memory.unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn node_allocatable(&self, name: &str) -> Option<ResourceUsage> {
+        self.node_allocatable.get(name).copied()
+    }
+}
+
+fn changed_keys<V: PartialEq>(
+    old: &HashMap<String, V>,
+    new: &HashMap<String, V>,
+) -> HashSet<String> {
+    let changed = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone());
+    let removed = old.keys().filter(|key| !new.contains_key(*key)).cloned();
+    changed.chain(removed).collect()
+}
+
+/// Renders CPU cores as millicores, i.e. `250m`.
+pub(crate) fn format_cpu(cores: f64) -> String {
+    format!("{}m", (cores * 1000.0).round() as i64)
+}
+
+/// Renders bytes in binary units, i.e. `128Mi`.
+pub(crate) fn format_memory(bytes: f64) -> String {
+    let units = ["", "Ki", "Mi", "Gi", "Ti"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{}{}", value.round() as i64, units[unit])
+}
+
+/// Usage as a percentage of the given total, `None` if the total is unknown.
+pub(crate) fn percent(usage: f64, total: f64) -> Option<i64> {
+    (total > 0.0).then(|| (usage / total * 100.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_usage() {
+        let mut store = MetricsStore::default();
+        let usage = |cpu: f64| ResourceUsage { cpu, memory: 0.0 };
+
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.nodes.insert("a".to_string(), usage(1.0));
+        snapshot.nodes.insert("b".to_string(), usage(2.0));
+        let (_, changed) = store.replace_usage(snapshot);
+        assert_eq!(changed.len(), 2);
+
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.nodes.insert("a".to_string(), usage(1.0));
+        let (_, changed) = store.replace_usage(snapshot);
+        assert_eq!(changed, HashSet::from(["b".to_string()]));
+        assert_eq!(store.node_usage("a"), Some(usage(1.0)));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_cpu(0.25), "250m");
+        assert_eq!(format_memory(128.0 * 1024.0 * 1024.0), "128Mi");
+        assert_eq!(percent(0.5, 2.0), Some(25));
+        assert_eq!(percent(0.5, 0.0), None);
+    }
+}
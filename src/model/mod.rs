@@ -1,8 +1,12 @@
 pub(crate) mod dynamic_object;
+pub(crate) mod log_export_request;
 pub(crate) mod log_request;
 pub(crate) mod r#macro;
+pub(crate) mod metrics;
 pub(crate) mod pod;
 pub(crate) mod port_forward_request;
 pub(crate) mod pseudo_resource;
+pub(crate) mod related;
 pub(crate) mod resource;
+pub(crate) mod resource_event;
 pub(crate) mod traits;
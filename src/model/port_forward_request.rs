@@ -1,9 +1,135 @@
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::reexports::sync::Mutex;
+use crate::traits::ext::mutex::MutexExt;
+use crate::util::ui::format_bytes;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum PortForwardTarget {
+    Pod(String),
+    /// Forwarded to a ready pod matching the service selector; the remote port is the service
+    /// port, resolved to the `targetPort` of the pod.
+    Service(String),
+    /// Forwarded to a ready pod matching the deployment selector.
+    Deployment(String),
+}
+
+impl PortForwardTarget {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            PortForwardTarget::Pod(name)
+            | PortForwardTarget::Service(name)
+            | PortForwardTarget::Deployment(name) => name,
+        }
+    }
+
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            PortForwardTarget::Pod(_) => "pod",
+            PortForwardTarget::Service(_) => "svc",
+            PortForwardTarget::Deployment(_) => "deploy",
+        }
+    }
+}
+
+impl Display for PortForwardTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.kind(), self.name())
+    }
+}
+
+/// A persisted part of a port forwarding request; port forward profiles are lists of specs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PortForwardSpec {
+    pub(crate) namespace: String,
+    pub(crate) target: PortForwardTarget,
+    pub(crate) remote_port: u16,
+    pub(crate) host_port: u16,
+    #[serde(default = "default_host")]
+    pub(crate) host: String,
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PortForwardStats {
+    pub(crate) num_connections: AtomicUsize,
+    pub(crate) num_active_connections: AtomicUsize,
+    pub(crate) num_restarts: AtomicUsize,
+    pub(crate) bytes_sent: AtomicU64,
+    pub(crate) bytes_received: AtomicU64,
+    pub(crate) last_error: Mutex<Option<String>>,
+    /// The listener kept failing and forwarding was given up.
+    pub(crate) failed: AtomicBool,
+}
+
+impl PortForwardStats {
+    pub(crate) fn set_last_error(&self, err: impl Display) {
+        *self.last_error.lock_unwrap() = Some(err.to_string());
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        let mut summary = if self.failed.load(Ordering::Relaxed) {
+            "FAILED ".to_string()
+        } else {
+            String::new()
+        };
+        summary.push_str(&format!(
+            "conn {}/{} sent {} recv {}",
+            self.num_active_connections.load(Ordering::Relaxed),
+            self.num_connections.load(Ordering::Relaxed),
+            format_bytes(self.bytes_sent.load(Ordering::Relaxed)),
+            format_bytes(self.bytes_received.load(Ordering::Relaxed)),
+        ));
+
+        let num_restarts = self.num_restarts.load(Ordering::Relaxed);
+        if num_restarts > 0 {
+            summary.push_str(&format!(" restarts {num_restarts}"));
+        }
+        if let Some(last_error) = self.last_error.lock_unwrap().as_ref() {
+            summary.push_str(&format!(" error: {last_error}"));
+        }
+
+        summary
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct PortForwardRequest {
     pub(crate) id: usize,
     pub(crate) namespace: String,
-    pub(crate) pod_name: String,
-    pub(crate) pod_port: u16,
+    pub(crate) target: PortForwardTarget,
+    pub(crate) remote_port: u16,
     pub(crate) host_port: u16,
     pub(crate) host: String,
+    pub(crate) stats: PortForwardStats,
+}
+
+impl PortForwardRequest {
+    pub(crate) fn from_spec(id: usize, spec: PortForwardSpec) -> Self {
+        Self {
+            id,
+            namespace: spec.namespace,
+            target: spec.target,
+            remote_port: spec.remote_port,
+            host_port: spec.host_port,
+            host: spec.host,
+            stats: PortForwardStats::default(),
+        }
+    }
+
+    pub(crate) fn to_spec(&self) -> PortForwardSpec {
+        PortForwardSpec {
+            namespace: self.namespace.clone(),
+            target: self.target.clone(),
+            remote_port: self.remote_port,
+            host_port: self.host_port,
+            host: self.host.clone(),
+        }
+    }
 }
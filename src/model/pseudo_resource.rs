@@ -18,6 +18,10 @@ pub(crate) struct PseudoResource {
     pub(crate) extractor_name: String,
     pub(crate) resource: rhai::Dynamic,
     pub(crate) source: ResourceView,
+    /// A stable identity evaluated by the extractor `key` script; `id` is used if missing.
+    pub(crate) key: Option<String>,
+    /// Set when a keyed pseudo resource disappears from its source.
+    pub(crate) deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl Default for PseudoResource {
@@ -27,6 +31,8 @@ impl Default for PseudoResource {
             extractor_name: "".to_string(),
             resource: rhai::Dynamic::UNIT,
             source: ResourceView::DynamicObject(Arc::new(DynamicObjectWrapper::default())),
+            key: None,
+            deleted_at: None,
         }
     }
 }
@@ -34,7 +40,8 @@ impl Default for PseudoResource {
 impl PseudoResource {
     pub(crate) fn uid(&self) -> Option<String> {
         let uid = self.source.uid_or_name();
-        let parts = [&uid, &self.extractor_name, &self.id];
+        let key = self.key.as_ref().unwrap_or(&self.id);
+        let parts = [&uid, &self.extractor_name, key];
         Some(parts.iter().join(PSEUDO_RESOURCE_JOIN_SEQ))
     }
 
@@ -55,7 +62,9 @@ impl PseudoResource {
     }
 
     pub(crate) fn deletion_timestamp(&self) -> Option<&chrono::DateTime<chrono::Utc>> {
-        self.source.deletion_timestamp()
+        self.deleted_at
+            .as_ref()
+            .or_else(|| self.source.deletion_timestamp())
     }
 
     pub(crate) fn resource_version(&self) -> Option<String> {
@@ -74,4 +83,8 @@ impl SerializeExt for PseudoResource {
     fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(&self.resource)
     }
+
+    fn to_rhai(&self) -> Result<rhai::Dynamic, Box<rhai::EvalAltResult>> {
+        Ok(self.resource.clone())
+    }
 }
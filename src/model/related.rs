@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use kube::api::GroupVersionKind;
+
+use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
+
+/// What a related resource reference points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RelatedTarget {
+    /// A single resource with the given name.
+    Name(String),
+    /// Resources whose labels match the selector, i.e. pods of a service.
+    Selector(BTreeMap<String, String>),
+    /// Resources whose `spec.selector` matches the labels of the source resource, i.e.
+    /// services of a pod.
+    Selecting,
+}
+
+/// A reference returned by a `related` script: `[gvk, namespace, target]`.
+#[derive(Debug, Clone)]
+pub(crate) struct RelatedRef {
+    pub(crate) relation: String,
+    pub(crate) gvk: GroupVersionKind,
+    /// Empty for cluster-scoped resources or to match any namespace.
+    pub(crate) namespace: String,
+    pub(crate) target: RelatedTarget,
+}
+
+/// A reference resolved against the cached resources. `resource` is `None` if nothing is
+/// cached for the reference yet.
+#[derive(Debug, Clone)]
+pub(crate) struct RelatedResource {
+    pub(crate) relation: String,
+    pub(crate) gvk: GroupVersionKind,
+    pub(crate) resource: Option<EvaluatedResource>,
+}
+
+/// Parses GVKs written as `GvkNameExt::full_name`, i.e. `v1/Node` or `apps/v1/Deployment`.
+pub(crate) fn parse_gvk(full_name: &str) -> Option<GroupVersionKind> {
+    let (group_version, kind) = full_name.rsplit_once('/')?;
+    let (group, version) = group_version
+        .rsplit_once('/')
+        .unwrap_or(("", group_version));
+    if version.is_empty() || kind.is_empty() {
+        return None;
+    }
+    Some(GroupVersionKind::gvk(group, version, kind))
+}
+
+/// An empty selector matches nothing, as it does for services.
+pub(crate) fn selector_matches(
+    selector: &BTreeMap<String, String>,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    !selector.is_empty()
+        && selector
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+}
+
+/// Reads `spec.selector.matchLabels` (workloads) or `spec.selector` (services).
+pub(crate) fn resource_selector(resource: &ResourceView) -> Option<BTreeMap<String, String>> {
+    match resource {
+        ResourceView::Service(service) => service.spec.as_ref()?.selector.clone(),
+        ResourceView::ReplicationController(controller) => {
+            controller.spec.as_ref()?.selector.clone()
+        }
+        ResourceView::Deployment(deployment) => {
+            deployment.spec.as_ref()?.selector.match_labels.clone()
+        }
+        ResourceView::ReplicaSet(replica_set) => {
+            replica_set.spec.as_ref()?.selector.match_labels.clone()
+        }
+        ResourceView::StatefulSet(stateful_set) => {
+            stateful_set.spec.as_ref()?.selector.match_labels.clone()
+        }
+        ResourceView::DaemonSet(daemon_set) => {
+            daemon_set.spec.as_ref()?.selector.match_labels.clone()
+        }
+        ResourceView::Job(job) => job.spec.as_ref()?.selector.as_ref()?.match_labels.clone(),
+        ResourceView::DynamicObject(object) => {
+            let selector = object.data.pointer("/spec/selector")?;
+            let selector = selector.get("matchLabels").unwrap_or(selector);
+            let selector = selector
+                .as_object()?
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect();
+            Some(selector)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use k8s_openapi::serde_json::{from_value, json};
+
+    use crate::reexports::k8s::{Deployment, Service};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_gvk() {
+        assert_eq!(
+            parse_gvk("v1/Node"),
+            Some(GroupVersionKind::gvk("", "v1", "Node"))
+        );
+        assert_eq!(
+            parse_gvk("discovery.k8s.io/v1/EndpointSlice"),
+            Some(GroupVersionKind::gvk(
+                "discovery.k8s.io",
+                "v1",
+                "EndpointSlice"
+            ))
+        );
+        assert_eq!(parse_gvk("Node"), None);
+    }
+
+    #[test]
+    fn test_selector_matches() {
+        let labels = BTreeMap::from([
+            ("app".to_string(), "web".to_string()),
+            ("tier".to_string(), "front".to_string()),
+        ]);
+        let selector = BTreeMap::from([("app".to_string(), "web".to_string())]);
+        assert!(selector_matches(&selector, &labels));
+        assert!(!selector_matches(&labels, &selector));
+        assert!(!selector_matches(&BTreeMap::new(), &labels));
+    }
+
+    #[test]
+    fn test_resource_selector() {
+        let selector = BTreeMap::from([("app".to_string(), "web".to_string())]);
+
+        let service: Service = from_value(json!({
+            "metadata": { "name": "web" },
+            "spec": { "selector": { "app": "web" } },
+        }))
+        .unwrap();
+        assert_eq!(
+            resource_selector(&ResourceView::Service(Arc::new(service))),
+            Some(selector.clone())
+        );
+
+        let deployment: Deployment = from_value(json!({
+            "metadata": { "name": "web" },
+            "spec": {
+                "selector": { "matchLabels": { "app": "web" } },
+                "template": {},
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            resource_selector(&ResourceView::Deployment(Arc::new(deployment))),
+            Some(selector)
+        );
+    }
+}
@@ -6,6 +6,7 @@ use itertools::Itertools;
 use kube::api::GroupVersionKind;
 use kube::{Resource, ResourceExt};
 
+use crate::config::extractor::Column;
 use crate::eval::eval_result::EvalResult;
 use crate::model::pseudo_resource::PSEUDO_RESOURCE_JOIN_SEQ;
 use crate::reexports::k8s::*;
@@ -71,6 +72,8 @@ mk_resource_enum!(
 pub(crate) struct EvaluatedResource {
     pub(crate) values: Arc<Vec<EvalResult>>,
     pub(crate) resource: ResourceView,
+    /// Columns the values were evaluated with.
+    pub(crate) columns: Arc<Vec<Column>>,
 }
 
 impl ResourceView {
@@ -87,6 +90,7 @@ impl ResourceView {
             ResourceView::Pod(r) => extract_phase!(r),
             ResourceView::Node(r) => extract_phase!(r),
             ResourceView::PersistentVolume(r) => extract_phase!(r),
+            ResourceView::PseudoResource(r) if r.deleted_at.is_some() => "Deleted".to_string(),
             ResourceView::PseudoResource(r) => r.source.status(),
             _ => if self.deletion_timestamp().is_some() {
                 "Deleted"
@@ -99,7 +103,9 @@ impl ResourceView {
 
     pub(crate) fn build_pseudo_gvk(&self, extractor_name: &str) -> GroupVersionKind {
         let mut gvk = self.gvk();
-        let parts = [&gvk.kind, extractor_name, &self.name()];
+        // pseudo resource ids may contain the separator, i.e. non-resource URLs of rules
+        let name = self.name().replace(PSEUDO_RESOURCE_JOIN_SEQ, "%2F");
+        let parts = [&gvk.kind, extractor_name, &name];
         gvk.kind = parts.join(PSEUDO_RESOURCE_JOIN_SEQ);
         gvk
     }
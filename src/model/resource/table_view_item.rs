@@ -3,13 +3,28 @@ use std::cmp::Ordering;
 use cursive::reexports::log::{error, info};
 use cursive_table_view::{TableView, TableViewItem};
 
+use crate::eval::eval_result::EvalResult;
+use crate::eval::sort::compare_eval_results;
 use crate::model::resource::resource_view::EvaluatedResource;
 use crate::traits::ext::table_view::TableViewExt;
 
+/// Not used by any color marker, so a deleted row doesn't look like a styled cell.
+pub(crate) const DELETED_MARK: &str = "† ";
+
 impl TableViewItem<usize> for EvaluatedResource {
     fn to_column(&self, column: usize) -> String {
         if let Some(eval_result) = self.values.get(column) {
-            eval_result.to_string()
+            // the table can't style cells, so deleted resources and styled cells are marked
+            // with a prefix
+            let marker = match eval_result {
+                EvalResult::Styled(_, style) => style.marker().unwrap_or_default(),
+                _ => "",
+            };
+            if column == 0 && self.resource.deletion_timestamp().is_some() {
+                format!("{DELETED_MARK}{marker}{}", eval_result.to_string())
+            } else {
+                format!("{marker}{}", eval_result.to_string())
+            }
         } else {
             error!("No column at index: {}", column);
             "-".to_string()
@@ -20,7 +35,15 @@ impl TableViewItem<usize> for EvaluatedResource {
     where
         Self: Sized,
     {
-        self.values.get(column).cmp(&other.values.get(column))
+        let sort_type = self
+            .columns
+            .get(column)
+            .map(|column| column.sort_type)
+            .unwrap_or_default();
+        match (self.values.get(column), other.values.get(column)) {
+            (Some(left), Some(right)) => compare_eval_results(left, right, sort_type),
+            (left, right) => left.is_some().cmp(&right.is_some()),
+        }
     }
 }
 
@@ -0,0 +1,106 @@
+use chrono::Utc;
+use kube::ResourceExt;
+
+use crate::model::resource::resource_view::ResourceView;
+
+/// A flattened view of both `core/v1/Event` and `events.k8s.io/v1/Event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResourceEvent {
+    pub(crate) uid: String,
+    pub(crate) involved_uid: String,
+    pub(crate) type_: String,
+    pub(crate) reason: String,
+    pub(crate) message: String,
+    pub(crate) source: String,
+    pub(crate) count: i32,
+    pub(crate) last_timestamp: Option<chrono::DateTime<Utc>>,
+}
+
+impl ResourceEvent {
+    pub(crate) fn from_resource(resource: &ResourceView) -> Option<Self> {
+        match resource {
+            ResourceView::CoreEvent(event) => Some(Self {
+                uid: event.uid()?,
+                involved_uid: event.involved_object.uid.clone()?,
+                type_: event.type_.clone().unwrap_or_default(),
+                reason: event.reason.clone().unwrap_or_default(),
+                message: event.message.clone().unwrap_or_default(),
+                source: event
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.component.clone())
+                    .unwrap_or_default(),
+                count: event.count.unwrap_or(1),
+                last_timestamp: event
+                    .last_timestamp
+                    .as_ref()
+                    .map(|ts| ts.0)
+                    .or_else(|| event.event_time.as_ref().map(|ts| ts.0))
+                    .or_else(|| event.first_timestamp.as_ref().map(|ts| ts.0))
+                    .or_else(|| event.creation_timestamp().map(|ts| ts.0)),
+            }),
+            ResourceView::Event(event) => Some(Self {
+                uid: event.uid()?,
+                involved_uid: event.regarding.as_ref()?.uid.clone()?,
+                type_: event.type_.clone().unwrap_or_default(),
+                reason: event.reason.clone().unwrap_or_default(),
+                message: event.note.clone().unwrap_or_default(),
+                source: event.reporting_controller.clone().unwrap_or_default(),
+                count: event
+                    .series
+                    .as_ref()
+                    .map(|series| series.count)
+                    .or(event.deprecated_count)
+                    .unwrap_or(1),
+                last_timestamp: event
+                    .series
+                    .as_ref()
+                    .map(|series| series.last_observed_time.0)
+                    .or_else(|| event.deprecated_last_timestamp.as_ref().map(|ts| ts.0))
+                    .or(Some(event.event_time.0)),
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_warning(&self) -> bool {
+        self.type_ == "Warning"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use k8s_openapi::serde_json;
+    use k8s_openapi::serde_json::json;
+
+    use crate::reexports::k8s::CoreEvent;
+
+    use super::*;
+
+    #[test]
+    fn test_from_core_event() {
+        let event: CoreEvent = serde_json::from_value(json!({
+            "apiVersion": "v1",
+            "kind": "Event",
+            "metadata": { "name": "example.1", "uid": "event-uid" },
+            "involvedObject": { "kind": "Pod", "name": "example", "uid": "pod-uid" },
+            "type": "Warning",
+            "reason": "BackOff",
+            "message": "Back-off restarting failed container",
+            "count": 3,
+            "lastTimestamp": "2023-05-01T10:00:00Z",
+        }))
+        .unwrap();
+
+        let event =
+            ResourceEvent::from_resource(&ResourceView::CoreEvent(Arc::new(event))).unwrap();
+
+        assert_eq!(event.involved_uid, "pod-uid");
+        assert_eq!(event.reason, "BackOff");
+        assert_eq!(event.count, 3);
+        assert!(event.is_warning());
+        assert!(event.last_timestamp.is_some());
+    }
+}
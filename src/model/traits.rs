@@ -3,4 +3,6 @@ use k8s_openapi::serde_json;
 pub(crate) trait SerializeExt {
     fn to_yaml(&self) -> Result<String, serde_yaml::Error>;
     fn to_json(&self) -> Result<String, serde_json::Error>;
+    /// Converts into a rhai value directly, without a JSON round-trip.
+    fn to_rhai(&self) -> Result<rhai::Dynamic, Box<rhai::EvalAltResult>>;
 }
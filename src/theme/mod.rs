@@ -1,4 +1,19 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use cursive::reexports::log::error;
 use cursive::theme::{BorderStyle, Color, Palette, Theme};
+use serde::Deserialize;
+
+use crate::ui::highlighter::{Highlighter, SharedSyntaxSet};
+use crate::util::fs::scan_files;
+use crate::util::panics::ResultExt;
+use crate::util::paths::resolve_path;
+
+/// The theme file used unless `theme` is set in config.yaml.
+pub(crate) const DEFAULT_THEME_NAME: &str = "dark";
+const DEFAULT_SYNTAX_THEME: &str = "base16-eighties.dark";
 
 pub(crate) fn get_theme() -> Theme {
     Theme {
@@ -32,3 +47,161 @@ pub(crate) fn get_theme() -> Theme {
         borders: BorderStyle::Outset,
     }
 }
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Borders {
+    Simple,
+    #[default]
+    Outset,
+    None,
+}
+
+/// A theme file `<theme-dir>/<name>.yaml`. Palette colors not set are taken from the
+/// built-in dark theme.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ThemeConfig {
+    /// Palette keys (`background`, `view`, `primary`, `highlight`, ...) to colors like
+    /// `#282a36` or `light red`.
+    #[serde(default)]
+    pub(crate) palette: BTreeMap<String, String>,
+    #[serde(default = "default_shadow")]
+    pub(crate) shadow: bool,
+    #[serde(default)]
+    pub(crate) borders: Borders,
+    /// A theme bundled with syntect, i.e. `InspiredGitHub`.
+    pub(crate) syntax_theme: Option<String>,
+    /// A `.tmTheme` file relative to the theme file, takes precedence over `syntax_theme`.
+    pub(crate) syntax_theme_path: Option<PathBuf>,
+
+    #[serde(skip)]
+    pub(crate) path: PathBuf,
+}
+
+fn default_shadow() -> bool {
+    true
+}
+
+impl ThemeConfig {
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let mut theme: Self = serde_yaml::from_str(&content)?;
+        theme.path = path.to_path_buf();
+        Ok(theme)
+    }
+
+    pub(crate) fn to_theme(&self) -> anyhow::Result<Theme> {
+        let mut theme = get_theme();
+        for (key, value) in &self.palette {
+            let color = Color::parse(value)
+                .with_context(|| format!("Invalid color {value} of palette key {key}"))?;
+            theme.palette.set_color(key, color);
+        }
+        theme.shadow = self.shadow;
+        theme.borders = match self.borders {
+            Borders::Simple => BorderStyle::Simple,
+            Borders::Outset => BorderStyle::Outset,
+            Borders::None => BorderStyle::None,
+        };
+        Ok(theme)
+    }
+
+    pub(crate) fn build_highlighter(
+        &self,
+        syntax_set: &SharedSyntaxSet,
+    ) -> anyhow::Result<Highlighter> {
+        match &self.syntax_theme_path {
+            Some(syntax_theme_path) => Highlighter::from_theme_file(
+                &resolve_path(&self.path, syntax_theme_path),
+                syntax_set,
+            ),
+            None => Highlighter::new(
+                self.syntax_theme.as_deref().unwrap_or(DEFAULT_SYNTAX_THEME),
+                syntax_set,
+            ),
+        }
+    }
+}
+
+/// Theme files of the theme dirs by their names, files of later dirs override the ones of
+/// earlier dirs. Files that fail to load are logged and skipped.
+pub(crate) fn load_themes(theme_dirs: &[PathBuf]) -> BTreeMap<String, ThemeConfig> {
+    let mut themes = BTreeMap::new();
+    for dir in theme_dirs {
+        let mut files = scan_files(std::slice::from_ref(dir));
+        files.sort();
+        for file in files {
+            let is_yaml = file
+                .extension()
+                .map(|ext| ext == "yaml" || ext == "yml")
+                .unwrap_or(false);
+            let name = file.file_stem().and_then(|name| name.to_str());
+            let name = match name {
+                Some(name) if is_yaml => name.to_string(),
+                _ => continue,
+            };
+            match ThemeConfig::load(&file) {
+                Ok(theme) => {
+                    themes.insert(name, theme);
+                }
+                Err(err) => error!("Failed to load theme {}: {err}", file.display()),
+            }
+        }
+    }
+    themes
+}
+
+/// Builds the UI theme and the highlighter of a theme file.
+pub(crate) fn load_theme(
+    theme_dirs: &[PathBuf],
+    name: &str,
+    syntax_set: &SharedSyntaxSet,
+) -> anyhow::Result<(Theme, Highlighter)> {
+    let themes = load_themes(theme_dirs);
+    let theme = themes
+        .get(name)
+        .with_context(|| format!("Theme {name} is not found in {theme_dirs:?}"))?;
+    Ok((theme.to_theme()?, theme.build_highlighter(syntax_set)?))
+}
+
+/// Falls back to the built-in theme if the configured one can't be loaded.
+pub(crate) fn load_theme_or_default(
+    theme_dirs: &[PathBuf],
+    name: &str,
+    syntax_set: &SharedSyntaxSet,
+) -> (Theme, Highlighter) {
+    match load_theme(theme_dirs, name, syntax_set) {
+        Ok(theme) => theme,
+        Err(err) => {
+            error!("Failed to load theme {name}: {err:#}");
+            (
+                get_theme(),
+                Highlighter::new(DEFAULT_SYNTAX_THEME, syntax_set).unwrap_or_log(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::highlighter::load_syntax_set;
+
+    use super::*;
+
+    #[test]
+    fn test_default_themes() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("default_config")
+            .join("themes");
+        let themes = load_themes(&[dir.clone()]);
+        assert!(themes.contains_key(DEFAULT_THEME_NAME));
+        assert!(themes.contains_key("light"));
+        let syntax_set = load_syntax_set();
+        for name in themes.keys() {
+            assert!(
+                load_theme(&[dir.clone()], name, &syntax_set).is_ok(),
+                "{name}"
+            );
+        }
+    }
+}
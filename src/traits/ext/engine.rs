@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use rhai::{Engine, EvalAltResult, AST};
+use rhai::{Engine, EvalAltResult, Position, AST};
 
 pub(crate) trait EngineExt {
     fn compile_file_with_imports(
@@ -35,12 +35,48 @@ impl EngineExt for Engine {
         content: &str,
         imports: &[String],
     ) -> Result<AST, EvalAltResult> {
-        let mut final_content = imports.join("\n");
-        final_content.push_str("\n\n");
-        final_content.push_str(content);
+        let final_content = prepend_imports(content, imports);
 
         let mut ast: AST = self.compile(&final_content)?;
         ast.set_source(final_content);
         Ok(ast)
     }
 }
+
+/// Prepends the import lines to a script. The script starts at the line returned by
+/// `imports_line_offset` + 1.
+pub(crate) fn prepend_imports(content: &str, imports: &[String]) -> String {
+    let mut final_content = imports.join("\n");
+    final_content.push_str("\n\n");
+    final_content.push_str(content);
+    final_content
+}
+
+/// The number of lines `prepend_imports` adds in front of a script.
+pub(crate) fn imports_line_offset(imports: &[String]) -> usize {
+    prepend_imports("", imports).matches('\n').count()
+}
+
+/// Returns the message of a compilation error and its position in the script without the
+/// prepended imports. Errors in the imports have no position.
+pub(crate) fn locate_script_error(err: &EvalAltResult, imports: &[String]) -> (Position, String) {
+    let offset = imports_line_offset(imports);
+    let position = err.position();
+    let position = match position.line() {
+        Some(line) if line > offset => Position::new(
+            (line - offset) as u16,
+            position.position().unwrap_or(0) as u16,
+        ),
+        _ => Position::NONE,
+    };
+
+    let message = match err {
+        EvalAltResult::ErrorParsing(parse_error, _) => parse_error.to_string(),
+        EvalAltResult::ErrorModuleNotFound(name, _) => format!("module `{name}` not found"),
+        EvalAltResult::ErrorInModule(name, inner, _) => {
+            format!("error in module `{name}`: {inner}")
+        }
+        err => err.to_string(),
+    };
+    (position, message)
+}
@@ -1,9 +1,8 @@
 use std::fmt::Debug;
 
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use kube::api::GroupVersionKind;
-use kube::ResourceExt;
 
 use crate::model::pseudo_resource::PSEUDO_RESOURCE_JOIN_SEQ;
 
@@ -35,10 +34,11 @@ pub(crate) trait GvkExt {
 
 pub(crate) trait PseudoResourceGvkExt {
     fn get_pseudo_parent(&self) -> Option<GroupVersionKind>;
-}
-
-pub(crate) trait PseudoGvkBuilderExt {
-    fn build_pseudo_gvk(&self, extractor_name: &str) -> GroupVersionKind;
+    /// The GVK pseudo resources are configured with: source names are dropped from the kind,
+    /// i.e. `ClusterRole/rule/admin/resource/0` becomes `ClusterRole/rule/resource`.
+    fn get_config_gvk(&self) -> GroupVersionKind;
+    /// The drill-down path of a pseudo resource GVK, i.e. `ClusterRole admin › rule 0 › resource`.
+    fn breadcrumb(&self) -> Option<String>;
 }
 
 impl PseudoResourceGvkExt for GroupVersionKind {
@@ -51,6 +51,47 @@ impl PseudoResourceGvkExt for GroupVersionKind {
             None
         }
     }
+
+    fn get_config_gvk(&self) -> GroupVersionKind {
+        let kind = self
+            .kind
+            .split(PSEUDO_RESOURCE_JOIN_SEQ)
+            .enumerate()
+            .filter(|(index, _)| *index == 0 || index % 2 == 1)
+            .map(|(_, part)| part)
+            .join(PSEUDO_RESOURCE_JOIN_SEQ);
+        let mut gvk = self.clone();
+        gvk.kind = kind;
+        gvk
+    }
+
+    fn breadcrumb(&self) -> Option<String> {
+        let parts = self
+            .kind
+            .split(PSEUDO_RESOURCE_JOIN_SEQ)
+            .collect::<Vec<_>>();
+        if parts.len() < 3 {
+            return None;
+        }
+        // `Kind/extractor/name/extractor/name...`: every extractor is applied to the named
+        // resource of the previous level
+        let kinds = std::iter::once(parts[0]).chain(parts.iter().skip(1).step_by(2).copied());
+        // names were escaped by `build_pseudo_gvk`
+        let names = parts
+            .iter()
+            .skip(2)
+            .step_by(2)
+            .map(|name| name.replace("%2F", PSEUDO_RESOURCE_JOIN_SEQ));
+        let crumbs = kinds
+            .zip_longest(names)
+            .map(|pair| match pair {
+                EitherOrBoth::Both(kind, name) => format!("{kind} {name}"),
+                EitherOrBoth::Left(kind) => kind.to_string(),
+                EitherOrBoth::Right(name) => name,
+            })
+            .join(" › ");
+        Some(crumbs)
+    }
 }
 
 impl<T> GvkStaticExt for T
@@ -77,24 +118,6 @@ where
     }
 }
 
-impl<T> PseudoGvkBuilderExt for T
-where
-    T: k8s_openapi::Metadata<Ty = ObjectMeta>
-        + 'static
-        + Clone
-        + Debug
-        + Send
-        + Sync
-        + for<'de> k8s_openapi::serde::Deserialize<'de>,
-{
-    fn build_pseudo_gvk(&self, extractor_name: &str) -> GroupVersionKind {
-        let mut gvk = self.gvk();
-        let parts = [&gvk.kind, extractor_name, &self.name_any()];
-        gvk.kind = parts.join(PSEUDO_RESOURCE_JOIN_SEQ);
-        gvk
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use k8s_openapi::api::core::v1::Pod;
@@ -110,4 +133,29 @@ mod tests {
         assert!(gvk.get_pseudo_parent().is_some());
         assert_eq!(gvk.get_pseudo_parent().unwrap().kind, "Pod/test");
     }
+
+    #[test]
+    fn test_config_gvk_and_breadcrumb() {
+        let mut gvk = Pod::gvk_for_type();
+        assert_eq!(gvk.get_config_gvk().kind, "Pod");
+        assert!(gvk.breadcrumb().is_none());
+
+        gvk.kind = "Pod/container/web".to_string();
+        assert_eq!(gvk.get_config_gvk().kind, "Pod/container");
+        assert_eq!(gvk.breadcrumb().unwrap(), "Pod web › container");
+
+        gvk.kind = "ClusterRole/rule/admin/resource/0".to_string();
+        assert_eq!(gvk.get_config_gvk().kind, "ClusterRole/rule/resource");
+        assert_eq!(
+            gvk.breadcrumb().unwrap(),
+            "ClusterRole admin › rule 0 › resource"
+        );
+
+        gvk.kind = "ClusterRole/rule/admin/resource_name/0::::%2Fhealthz".to_string();
+        assert_eq!(gvk.get_config_gvk().kind, "ClusterRole/rule/resource_name");
+        assert_eq!(
+            gvk.breadcrumb().unwrap(),
+            "ClusterRole admin › rule 0::::/healthz › resource_name"
+        );
+    }
 }
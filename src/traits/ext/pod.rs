@@ -8,6 +8,7 @@ pub(crate) trait PodExt {
     fn get_pod_containers(&self) -> Option<Vec<PodContainerView>>;
     fn get_expected_exec_container(&self) -> Option<Container>;
     fn get_first_container(&self) -> Option<Container>;
+    fn is_ready(&self) -> bool;
 }
 
 impl PodExt for Pod {
@@ -63,4 +64,17 @@ impl PodExt for Pod {
             .next()
             .map(|container_view| container_view.container)
     }
+
+    fn is_ready(&self) -> bool {
+        if self.metadata.deletion_timestamp.is_some() {
+            return false;
+        }
+
+        self.status
+            .as_ref()
+            .and_then(|status| status.conditions.as_ref())
+            .into_iter()
+            .flatten()
+            .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+    }
 }
@@ -1,13 +1,17 @@
 use std::sync::Arc;
 
 use cursive::traits::*;
-use cursive::views::Dialog;
+use cursive::views::{Dialog, LinearLayout, Panel, SelectView};
 use cursive_cached_text_view::CachedTextView;
 
 use crate::model::resource::resource_view::ResourceView;
+use crate::model::resource_event::ResourceEvent;
 use crate::reexports::sync::Mutex;
 use crate::traits::ext::gvk::GvkExt;
+use crate::traits::ext::kanal_sender::KanalSenderExt;
 use crate::traits::ext::mutex::MutexExt;
+use crate::ui::components::events_view::build_events_pane;
+use crate::ui::signals::InterUiSignal;
 use crate::ui::ui_store::{UiStore, UiStoreDispatcherExt};
 use crate::ui::view_meta::ViewMeta;
 use crate::util::view_with_data::ViewWithMeta;
@@ -15,21 +19,53 @@ use crate::util::view_with_data::ViewWithMeta;
 pub(crate) fn build_code_view(
     store: Arc<Mutex<UiStore>>,
     resource: ResourceView,
+    events: &[ResourceEvent],
 ) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
-    let styled_string = store.lock_sync()?.highlight(&resource)?;
+    let (styled_string, render_profiles, to_ui_sender) = store.locking(|store| {
+        let render_profile = &store.render_profiles.default;
+        Ok((
+            store.highlight(&resource, render_profile)?,
+            Arc::clone(&store.render_profiles),
+            store.inter_ui_sender.clone(),
+        ))
+    })?;
 
+    let counter = store.inc_counter();
     let view_meta = ViewMeta::Code {
-        id: store.inc_counter(),
+        id: counter,
         gvk: resource.gvk(),
         title: resource.name(),
         uid: resource.uid_or_name(),
+        render_profile: render_profiles.default.clone(),
     };
 
+    let names = render_profiles.names();
+    let selected = names
+        .iter()
+        .position(|name| name == &render_profiles.default)
+        .unwrap_or_default();
+    let profile_view = SelectView::new()
+        .popup()
+        .with_all_str(names)
+        .selected(selected)
+        .on_submit(move |_, name: &String| {
+            to_ui_sender.send_unwrap(InterUiSignal::CodeApplyRenderProfile(
+                counter,
+                name.to_string(),
+            ));
+        });
+
     let tv = CachedTextView::new(styled_string, 5)
         .with_name(view_meta.get_unique_name())
         .full_screen()
         .scrollable();
-    let dialog = Dialog::around(tv).title(resource.full_unique_name());
+
+    let mut main_layout = LinearLayout::vertical();
+    main_layout.add_child(Panel::new(profile_view).title("Render profile"));
+    main_layout.add_child(tv);
+    main_layout.add_child(build_events_pane(view_meta.get_events_name(), events));
+
+    let dialog = Dialog::around(main_layout).title(resource.full_unique_name());
 
     Ok(ViewWithMeta::new(dialog, view_meta))
 }
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use cursive::theme::{BaseColor, Color, Style};
+use cursive::traits::{Nameable, Resizable, Scrollable};
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, Panel, TextView};
+
+use crate::config::extractor::ConfigError;
+use crate::reexports::sync::{Mutex, RwLock};
+use crate::traits::ext::mutex::MutexExt;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::view_with_data::ViewWithMeta;
+
+pub(crate) const CONFIG_ERRORS_DIALOG_NAME: &str = "Config errors";
+
+pub(crate) fn render_config_errors(errors: &[ConfigError]) -> StyledString {
+    let mut result = StyledString::new();
+    if errors.is_empty() {
+        result.append_styled("No config errors", Style::secondary());
+        return result;
+    }
+
+    for error in errors {
+        let mut location = error.path.display().to_string();
+        if let Some(line) = error.position.line() {
+            location.push_str(&format!(":{line}"));
+            if let Some(position) = error.position.position() {
+                location.push_str(&format!(":{position}"));
+            }
+        }
+        result.append_styled(location, Style::from(Color::Light(BaseColor::Red)));
+        result.append_plain(format!("\n  {}: {}\n", error.context, error.message));
+    }
+
+    result
+}
+
+/// The dialog is refreshed while open when the configs are reloaded.
+pub(crate) fn build_config_errors_view(
+    store: Arc<Mutex<UiStore>>,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (errors, counter) =
+        store.locking(|mut store| Ok((Arc::clone(&store.config_errors), store.inc_counter())))?;
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: CONFIG_ERRORS_DIALOG_NAME.to_string(),
+    };
+
+    let tv = TextView::new(render_config_errors(&errors));
+    let dialog = Dialog::around(Panel::new(
+        tv.with_name(view_meta.get_unique_name())
+            .scrollable()
+            .min_width(60),
+    ))
+    .title(view_meta.title());
+
+    Ok(ViewWithMeta {
+        inner: Box::new(dialog),
+        meta: Arc::new(RwLock::new(view_meta)),
+    })
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use cursive::traits::{Nameable, Resizable};
+use cursive::views::{Dialog, Panel, SelectView};
+use kube::api::GroupVersionKind;
+
+use crate::reexports::sync::{Mutex, RwLock};
+use crate::traits::ext::gvk::GvkNameExt;
+use crate::traits::ext::kanal_sender::KanalSenderExt;
+use crate::traits::ext::mutex::MutexExt;
+use crate::traits::ext::rw_lock::RwLockExt;
+use crate::ui::resource_manager::DeletedResource;
+use crate::ui::signals::InterUiSignal;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::ui::ago;
+use crate::util::view_with_data::ViewWithMeta;
+
+fn build_title(deleted: &DeletedResource) -> String {
+    let namespace = deleted.resource.namespace();
+    let name = if namespace.is_empty() {
+        deleted.resource.name()
+    } else {
+        format!("{namespace}/{}", deleted.resource.name())
+    };
+    let age = ago(Utc::now().signed_duration_since(deleted.deleted_at));
+    format!("{name} (deleted {age} ago)")
+}
+
+pub(crate) fn build_deleted_history_view(
+    store: Arc<Mutex<UiStore>>,
+    gvk: &GroupVersionKind,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (resource_manager, to_ui_sender, counter) = store.locking(|mut store| {
+        Ok((
+            Arc::clone(&store.resource_manager),
+            store.inter_ui_sender.clone(),
+            store.inc_counter(),
+        ))
+    })?;
+    let history = resource_manager.read_sync()?.get_deleted_history(gvk);
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: format!("Deleted {}", gvk.full_name()),
+    };
+
+    let mut sv = SelectView::new();
+    for deleted in history {
+        sv.add_item(build_title(&deleted), deleted);
+    }
+
+    sv.set_on_submit(move |_, deleted: &DeletedResource| {
+        to_ui_sender.send_unwrap(InterUiSignal::ShowYaml(deleted.resource.clone()));
+    });
+
+    let dialog = Dialog::around(Panel::new(
+        sv.with_name(view_meta.get_unique_name()).min_width(40),
+    ))
+    .title(view_meta.title());
+
+    Ok(ViewWithMeta {
+        inner: Box::new(dialog),
+        meta: Arc::new(RwLock::new(view_meta)),
+    })
+}
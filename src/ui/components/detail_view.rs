@@ -5,8 +5,10 @@ use cursive::traits::*;
 use cursive::views::{LinearLayout, Panel};
 
 use crate::model::resource::resource_view::ResourceView;
+use crate::model::resource_event::ResourceEvent;
 use crate::reexports::sync::Mutex;
 use crate::traits::ext::gvk::{GvkExt, GvkNameExt};
+use crate::ui::components::events_view::build_events_pane;
 use crate::ui::ui_store::{UiStore, UiStoreDispatcherExt};
 use crate::ui::view_meta::ViewMeta;
 use crate::util::view_with_data::ViewWithMeta;
@@ -15,6 +17,7 @@ pub(crate) fn build_detail_view(
     store: Arc<Mutex<UiStore>>,
     resource: ResourceView,
     html: String,
+    events: &[ResourceEvent],
 ) -> ViewWithMeta<ViewMeta> {
     let mut view = cursive_markup::MarkupView::html(&html);
     view.on_link_focus(|_, url| {
@@ -37,6 +40,7 @@ pub(crate) fn build_detail_view(
     let view = view.with_name(meta.get_unique_name()).scrollable();
     let panel = Panel::new(view).title(title);
     main_layout.add_child(panel.full_screen());
+    main_layout.add_child(build_events_pane(meta.get_events_name(), events));
 
     ViewWithMeta::new(main_layout, meta)
 }
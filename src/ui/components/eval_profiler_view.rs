@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use cursive::theme::Style;
+use cursive::traits::{Nameable, Resizable, Scrollable};
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, Panel, TextView};
+
+use crate::eval::profiler::{ProfileEntry, SharedEvalProfiler};
+use crate::reexports::sync::{Mutex, RwLock};
+use crate::traits::ext::mutex::MutexExt;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::view_with_data::ViewWithMeta;
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+pub(crate) fn render_eval_profile(entries: &[ProfileEntry]) -> StyledString {
+    let mut result = StyledString::new();
+    if entries.is_empty() {
+        result.append_styled(
+            "Nothing was evaluated while the profiler was open",
+            Style::secondary(),
+        );
+        return result;
+    }
+
+    result.append_styled(
+        format!(
+            "{: <40} {: <40} {: >8} {: >12} {: >10} {: >10}\n",
+            "GVK", "Target", "Count", "Total", "Mean", "p99"
+        ),
+        Style::title_primary(),
+    );
+    for entry in entries {
+        result.append_plain(format!(
+            "{: <40} {: <40} {: >8} {: >12} {: >10} {: >10}\n",
+            entry.gvk,
+            entry.target.to_string(),
+            entry.count,
+            format_duration(entry.total),
+            format_duration(entry.mean),
+            format_duration(entry.p99),
+        ));
+    }
+
+    result
+}
+
+/// The profile is a snapshot, `Refresh` takes a new one and `Reset` clears the recorded
+/// evaluations. Evaluations are recorded until the dialog is dropped, whichever way it's
+/// closed.
+pub(crate) fn build_eval_profiler_view(
+    store: Arc<Mutex<UiStore>>,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (profiler, counter) =
+        store.locking(|mut store| Ok((Arc::clone(&store.eval_profiler), store.inc_counter())))?;
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: "Rhai evaluation profile".to_string(),
+    };
+    let name = view_meta.get_unique_name();
+
+    let session = Arc::new(profiler.start());
+    let refresh = |profiler: &SharedEvalProfiler, name: &str| {
+        let profiler = Arc::clone(profiler);
+        let name = name.to_string();
+        // the buttons own the session
+        let session = Arc::clone(&session);
+        move |siv: &mut cursive::Cursive| {
+            let _session = &session;
+            let content = render_eval_profile(&profiler.snapshot());
+            siv.call_on_name(&name, |tv: &mut TextView| tv.set_content(content));
+        }
+    };
+    let reset = {
+        let profiler = Arc::clone(&profiler);
+        let refresh = refresh(&profiler, &name);
+        move |siv: &mut cursive::Cursive| {
+            profiler.reset();
+            refresh(siv);
+        }
+    };
+
+    let tv = TextView::new(render_eval_profile(&profiler.snapshot()));
+    let dialog = Dialog::around(Panel::new(
+        tv.with_name(name.clone()).scrollable().min_width(120),
+    ))
+    .title(view_meta.title())
+    .button("Refresh", refresh(&profiler, &name))
+    .button("Reset", reset);
+
+    Ok(ViewWithMeta {
+        inner: Box::new(dialog),
+        meta: Arc::new(RwLock::new(view_meta)),
+    })
+}
@@ -0,0 +1,52 @@
+use chrono::Utc;
+use cursive::theme::{BaseColor, Color, Style};
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::view::ScrollStrategy;
+use cursive::views::Panel;
+use cursive::View;
+use cursive_cached_text_view::CachedTextView;
+
+use crate::model::resource_event::ResourceEvent;
+use crate::util::ui::ago;
+
+const EVENTS_PANE_HEIGHT: usize = 8;
+
+pub(crate) fn render_events(events: &[ResourceEvent]) -> StyledString {
+    let mut result = StyledString::new();
+    if events.is_empty() {
+        result.append_styled("No events", Style::secondary());
+        return result;
+    }
+
+    for event in events {
+        let age = event
+            .last_timestamp
+            .map(|ts| ago(Utc::now().signed_duration_since(ts)))
+            .unwrap_or_else(|| "-".to_string());
+
+        let line = format!(
+            "{age: >5} {: <8} {: <24} x{: <4} {}: {}\n",
+            event.type_, event.reason, event.count, event.source, event.message
+        );
+
+        if event.is_warning() {
+            result.append_styled(line, Style::from(Color::Light(BaseColor::Red)));
+        } else {
+            result.append_plain(line);
+        }
+    }
+
+    result
+}
+
+pub(crate) fn build_events_pane(name: String, events: &[ResourceEvent]) -> impl View {
+    let tv = CachedTextView::new(render_events(events), 5)
+        .with_name(name)
+        .scrollable()
+        .scroll_strategy(ScrollStrategy::StickToBottom);
+
+    Panel::new(tv)
+        .title("Events")
+        .max_height(EVENTS_PANE_HEIGHT + 2)
+}
@@ -2,11 +2,13 @@ use std::cmp::Ordering;
 use std::sync::Arc;
 
 use cursive::direction::Orientation;
+use cursive::reexports::log::warn;
 use cursive::traits::*;
 use cursive::views::{LinearLayout, Panel};
 use cursive_table_view::TableView;
 use kube::core::GroupVersionKind;
 
+use crate::config::extractor::{DefaultSort, SortOrder};
 use crate::model::resource::resource_view::EvaluatedResource;
 use crate::reexports::sync::Mutex;
 use crate::traits::ext::gvk::GvkNameExt;
@@ -26,11 +28,20 @@ trait UiStoreComponentExt {
 
 impl UiStoreComponentExt for Arc<Mutex<UiStore>> {
     fn build_list_view_table(&self, gvk: &GroupVersionKind) -> TableView<EvaluatedResource, usize> {
-        let column_handles = {
-            self.lock_unwrap()
-                .resource_manager
-                .read_unwrap()
-                .get_columns(gvk)
+        let (column_handles, sort, to_ui_sender) = {
+            let store = self.lock_unwrap();
+            let resource_manager = store.resource_manager.read_unwrap();
+            // the sort chosen by the user takes precedence over the configured one
+            let sort = store
+                .sort_state
+                .get(gvk)
+                .cloned()
+                .or_else(|| resource_manager.get_default_sort(gvk));
+            (
+                resource_manager.get_columns(gvk),
+                sort,
+                store.inter_ui_sender.clone(),
+            )
         };
 
         let mut table: TableView<EvaluatedResource, usize> = TableView::new();
@@ -51,6 +62,37 @@ impl UiStoreComponentExt for Arc<Mutex<UiStore>> {
             );
         }
 
+        if let Some(sort) = sort {
+            match column_handles
+                .iter()
+                .position(|column| column.name == sort.column)
+            {
+                Some(index) => table.sort_by(index, sort.order.to_ordering()),
+                None => warn!(
+                    "{}: sort column {} was not found",
+                    gvk.full_name(),
+                    sort.column
+                ),
+            }
+        }
+
+        let gvk = gvk.clone();
+        let column_names = column_handles
+            .iter()
+            .map(|column| column.name.clone())
+            .collect::<Vec<_>>();
+        table.set_on_sort(move |_, index, ordering| {
+            let column = match column_names.get(index) {
+                Some(name) => name.clone(),
+                None => return,
+            };
+            let sort = DefaultSort {
+                column,
+                order: SortOrder::from_ordering(ordering),
+            };
+            to_ui_sender.send_unwrap(InterUiSignal::ListSortChanged(gvk.clone(), sort));
+        });
+
         table
     }
 }
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use cursive::direction::Orientation;
+use cursive::reexports::log::error;
+use cursive::traits::Nameable;
+use cursive::views::{Checkbox, Dialog, EditView, LinearLayout, Panel};
+use cursive::Cursive;
+
+use crate::model::log_export_request::{parse_line_range, LogExportRequest, LogExportTarget};
+use crate::reexports::sync::Mutex;
+use crate::traits::ext::cloning_callback::CloningCallbackExt;
+use crate::traits::ext::kanal_sender::KanalSenderExt;
+use crate::traits::ext::mutex::MutexExt;
+use crate::ui::signals::InterUiSignal;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::panics::OptionExt;
+use crate::util::view_with_data::ViewWithMeta;
+
+#[derive(Debug, Clone, Copy)]
+enum ExportAction {
+    Save,
+    Copy,
+    Pipe,
+}
+
+pub(crate) fn build_log_export_dialog_view(
+    log_view_id: usize,
+    store: Arc<Mutex<UiStore>>,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (to_ui_sender, counter) =
+        store.locking(|mut store| Ok((store.inter_ui_sender.clone(), store.inc_counter())))?;
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: format!("Log Export for {log_view_id}"),
+    };
+
+    let path_edit_name = view_meta.get_edit_name("path");
+    let range_edit_name = view_meta.get_edit_name("range");
+    let command_edit_name = view_meta.get_edit_name("command");
+    let as_shown_checkbox_name = view_meta.get_checkbox_name("as_shown");
+
+    let default_path = std::env::temp_dir().join(format!("kgv-logs-{log_view_id}.log"));
+
+    let submit = {
+        let path_edit_name = path_edit_name.clone();
+        let range_edit_name = range_edit_name.clone();
+        let command_edit_name = command_edit_name.clone();
+        let as_shown_checkbox_name = as_shown_checkbox_name.clone();
+
+        Arc::new(move |siv: &mut Cursive, action: ExportAction| {
+            let range = siv
+                .call_on_name(&range_edit_name, |view: &mut EditView| view.get_content())
+                .unwrap_or_log();
+
+            let range = match parse_line_range(&range) {
+                Ok(range) => range,
+                Err(err) => {
+                    error!("Invalid range {range}: {err}");
+                    return;
+                }
+            };
+
+            let as_shown = siv
+                .call_on_name(&as_shown_checkbox_name, |view: &mut Checkbox| {
+                    view.is_checked()
+                })
+                .unwrap_or_log();
+
+            let target = match action {
+                ExportAction::Save => {
+                    let path = siv
+                        .call_on_name(&path_edit_name, |view: &mut EditView| view.get_content())
+                        .unwrap_or_log();
+                    if path.is_empty() {
+                        error!("Export path is empty");
+                        return;
+                    }
+                    LogExportTarget::File(PathBuf::from(path.as_str()))
+                }
+                ExportAction::Copy => LogExportTarget::Clipboard,
+                ExportAction::Pipe => {
+                    let command = siv
+                        .call_on_name(&command_edit_name, |view: &mut EditView| view.get_content())
+                        .unwrap_or_log();
+                    if command.trim().is_empty() {
+                        error!("Pipe command is empty");
+                        return;
+                    }
+                    LogExportTarget::Pipe(command.to_string())
+                }
+            };
+
+            store.lock_unwrap().view_stack.pop();
+            siv.pop_layer();
+
+            to_ui_sender.send_unwrap(InterUiSignal::LogsExport(LogExportRequest {
+                view_id: log_view_id,
+                target,
+                as_shown,
+                range,
+            }));
+        })
+    };
+
+    let path_edit_view = submit.cloning(|submit| {
+        EditView::new()
+            .content(default_path.display().to_string())
+            .on_submit(move |siv, _| submit(siv, ExportAction::Save))
+            .with_name(&path_edit_name)
+    });
+
+    let range_edit_view = EditView::new().with_name(&range_edit_name);
+
+    let command_edit_view = submit.cloning(|submit| {
+        EditView::new()
+            .content("less -R")
+            .on_submit(move |siv, _| submit(siv, ExportAction::Pipe))
+            .with_name(&command_edit_name)
+    });
+
+    let as_shown_checkbox = Checkbox::new().with_name(&as_shown_checkbox_name);
+
+    let mut options_layout = LinearLayout::new(Orientation::Horizontal);
+    options_layout.add_child(Panel::new(range_edit_view).title("Lines (from:to)"));
+    options_layout.add_child(Panel::new(as_shown_checkbox).title("As shown"));
+
+    let mut main_layout = LinearLayout::new(Orientation::Vertical);
+    main_layout.add_child(Panel::new(path_edit_view).title("File"));
+    main_layout.add_child(Panel::new(command_edit_view).title("Pipe to command"));
+    main_layout.add_child(options_layout);
+
+    let dialog = Dialog::around(main_layout)
+        .title("Export Logs")
+        .button(
+            "Save",
+            submit.cloning(|submit| move |siv: &mut Cursive| submit(siv, ExportAction::Save)),
+        )
+        .button(
+            "Copy",
+            submit.cloning(|submit| move |siv: &mut Cursive| submit(siv, ExportAction::Copy)),
+        )
+        .button("Pipe", move |siv| submit(siv, ExportAction::Pipe));
+
+    Ok(ViewWithMeta::new(dialog, view_meta))
+}
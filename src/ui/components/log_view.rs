@@ -4,7 +4,10 @@ use cursive::direction::Orientation;
 use cursive::reexports::log::error;
 use cursive::traits::{Nameable, Resizable, Scrollable};
 use cursive::view::ScrollStrategy;
-use cursive::views::{Checkbox, LinearLayout, Panel};
+use cursive::views::{
+    Checkbox, EditView, LinearLayout, NamedView, Panel, ResizedView, ScrollView, SelectView,
+};
+use cursive::Cursive;
 use cursive_cached_text_view::CachedTextView;
 use k8s_openapi::api::core::v1::{Container, Pod};
 use kube::api::LogParams;
@@ -17,17 +20,39 @@ use crate::traits::ext::kanal_sender::KanalSenderExt;
 use crate::traits::ext::mutex::MutexExt;
 use crate::ui::signals::InterUiSignal;
 use crate::ui::ui_store::UiStore;
-use crate::ui::view_meta::{LogFilter, ViewMeta};
-use crate::util::ui::build_edit_view;
+use crate::ui::view_meta::{LogFilter, TimestampFormat, ViewMeta};
+use crate::util::ui::{build_edit_view, parse_instant};
 use crate::util::view_with_data::ViewWithMeta;
 
+pub(crate) type LogScrollView = ScrollView<ResizedView<NamedView<CachedTextView>>>;
+
+fn parse_optional_instant(text: &str) -> Option<anyhow::Result<chrono::DateTime<chrono::Utc>>> {
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some(parse_instant(text, chrono::Utc::now()))
+    }
+}
+
+fn build_time_edit_view<F>(name: String, on_submit: F) -> NamedView<EditView>
+where
+    F: Fn(&mut Cursive, &str) + 'static,
+{
+    EditView::new().on_submit(on_submit).with_name(name)
+}
+
 pub(crate) fn build_log_view(
     pod: &Pod,
     container: &Container,
     store: Arc<Mutex<UiStore>>,
 ) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
-    let (to_ui_sender, counter) =
-        store.locking(|mut store| Ok((store.inter_ui_sender.clone(), store.inc_counter())))?;
+    let (to_ui_sender, log_defaults, counter) = store.locking(|mut store| {
+        Ok((
+            store.inter_ui_sender.clone(),
+            store.log_defaults,
+            store.inc_counter(),
+        ))
+    })?;
 
     let log_params = LogParams {
         container: Some(container.name.clone()),
@@ -35,8 +60,8 @@ pub(crate) fn build_log_view(
         limit_bytes: None,
         pretty: true,
         previous: false,
-        since_seconds: Some(60 * 60),
-        tail_lines: Some(1000),
+        since_seconds: log_defaults.since_seconds,
+        tail_lines: log_defaults.tail_lines,
         timestamps: true,
     };
 
@@ -52,10 +77,12 @@ pub(crate) fn build_log_view(
         filter: LogFilter {
             show_timestamps: true,
             value: String::default(),
+            ..LogFilter::default()
         },
         request,
         log_items: vec![],
         next_index: 0,
+        rendered_rows: vec![],
     };
 
     let mut main_layout = LinearLayout::new(Orientation::Vertical);
@@ -71,7 +98,10 @@ pub(crate) fn build_log_view(
     let since_minutes_edit_view = to_ui_sender.cloning(|to_ui_sender| {
         build_edit_view(
             view_meta.get_edit_name("since_minutes"),
-            "60",
+            log_defaults
+                .since_seconds
+                .map(|seconds| (seconds / 60).to_string())
+                .unwrap_or_default(),
             move |_, text, _| {
                 if let Ok(value) = text.parse::<usize>() {
                     to_ui_sender.send_unwrap(InterUiSignal::LogsApplySinceMinutes(counter, value));
@@ -86,7 +116,10 @@ pub(crate) fn build_log_view(
     let filter_tail_lines_edit_view = to_ui_sender.cloning(|to_ui_sender| {
         build_edit_view(
             view_meta.get_edit_name("tail_lines"),
-            "1000",
+            log_defaults
+                .tail_lines
+                .map(|lines| lines.to_string())
+                .unwrap_or_default(),
             move |_, text, _| {
                 if let Ok(value) = text.parse::<usize>() {
                     to_ui_sender.send_unwrap(InterUiSignal::LogsApplyTailLines(counter, value));
@@ -123,6 +156,67 @@ pub(crate) fn build_log_view(
     filter_layout.add_child(since_minutes_panel);
     filter_layout.add_child(filter_tail_lines_panel);
 
+    let mut time_layout = LinearLayout::new(Orientation::Horizontal);
+
+    let since_time_edit_view = to_ui_sender.cloning(|to_ui_sender| {
+        build_time_edit_view(view_meta.get_edit_name("since_time"), move |_, text| {
+            match parse_optional_instant(text).transpose() {
+                Ok(since_time) => {
+                    to_ui_sender.send_unwrap(InterUiSignal::LogsApplySinceTime(counter, since_time))
+                }
+                Err(err) => error!("Failed to parse since time {text}: {err}"),
+            }
+        })
+    });
+    let since_time_panel = Panel::new(since_time_edit_view)
+        .title("Since time")
+        .full_width();
+
+    let until_time_edit_view = to_ui_sender.cloning(|to_ui_sender| {
+        build_time_edit_view(view_meta.get_edit_name("until_time"), move |_, text| {
+            match parse_optional_instant(text).transpose() {
+                Ok(until_time) => {
+                    to_ui_sender.send_unwrap(InterUiSignal::LogsApplyUntilTime(counter, until_time))
+                }
+                Err(err) => error!("Failed to parse until time {text}: {err}"),
+            }
+        })
+    });
+    let until_time_panel = Panel::new(until_time_edit_view)
+        .title("Until time")
+        .full_width();
+
+    let jump_edit_view = to_ui_sender.cloning(|to_ui_sender| {
+        build_time_edit_view(view_meta.get_edit_name("jump_to"), move |_, text| {
+            match parse_optional_instant(text).transpose() {
+                Ok(instant) => {
+                    to_ui_sender.send_unwrap(InterUiSignal::LogsJumpToTime(counter, instant))
+                }
+                Err(err) => error!("Failed to parse jump time {text}: {err}"),
+            }
+        })
+    });
+    let jump_panel = Panel::new(jump_edit_view).title("Jump to").full_width();
+
+    let timestamp_format_view = to_ui_sender.cloning(|to_ui_sender| {
+        SelectView::new()
+            .popup()
+            .with_all(
+                TimestampFormat::all()
+                    .into_iter()
+                    .map(|format| (format.as_ref().to_string(), format)),
+            )
+            .on_submit(move |_, format: &TimestampFormat| {
+                to_ui_sender.send_unwrap(InterUiSignal::LogsApplyTimestampFormat(counter, *format));
+            })
+    });
+    let timestamp_format_panel = Panel::new(timestamp_format_view).title("Time format");
+
+    time_layout.add_child(since_time_panel);
+    time_layout.add_child(until_time_panel);
+    time_layout.add_child(jump_panel);
+    time_layout.add_child(timestamp_format_panel);
+
     let tv = CachedTextView::new("", 5)
         // .no_wrap()
         .with_name(view_meta.get_unique_name())
@@ -130,9 +224,11 @@ pub(crate) fn build_log_view(
         .scrollable()
         .scroll_x(true)
         .scroll_y(true)
-        .scroll_strategy(ScrollStrategy::StickToBottom);
+        .scroll_strategy(ScrollStrategy::StickToBottom)
+        .with_name(view_meta.get_scroll_name());
 
     main_layout.add_child(filter_layout.full_width());
+    main_layout.add_child(time_layout.full_width());
 
     let dialog_title = format!(
         "Logs {}/{}/{}",
@@ -1,31 +1,67 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use cursive::menu;
+use cursive::reexports::log::error;
 use cursive::views::Menubar;
 use kube::api::GroupVersionKind;
 
+use crate::config::port_forward_profiles::PortForwardProfiles;
 use crate::reexports::sync::Mutex;
+use crate::theme::load_themes;
+use crate::traits::ext::cloning_callback::CloningCallbackExt;
 use crate::traits::ext::gvk::GvkNameExt;
 use crate::traits::ext::kanal_sender::KanalSenderExt;
 use crate::traits::ext::mutex::MutexExt;
 use crate::ui::components::gvk_switcher::build_gvk_show_chain;
+use crate::ui::signals::InterUiSignal;
 use crate::ui::ui_store::UiStore;
 use crate::util::ui::group_gvks;
+use crate::util::yaml::YamlFile;
 
 pub(crate) fn build_menu(
     discovered_gvks: Vec<GroupVersionKind>,
     store: Arc<Mutex<UiStore>>,
 ) -> Menubar {
-    let (to_backend_sender, to_ui_sender) = {
+    let (
+        to_backend_sender,
+        to_ui_sender,
+        pf_profiles_path,
+        num_config_errors,
+        theme_dirs,
+        theme_name,
+    ) = {
         let store = store.lock_unwrap();
         (
             store.to_backend_sender.clone(),
             store.inter_ui_sender.clone(),
+            store.pf_profiles_path.clone(),
+            store.config_errors.len(),
+            store.theme_dirs.clone(),
+            store.theme_name.clone(),
         )
     };
 
     let mut menubar = Menubar::new();
+    if num_config_errors > 0 {
+        let to_ui_sender = to_ui_sender.clone();
+        menubar.add_leaf(
+            format!("⚠ Config errors: {num_config_errors}"),
+            move |_| {
+                to_ui_sender.send_unwrap(InterUiSignal::ShowConfigErrors);
+            },
+        );
+    }
     menubar.add_subtree("File", menu::Tree::new().leaf("Exit", |s| s.quit()));
+    menubar.add_subtree("Resource", build_resource_tree(to_ui_sender.clone()));
+    menubar.add_subtree(
+        "Port Forwarding",
+        build_port_forwarding_tree(&pf_profiles_path, to_ui_sender.clone()),
+    );
+    menubar.add_subtree(
+        "Theme",
+        build_theme_tree(&theme_dirs, &theme_name, to_ui_sender.clone()),
+    );
 
     let grouped_gvks = group_gvks(discovered_gvks);
 
@@ -52,3 +88,78 @@ pub(crate) fn build_menu(
 
     menubar
 }
+
+/// Actions on the resource selected in the top window, same as their hotkeys.
+fn build_resource_tree(to_ui_sender: kanal::Sender<InterUiSignal>) -> menu::Tree {
+    let actions: [(&str, fn() -> InterUiSignal); 3] = [
+        ("Owner tree (Ctrl+t)", || InterUiSignal::CtrlTPressed),
+        ("Related resources (Ctrl+o)", || InterUiSignal::CtrlOPressed),
+        ("Revisions (Ctrl+r)", || InterUiSignal::CtrlRPressed),
+    ];
+
+    let mut tree = menu::Tree::new();
+    for (label, signal) in actions {
+        let to_ui_sender = to_ui_sender.clone();
+        tree = tree.leaf(label, move |_| {
+            to_ui_sender.send_unwrap(signal());
+        });
+    }
+    tree
+}
+
+/// Theme files are listed each time the menu is built, so new ones show up on the next
+/// rebuild.
+fn build_theme_tree(
+    theme_dirs: &[PathBuf],
+    theme_name: &str,
+    to_ui_sender: kanal::Sender<InterUiSignal>,
+) -> menu::Tree {
+    let mut tree = menu::Tree::new();
+    for name in load_themes(theme_dirs).into_keys() {
+        let label = if name == theme_name {
+            format!("✓ {name}")
+        } else {
+            format!("  {name}")
+        };
+        let to_ui_sender = to_ui_sender.clone();
+        tree = tree.leaf(label, move |_| {
+            to_ui_sender.send_unwrap(InterUiSignal::SwitchTheme(name.clone()));
+        });
+    }
+    tree
+}
+
+fn build_port_forwarding_tree(
+    pf_profiles_path: &Path,
+    to_ui_sender: kanal::Sender<InterUiSignal>,
+) -> menu::Tree {
+    let mut tree = to_ui_sender.cloning(|to_ui_sender| {
+        menu::Tree::new().leaf("Active forwards", move |_| {
+            to_ui_sender.send_unwrap(InterUiSignal::CtrlGPressed);
+        })
+    });
+
+    let profiles = match PortForwardProfiles::load(pf_profiles_path) {
+        Ok(profiles) => profiles,
+        Err(err) => {
+            error!(
+                "Failed to load port forward profiles from {}: {err}",
+                pf_profiles_path.display()
+            );
+            return tree;
+        }
+    };
+
+    if !profiles.profiles.is_empty() {
+        tree.add_delimiter();
+    }
+
+    for name in profiles.profiles.into_keys() {
+        let to_ui_sender = to_ui_sender.clone();
+        tree = tree.leaf(format!("Start {name}"), move |_| {
+            to_ui_sender.send_unwrap(InterUiSignal::StartPortForwardProfile(name.clone()));
+        });
+    }
+
+    tree
+}
@@ -1,9 +1,17 @@
 pub(crate) mod code_view;
+pub(crate) mod config_errors_view;
+pub(crate) mod deleted_history_view;
 pub(crate) mod detail_view;
+pub(crate) mod eval_profiler_view;
+pub(crate) mod events_view;
 pub(crate) mod gvk_list_view;
 pub(crate) mod gvk_switcher;
+pub(crate) mod log_export_dialog_view;
 pub(crate) mod log_view;
 pub(crate) mod menu;
+pub(crate) mod owner_tree_view;
 pub(crate) mod port_forwarding_dialog_view;
 pub(crate) mod port_forwarding_view;
+pub(crate) mod related_view;
+pub(crate) mod revision_diff_view;
 pub(crate) mod window_switcher;
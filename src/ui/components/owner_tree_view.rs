@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use cursive::traits::{Nameable, Resizable, Scrollable};
+use cursive::views::{Dialog, Panel, SelectView};
+
+use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
+use crate::reexports::sync::{Mutex, RwLock};
+use crate::traits::ext::gvk::GvkExt;
+use crate::traits::ext::kanal_sender::KanalSenderExt;
+use crate::traits::ext::mutex::MutexExt;
+use crate::traits::ext::rw_lock::RwLockExt;
+use crate::ui::resource_manager::OwnerTree;
+use crate::ui::signals::InterUiSignal;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::ui::ago;
+use crate::util::view_with_data::ViewWithMeta;
+
+fn build_label(resource: &ResourceView) -> String {
+    let kind = match resource {
+        ResourceView::PseudoResource(pseudo_resource) => pseudo_resource.extractor_name.clone(),
+        resource => resource.gvk().kind,
+    };
+    let age = ago(Utc::now().signed_duration_since(resource.creation_timestamp()));
+    format!("{kind} {}  {}  {age}", resource.name(), resource.status())
+}
+
+fn flatten_tree(
+    tree: &OwnerTree,
+    prefix: &str,
+    is_last: Option<bool>,
+    items: &mut Vec<(String, EvaluatedResource)>,
+) {
+    let (branch, child_prefix) = match is_last {
+        None => ("", String::new()),
+        Some(true) => ("└─ ", format!("{prefix}   ")),
+        Some(false) => ("├─ ", format!("{prefix}│  ")),
+    };
+    let label = format!("{prefix}{branch}{}", build_label(&tree.resource.resource));
+    items.push((label, tree.resource.clone()));
+
+    for (index, child) in tree.children.iter().enumerate() {
+        let is_last = index + 1 == tree.children.len();
+        flatten_tree(child, &child_prefix, Some(is_last), items);
+    }
+}
+
+/// Replaces the tree items keeping the resource with the given uid selected.
+pub(crate) fn render_owner_tree(
+    sv: &mut SelectView<EvaluatedResource>,
+    tree: &OwnerTree,
+    selected_uid: &str,
+) {
+    let mut items = vec![];
+    flatten_tree(tree, "", None, &mut items);
+
+    let selected = items
+        .iter()
+        .position(|(_, resource)| resource.resource.uid_or_name() == selected_uid)
+        .unwrap_or(0);
+
+    sv.clear();
+    sv.add_all(items);
+    // the selection callback is not needed: nothing listens to the selection changes
+    let _ = sv.set_selection(selected);
+}
+
+/// Re-renders the tree keeping the current selection, or the root resource if nothing is selected.
+pub(crate) fn refresh_owner_tree(
+    sv: &mut SelectView<EvaluatedResource>,
+    tree: &OwnerTree,
+    uid: &str,
+) {
+    let selected_uid = sv
+        .selection()
+        .map(|resource| resource.resource.uid_or_name())
+        .unwrap_or_else(|| uid.to_string());
+    render_owner_tree(sv, tree, &selected_uid);
+}
+
+pub(crate) fn build_owner_tree_view(
+    store: Arc<Mutex<UiStore>>,
+    resource: &ResourceView,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (resource_manager, to_ui_sender, counter) = store.locking(|mut store| {
+        Ok((
+            Arc::clone(&store.resource_manager),
+            store.inter_ui_sender.clone(),
+            store.inc_counter(),
+        ))
+    })?;
+    let uid = resource.uid_or_name();
+    let tree = resource_manager.read_sync()?.get_owner_tree(&uid);
+
+    let view_meta = ViewMeta::Tree {
+        id: counter,
+        gvk: resource.gvk(),
+        name: resource.name(),
+        uid: uid.clone(),
+    };
+
+    let mut sv = SelectView::new();
+    if let Some(tree) = tree {
+        render_owner_tree(&mut sv, &tree, &uid);
+    }
+
+    sv.set_on_submit(move |_, resource: &EvaluatedResource| {
+        to_ui_sender.send_unwrap(InterUiSignal::ShowDetails(resource.resource.clone()));
+    });
+
+    let dialog = Dialog::around(Panel::new(
+        sv.with_name(view_meta.get_unique_name())
+            .scrollable()
+            .min_width(60),
+    ))
+    .title(view_meta.title());
+
+    Ok(ViewWithMeta {
+        inner: Box::new(dialog),
+        meta: Arc::new(RwLock::new(view_meta)),
+    })
+}
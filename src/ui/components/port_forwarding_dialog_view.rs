@@ -5,10 +5,10 @@ use cursive::reexports::log::error;
 use cursive::traits::Nameable;
 use cursive::views::{Dialog, EditView, LinearLayout, Panel};
 use cursive::Cursive;
-use k8s_openapi::api::core::v1::{Container, Pod};
-use kube::ResourceExt;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Container, Pod, Service};
 
-use crate::model::port_forward_request::PortForwardRequest;
+use crate::model::port_forward_request::{PortForwardRequest, PortForwardSpec, PortForwardTarget};
 use crate::reexports::sync::Mutex;
 use crate::traits::ext::cloning_callback::CloningCallbackExt;
 use crate::traits::ext::mutex::MutexExt;
@@ -35,34 +35,58 @@ fn get_first_pod_port(pod: &Pod) -> Option<u16> {
         .next()
 }
 
+pub(crate) fn get_default_pod_port(pod: &Pod, container: &Container) -> u16 {
+    get_first_container_port(container)
+        .or_else(|| get_first_pod_port(pod))
+        .unwrap_or(80)
+}
+
+pub(crate) fn get_default_service_port(service: &Service) -> u16 {
+    service
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.ports.as_ref())
+        .and_then(|ports| ports.first())
+        .map(|port| port.port as u16)
+        .unwrap_or(80)
+}
+
+pub(crate) fn get_default_deployment_port(deployment: &Deployment) -> u16 {
+    deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.spec.as_ref())
+        .into_iter()
+        .flat_map(|spec| spec.containers.iter())
+        .find_map(get_first_container_port)
+        .unwrap_or(80)
+}
+
 pub(crate) fn build_port_forwarding_dialog_view(
-    pod: &Pod,
-    container: &Container,
+    namespace: String,
+    target: PortForwardTarget,
+    default_remote_port: u16,
+    title: String,
     store: Arc<Mutex<UiStore>>,
 ) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
-    let default_pod_port = get_first_container_port(container)
-        .or_else(|| get_first_pod_port(pod))
-        .unwrap_or(80);
-
-    let default_host_port = default_pod_port.saturating_add(10000);
+    let default_host_port = default_remote_port.saturating_add(10000);
 
     let (to_backend_sender, counter) =
         store.locking(|mut store| Ok((store.to_backend_sender.clone(), store.inc_counter())))?;
 
     let view_meta = ViewMeta::Dialog {
         id: counter,
-        name: format!("Port Forward for {}", pod.name_any()),
+        name: format!("Port Forward for {target}"),
     };
 
     let host_port_edit_name = view_meta.get_edit_name("host_port");
-    let pod_port_edit_name = view_meta.get_edit_name("pod_port");
+    let remote_port_edit_name = view_meta.get_edit_name("remote_port");
     let host_edit_name = view_meta.get_edit_name("host");
 
     let submit = {
-        let namespace = pod.namespace().unwrap_or_default();
-        let pod_name = pod.name_any();
+        let target = target.clone();
         let host_port_edit_name = host_port_edit_name.clone();
-        let pod_port_edit_name = pod_port_edit_name.clone();
+        let remote_port_edit_name = remote_port_edit_name.clone();
         let host_edit_name = host_edit_name.clone();
 
         Arc::new(move |siv: &mut Cursive| {
@@ -90,17 +114,17 @@ pub(crate) fn build_port_forwarding_dialog_view(
                 return;
             }
 
-            let pod_port = siv
-                .call_on_name(&pod_port_edit_name, |view: &mut EditView| {
+            let remote_port = siv
+                .call_on_name(&remote_port_edit_name, |view: &mut EditView| {
                     view.get_content()
                 })
                 .unwrap_or_log()
                 .parse::<u16>();
 
-            let pod_port = if let Ok(pod_port) = pod_port {
-                pod_port
+            let remote_port = if let Ok(remote_port) = remote_port {
+                remote_port
             } else {
-                error!("Invalid pod port: {:?}", pod_port);
+                error!("Invalid remote port: {:?}", remote_port);
                 return;
             };
 
@@ -108,17 +132,14 @@ pub(crate) fn build_port_forwarding_dialog_view(
                 .call_on_name(&host_edit_name, |view: &mut EditView| view.get_content())
                 .unwrap_or_log();
 
-            let namespace = namespace.clone();
-            let pod_name = pod_name.clone();
-
-            let request = PortForwardRequest {
-                id: counter,
-                namespace,
-                pod_name,
+            let spec = PortForwardSpec {
+                namespace: namespace.clone(),
+                target: target.clone(),
                 host_port,
-                pod_port,
+                remote_port,
                 host: host.as_ref().to_string(),
             };
+            let request = PortForwardRequest::from_spec(counter, spec);
 
             to_backend_sender
                 .send(ToBackendSignal::PortForward(Arc::new(request)))
@@ -137,11 +158,11 @@ pub(crate) fn build_port_forwarding_dialog_view(
             .with_name(&host_port_edit_name)
     });
 
-    let pod_port_edit_view = submit.cloning(|submit| {
+    let remote_port_edit_view = submit.cloning(|submit| {
         EditView::new()
-            .content(format!("{default_pod_port}"))
+            .content(format!("{default_remote_port}"))
             .on_submit(move |siv, _| submit(siv))
-            .with_name(&pod_port_edit_name)
+            .with_name(&remote_port_edit_name)
     });
 
     let host_edit_view = submit.cloning(|submit| {
@@ -153,15 +174,15 @@ pub(crate) fn build_port_forwarding_dialog_view(
 
     let mut main_layout = LinearLayout::new(Orientation::Vertical);
     main_layout.add_child(Panel::new(host_port_edit_view).title("Host Port"));
-    main_layout.add_child(Panel::new(pod_port_edit_view).title("Pod Port"));
+    let remote_port_title = match target {
+        PortForwardTarget::Service(_) => "Service Port",
+        _ => "Pod Port",
+    };
+    main_layout.add_child(Panel::new(remote_port_edit_view).title(remote_port_title));
     main_layout.add_child(Panel::new(host_edit_view).title("Host"));
 
     let panel = Dialog::around(main_layout)
-        .title(format!(
-            "Port Forwarding: {}/{}",
-            pod.name_any(),
-            container.name
-        ))
+        .title(format!("Port Forwarding: {title}"))
         .button("Forward", move |siv| {
             submit(siv);
         });
@@ -1,48 +1,82 @@
 use std::sync::Arc;
 
 use cursive::direction::Orientation;
+use cursive::reexports::log::error;
 use cursive::traits::Nameable;
-use cursive::views::{Dialog, LinearLayout, SelectView};
+use cursive::views::{Dialog, EditView, LinearLayout, Panel, SelectView};
+use cursive::Cursive;
 
 use crate::model::port_forward_request::PortForwardRequest;
 use crate::reexports::sync::Mutex;
 use crate::traits::ext::kanal_sender::KanalSenderExt;
 use crate::traits::ext::mutex::MutexExt;
-use crate::ui::signals::ToBackendSignal;
+use crate::ui::signals::{InterUiSignal, ToBackendSignal};
 use crate::ui::ui_store::UiStore;
 use crate::ui::view_meta::ViewMeta;
+use crate::util::panics::OptionExt;
 use crate::util::view_with_data::ViewWithMeta;
 
+pub(crate) const PORT_FORWARDING_DIALOG_NAME: &str = "Port Forwarding List";
+
+fn build_title(request: &PortForwardRequest) -> String {
+    format!(
+        "{}:{} -> {}:{} [{}]",
+        request.host,
+        request.host_port,
+        request.target,
+        request.remote_port,
+        request.stats.summary()
+    )
+}
+
+/// Replaces the items keeping the selected request selected.
+pub(crate) fn fill_items(
+    sv: &mut SelectView<Arc<PortForwardRequest>>,
+    pf_requests: Vec<Arc<PortForwardRequest>>,
+) {
+    let selected_id = sv.selection().map(|request| request.id);
+    sv.clear();
+    for request in pf_requests {
+        sv.add_item(build_title(&request), request);
+    }
+    if let Some(index) = sv
+        .iter()
+        .position(|(_, request)| Some(request.id) == selected_id)
+    {
+        // nothing listens to the selection changes
+        let _ = sv.set_selection(index);
+    }
+}
+
 pub(crate) fn build_port_forwarding_view(
     store: Arc<Mutex<UiStore>>,
 ) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
-    let (pf_requests, to_backend_sender, counter) = store.locking(|mut store| {
+    let (pf_requests, to_backend_sender, to_ui_sender, counter) = store.locking(|mut store| {
         Ok((
             store.pf_requests.clone(),
             store.to_backend_sender.clone(),
+            store.inter_ui_sender.clone(),
             store.inc_counter(),
         ))
     })?;
 
     let view_meta = ViewMeta::Dialog {
         id: counter,
-        name: "Port Forwarding List".to_string(),
+        name: PORT_FORWARDING_DIALOG_NAME.to_string(),
     };
 
     let mut main_layout = LinearLayout::new(Orientation::Vertical);
 
     let mut sv = SelectView::new();
-    for request in pf_requests {
-        let title = format!(
-            "{}:{} -> {}:{}",
-            request.host, request.host_port, request.pod_name, request.pod_port
-        );
-        sv.add_item(title, request);
-    }
+    fill_items(&mut sv, pf_requests);
 
     let sv_name = view_meta.get_unique_name();
-    sv.set_on_submit(
-        move |siv: &mut cursive::Cursive, request: &Arc<PortForwardRequest>| {
+    let profile_edit_name = view_meta.get_edit_name("profile");
+
+    sv.set_on_submit({
+        let store = Arc::clone(&store);
+        let sv_name = sv_name.clone();
+        move |siv: &mut Cursive, request: &Arc<PortForwardRequest>| {
             to_backend_sender.send_unwrap(ToBackendSignal::StopForwarding(Arc::clone(request)));
             let mut store = store.lock_unwrap();
             store.pf_requests.retain(|req| req.id != request.id);
@@ -56,11 +90,35 @@ pub(crate) fn build_port_forwarding_view(
                     };
                 sv.remove_item(index);
             });
-        },
-    );
+        }
+    });
+
+    main_layout.add_child(sv.with_name(&sv_name));
+    main_layout
+        .add_child(Panel::new(EditView::new().with_name(&profile_edit_name)).title("Profile name"));
 
-    main_layout.add_child(sv.with_name(view_meta.get_unique_name()));
+    let save_profile = move |siv: &mut Cursive| {
+        let name = siv
+            .call_on_name(&profile_edit_name, |view: &mut EditView| view.get_content())
+            .unwrap_or_log();
+        let name = name.trim();
+        if name.is_empty() {
+            error!("Profile name is empty");
+            return;
+        }
+        to_ui_sender.send_unwrap(InterUiSignal::SavePortForwardProfile(name.to_string()));
+    };
+
+    let refresh = move |siv: &mut Cursive| {
+        let pf_requests = store.lock_unwrap().pf_requests.clone();
+        siv.call_on_name(&sv_name, |sv: &mut SelectView<Arc<PortForwardRequest>>| {
+            fill_items(sv, pf_requests);
+        });
+    };
 
-    let panel = Dialog::around(main_layout).title("Port Forwarding List");
+    let panel = Dialog::around(main_layout)
+        .title("Port Forwarding List")
+        .button("Refresh", refresh)
+        .button("Save as profile", save_profile);
     Ok(ViewWithMeta::new(panel, view_meta))
 }
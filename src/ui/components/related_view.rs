@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use cursive::traits::{Nameable, Resizable, Scrollable};
+use cursive::views::{Dialog, Panel, SelectView};
+
+use crate::model::related::RelatedResource;
+use crate::model::resource::resource_view::ResourceView;
+use crate::reexports::sync::{Mutex, RwLock};
+use crate::traits::ext::gvk::{GvkExt, GvkNameExt};
+use crate::traits::ext::kanal_sender::KanalSenderExt;
+use crate::traits::ext::mutex::MutexExt;
+use crate::ui::components::gvk_switcher::build_gvk_show_chain;
+use crate::ui::signals::InterUiSignal;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::view_with_data::ViewWithMeta;
+
+fn build_label(related: &RelatedResource) -> String {
+    let kind = &related.gvk.kind;
+    let resource = match &related.resource {
+        Some(resource) => &resource.resource,
+        None => return format!("{}: {kind} (not loaded, open the list)", related.relation),
+    };
+    let namespace = resource.namespace();
+    let name = if namespace.is_empty() {
+        resource.name()
+    } else {
+        format!("{namespace}/{}", resource.name())
+    };
+    format!("{}: {kind} {name}  {}", related.relation, resource.status())
+}
+
+pub(crate) fn build_related_view(
+    store: Arc<Mutex<UiStore>>,
+    resource: &ResourceView,
+    related: Vec<RelatedResource>,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (to_ui_sender, to_backend_sender, counter) = store.locking(|mut store| {
+        Ok((
+            store.inter_ui_sender.clone(),
+            store.to_backend_sender.clone(),
+            store.inc_counter(),
+        ))
+    })?;
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: format!(
+            "Related to {} {}",
+            resource.gvk().full_name(),
+            resource.name()
+        ),
+    };
+
+    let mut sv = SelectView::new();
+    for related in related {
+        sv.add_item(build_label(&related), related);
+    }
+
+    sv.set_on_submit(move |_, related: &RelatedResource| {
+        let signal = match &related.resource {
+            Some(resource) => InterUiSignal::ShowDetails(resource.resource.clone()),
+            None => build_gvk_show_chain(to_backend_sender.clone(), &related.gvk),
+        };
+        to_ui_sender.send_unwrap(signal);
+    });
+
+    let dialog = Dialog::around(Panel::new(
+        sv.with_name(view_meta.get_unique_name())
+            .scrollable()
+            .min_width(50),
+    ))
+    .title(view_meta.title());
+
+    Ok(ViewWithMeta {
+        inner: Box::new(dialog),
+        meta: Arc::new(RwLock::new(view_meta)),
+    })
+}
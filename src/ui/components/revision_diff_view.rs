@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, LinearLayout, Panel, SelectView};
+use cursive::Cursive;
+use cursive_cached_text_view::CachedTextView;
+
+use crate::model::resource::resource_view::ResourceView;
+use crate::model::traits::SerializeExt;
+use crate::reexports::sync::Mutex;
+use crate::traits::ext::mutex::MutexExt;
+use crate::traits::ext::rw_lock::RwLockExt;
+use crate::ui::highlighter::Highlighter;
+use crate::ui::resource_manager::ResourceRevision;
+use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::ViewMeta;
+use crate::util::diff::{strip_noisy_fields, unified_diff};
+use crate::util::panics::ResultExt;
+use crate::util::ui::ago;
+use crate::util::view_with_data::ViewWithMeta;
+
+const DIFF_CONTEXT_LINES: usize = 3;
+
+fn build_title(revision: &ResourceRevision) -> String {
+    let age = ago(Utc::now().signed_duration_since(revision.observed_at));
+    format!("{} ({age} ago)", revision.resource_version)
+}
+
+fn render_diff(
+    highlighter: &Highlighter,
+    from: &ResourceRevision,
+    to: &ResourceRevision,
+) -> anyhow::Result<StyledString> {
+    let from_yaml = strip_noisy_fields(&from.resource.to_yaml()?)?;
+    let to_yaml = strip_noisy_fields(&to.resource.to_yaml()?)?;
+    let diff = unified_diff(
+        &from_yaml,
+        &to_yaml,
+        &from.resource_version,
+        &to.resource_version,
+        DIFF_CONTEXT_LINES,
+    );
+
+    if diff.is_empty() {
+        return Ok(StyledString::plain("No changes"));
+    }
+    highlighter.highlight(&diff, "diff")
+}
+
+/// Renders the diff of the selected revisions again, a view without revision selects is
+/// left as is.
+pub(crate) fn update_revision_diff(
+    siv: &mut Cursive,
+    highlighter: &Highlighter,
+    view_meta: &ViewMeta,
+) {
+    update_diff(
+        siv,
+        highlighter,
+        &view_meta.get_select_name("from"),
+        &view_meta.get_select_name("to"),
+        &view_meta.get_unique_name(),
+    );
+}
+
+fn update_diff(
+    siv: &mut Cursive,
+    highlighter: &Highlighter,
+    from_name: &str,
+    to_name: &str,
+    text_name: &str,
+) {
+    let from = siv
+        .call_on_name(from_name, |sv: &mut SelectView<ResourceRevision>| {
+            sv.selection()
+        })
+        .flatten();
+    let to = siv
+        .call_on_name(to_name, |sv: &mut SelectView<ResourceRevision>| {
+            sv.selection()
+        })
+        .flatten();
+
+    let (from, to) = match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return,
+    };
+
+    let content = render_diff(highlighter, &from, &to).unwrap_or_log();
+    siv.call_on_name(text_name, move |tv: &mut CachedTextView| {
+        tv.set_content(content);
+    });
+}
+
+pub(crate) fn build_revision_diff_view(
+    store: Arc<Mutex<UiStore>>,
+    resource: &ResourceView,
+) -> anyhow::Result<ViewWithMeta<ViewMeta>> {
+    let (resource_manager, highlighter, counter) = store.locking(|mut store| {
+        Ok((
+            Arc::clone(&store.resource_manager),
+            Arc::clone(&store.highlighter),
+            store.inc_counter(),
+        ))
+    })?;
+    let revisions = resource_manager
+        .read_sync()?
+        .get_revisions(&resource.uid_or_name());
+
+    let view_meta = ViewMeta::Dialog {
+        id: counter,
+        name: format!("Revisions {}", resource.full_unique_name()),
+    };
+    let from_name = view_meta.get_select_name("from");
+    let to_name = view_meta.get_select_name("to");
+    let text_name = view_meta.get_unique_name();
+
+    let mut from_sv = SelectView::new().popup();
+    let mut to_sv = SelectView::new().popup();
+    for revision in revisions.iter() {
+        from_sv.add_item(build_title(revision), revision.clone());
+        to_sv.add_item(build_title(revision), revision.clone());
+    }
+    // compare the previous revision to the latest one by default
+    let from_sv = from_sv.selected(revisions.len().saturating_sub(2));
+    let to_sv = to_sv.selected(revisions.len().saturating_sub(1));
+
+    let initial = match (
+        revisions
+            .len()
+            .checked_sub(2)
+            .and_then(|i| revisions.get(i)),
+        revisions.last(),
+    ) {
+        (Some(from), Some(to)) => render_diff(&highlighter, from, to)?,
+        _ => StyledString::plain("Not enough revisions recorded yet"),
+    };
+
+    let on_select = {
+        let (from_name, to_name, text_name) =
+            (from_name.clone(), to_name.clone(), text_name.clone());
+        // the highlighter of the current theme
+        move |siv: &mut Cursive, _: &ResourceRevision| {
+            let highlighter = Arc::clone(&store.lock_unwrap().highlighter);
+            update_diff(siv, &highlighter, &from_name, &to_name, &text_name);
+        }
+    };
+    let from_sv = from_sv.on_submit(on_select.clone());
+    let to_sv = to_sv.on_submit(on_select);
+
+    let mut selectors = LinearLayout::horizontal();
+    selectors.add_child(Panel::new(from_sv.with_name(from_name)).title("From"));
+    selectors.add_child(Panel::new(to_sv.with_name(to_name)).title("To"));
+
+    let tv = CachedTextView::new(initial, 5)
+        .with_name(text_name)
+        .full_screen()
+        .scrollable();
+
+    let mut main_layout = LinearLayout::vertical();
+    main_layout.add_child(selectors);
+    main_layout.add_child(tv);
+
+    let dialog = Dialog::around(main_layout).title(view_meta.title());
+
+    Ok(ViewWithMeta::new(dialog, view_meta))
+}
@@ -1,14 +1,17 @@
 use std::sync::Arc;
 
+use cursive::reexports::log::warn;
 use handlebars::Handlebars;
 use handlebars::{Context, Helper, HelperResult, JsonRender, Output, RenderContext};
 use itertools::Itertools;
 use percent_encoding::{percent_encode, NON_ALPHANUMERIC};
 use rhai::Engine;
 
+use crate::config::detail_templates::DetailTemplates;
 use crate::config::extractor::{DetailsTemplate, ExtractorConfig};
+use crate::eval::engine_factory::start_evaluation;
+use crate::eval::rhai_objects::SharedRhaiObjects;
 use crate::model::resource::resource_view::ResourceView;
-use crate::model::traits::SerializeExt;
 use crate::traits::ext::gvk::{GvkExt, GvkNameExt};
 use crate::util::error::{LogError, LogErrorOptionExt, LogErrorResultExt};
 use crate::util::panics::{OptionExt, ResultExt};
@@ -18,16 +21,22 @@ use crate::util::watcher::LazyWatcher;
 pub(crate) struct DetailViewRenderer {
     engine_watcher: Arc<LazyWatcher<Engine>>,
     extractor_config_watcher: Arc<LazyWatcher<ExtractorConfig>>,
+    detail_templates_watcher: Arc<LazyWatcher<DetailTemplates>>,
+    rhai_objects: SharedRhaiObjects,
 }
 
 impl DetailViewRenderer {
     pub(crate) fn new(
         engine_watcher: &Arc<LazyWatcher<Engine>>,
         extractor_config_watcher: &Arc<LazyWatcher<ExtractorConfig>>,
+        detail_templates_watcher: &Arc<LazyWatcher<DetailTemplates>>,
+        rhai_objects: &SharedRhaiObjects,
     ) -> Self {
         Self {
             engine_watcher: Arc::clone(engine_watcher),
             extractor_config_watcher: Arc::clone(extractor_config_watcher),
+            detail_templates_watcher: Arc::clone(detail_templates_watcher),
+            rhai_objects: Arc::clone(rhai_objects),
         }
     }
 
@@ -41,22 +50,18 @@ impl DetailViewRenderer {
             .get(&gvk)
             .to_log_error(|| format!("A template for GVK {gvk_full_name} is not registered"))?;
 
-        let json = resource.to_json().to_log_error(|err| {
+        let var = self.rhai_objects.get(resource).to_log_error(|err| {
             format!(
-                "Failed serialize resource {} to json: {err}",
+                "Failed to convert resource {} into a rhai map: {err}",
                 resource.full_unique_name()
             )
         })?;
         let engine = self.engine_watcher.build();
-        let var = engine.parse_json(json, true).to_log_error(|err| {
-            format!(
-                "Failed to convert a json into a rhai map for resource {}: {err}",
-                resource.full_unique_name()
-            )
-        })?;
 
         let hbs = self.setup_hbs(engine, &resource.gvk().full_name(), details_template)?;
 
+        // rhai helpers are timed out as a part of the template
+        let _evaluation = start_evaluation();
         let html = hbs
             .render(&resource.gvk().full_name(), &var)
             .to_log_error(|err| {
@@ -77,28 +82,49 @@ impl DetailViewRenderer {
         let mut hbs = build_handlebars();
         hbs.set_engine(engine);
 
-        if let Some(parent) = details_template.template.parent() {
-            hbs.register_templates_directory(".hbs", parent)
-                .to_log_error(|err| {
-                    format!(
-                        "Failed to register templates directory {}: {err}",
-                        parent.display()
-                    )
-                })?;
+        let detail_templates = self.detail_templates_watcher.value();
+        let template_path = detail_templates.resolve_template(details_template);
+
+        // partials next to a template outside of the detail template dirs, overridden by the
+        // ones of the dirs; a broken partial only fails the templates that include it
+        if let Some(parent) = template_path.parent() {
+            if let Err(err) = hbs.register_templates_directory(".hbs", parent) {
+                warn!(
+                    "Failed to register templates directory {}: {err}",
+                    parent.display()
+                );
+            }
+        }
+        for (name, path) in detail_templates.templates() {
+            // already listed in the config errors
+            if detail_templates.is_broken(path) {
+                continue;
+            }
+            if let Err(err) = hbs.register_template_file(name, path) {
+                warn!(
+                    "Failed to register a partial {name} at {}: {err}",
+                    path.display()
+                );
+            }
         }
-        hbs.register_template_file(gvk_full_name, &details_template.template)
+        hbs.register_template_file(gvk_full_name, &template_path)
             .to_log_error(|err| {
                 format!(
                     "Failed to import a template for {gvk_full_name} at {}: {}",
-                    details_template.template.display(),
+                    template_path.display(),
                     err
                 )
             })?;
 
         for helper in &details_template.helpers {
-            hbs.register_script_helper_file(&helper.name, &helper.path)
+            let helper_path = detail_templates.resolve(&template_path, &helper.path);
+            hbs.register_script_helper_file(&helper.name, &helper_path)
                 .to_log_error(|err| {
-                    format!("Failed to register a hbs helper: {helper:?}: {err}")
+                    format!(
+                        "Failed to register a hbs helper {} at {}: {err}",
+                        helper.name,
+                        helper_path.display()
+                    )
                 })?;
         }
 
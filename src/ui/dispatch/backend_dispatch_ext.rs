@@ -1,8 +1,10 @@
 use std::ops::Deref;
 use std::sync::Arc;
 
+use crate::model::metrics::MetricsSnapshot;
 use crate::model::port_forward_request::PortForwardRequest;
 use cursive::reexports::log::{info, warn};
+use cursive::views::SelectView;
 use cursive_cached_text_view::CachedTextView;
 use cursive_markup::html::RichRenderer;
 use cursive_markup::MarkupView;
@@ -10,12 +12,15 @@ use cursive_table_view::TableView;
 use kube::api::GroupVersionKind;
 
 use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
+use crate::model::resource_event::ResourceEvent;
 use crate::reexports::sync::RwLock;
 use crate::traits::ext::gvk::GvkExt;
 use crate::traits::ext::mutex::MutexExt;
 use crate::traits::ext::rw_lock::RwLockExt;
 use crate::traits::ext::table_view::TableViewExt;
+use crate::ui::components::events_view::render_events;
 use crate::ui::components::menu::build_menu;
+use crate::ui::components::owner_tree_view::refresh_owner_tree;
 use crate::ui::dispatch::send_helper_ext::DispatchContextSendHelperExt;
 use crate::ui::dispatcher::DispatchContext;
 use crate::ui::signals::{FromBackendSignal, ToBackendSignal};
@@ -27,6 +32,7 @@ pub(crate) trait DispatchContextBackendExt {
     fn dispatch_response_discovered_gvks(self, gvks: Vec<GroupVersionKind>) -> anyhow::Result<()>;
     fn dispatch_response_resource_updated(self, resource: ResourceView) -> anyhow::Result<()>;
     fn dispatch_response_resource_deleted(self, resource: ResourceView) -> anyhow::Result<()>;
+    fn dispatch_response_metrics_updated(self, snapshot: MetricsSnapshot) -> anyhow::Result<()>;
     fn dispatch_response_log_data(
         self,
         view_id: usize,
@@ -34,6 +40,41 @@ pub(crate) trait DispatchContextBackendExt {
         seq_id: usize,
     ) -> anyhow::Result<()>;
     fn refresh_all(&self, evaluated_resource: EvaluatedResource) -> anyhow::Result<()>;
+    fn refresh_events(&self, resource: &ResourceView) -> anyhow::Result<()>;
+    fn refresh_owner_trees(&self, resource: &ResourceView) -> anyhow::Result<()>;
+    fn refresh_owner_trees(&self, resource: &ResourceView) -> anyhow::Result<()> {
+        // events are never part of a tree and arrive far more often than anything else
+        if ResourceEvent::from_resource(resource).is_some() {
+            return Ok(());
+        }
+
+        let (trees, resource_manager) = self.data.locking(|store| {
+            Ok((
+                store.view_stack.find_trees(),
+                Arc::clone(&store.resource_manager),
+            ))
+        })?;
+
+        for view in trees {
+            let (name, uid) = {
+                let view_meta = view.read_sync()?;
+                match view_meta.deref() {
+                    ViewMeta::Tree { uid, .. } => (view_meta.get_unique_name(), uid.clone()),
+                    _ => continue,
+                }
+            };
+            let tree = match resource_manager.read_sync()?.get_owner_tree(&uid) {
+                Some(tree) => tree,
+                None => continue,
+            };
+            self.call_on_name(&name, move |sv: &mut SelectView<EvaluatedResource>| {
+                refresh_owner_tree(sv, &tree, &uid);
+            });
+        }
+
+        Ok(())
+    }
+
     fn refresh_single_code_view(
         &self,
         evaluated_resource: EvaluatedResource,
@@ -79,7 +120,10 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
         );
 
         let all_resources = self.data.locking(|store| {
-            let (resource, mut pseudo) = store.resource_manager.write_sync()?.replace(resource);
+            let (resource, mut pseudo) = store
+                .resource_manager
+                .write_sync()?
+                .replace(resource.clone());
             pseudo.push(resource);
             Ok(pseudo)
         })?;
@@ -88,7 +132,8 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
             let _ = self.refresh_all(evaluated_resource);
         }
 
-        Ok(())
+        self.refresh_owner_trees(&resource)?;
+        self.refresh_events(&resource)
     }
 
     fn dispatch_response_resource_deleted(self, resource: ResourceView) -> anyhow::Result<()> {
@@ -107,6 +152,31 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
             );
         }
 
+        self.refresh_owner_trees(&resource)?;
+        self.refresh_events(&resource)
+    }
+
+    fn dispatch_response_metrics_updated(self, snapshot: MetricsSnapshot) -> anyhow::Result<()> {
+        // the store is not locked and the resource manager is only write-locked to swap the
+        // values, the scripts run under a read lock
+        let resource_manager = self
+            .data
+            .locking(|store| Ok(Arc::clone(&store.resource_manager)))?;
+        let outdated_by_gvk = resource_manager.write_sync()?.replace_metrics(snapshot);
+
+        for (gvk, outdated) in outdated_by_gvk {
+            let evaluated = resource_manager
+                .read_sync()?
+                .evaluate_metrics_columns(&gvk, outdated);
+            let updated_resources = resource_manager
+                .write_sync()?
+                .apply_metrics_columns(evaluated);
+
+            for evaluated_resource in updated_resources {
+                let _ = self.refresh_all(evaluated_resource);
+            }
+        }
+
         Ok(())
     }
 
@@ -174,6 +244,7 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
                     })
                 }
                 ViewMeta::Dialog { .. } => continue,
+                ViewMeta::Tree { .. } => continue,
                 ViewMeta::WindowSwitcher { .. } => continue,
                 ViewMeta::GvkSwitcher { .. } => continue,
                 ViewMeta::Logs { .. } => continue,
@@ -185,6 +256,33 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
         Ok(())
     }
 
+    fn refresh_events(&self, resource: &ResourceView) -> anyhow::Result<()> {
+        let event = if let Some(event) = ResourceEvent::from_resource(resource) {
+            event
+        } else {
+            return Ok(());
+        };
+
+        let (affected_views, events) = self.data.locking(|store| {
+            let affected_views = store.view_stack.find_all_by_uid(&event.involved_uid);
+            let events = store
+                .resource_manager
+                .read_sync()?
+                .get_events(&event.involved_uid);
+            Ok((affected_views, events))
+        })?;
+
+        for view in affected_views {
+            let name = view.read_sync()?.get_events_name();
+            let styled = render_events(&events);
+            self.call_on_name(&name, move |tv: &mut CachedTextView| {
+                tv.set_content(styled);
+            });
+        }
+
+        Ok(())
+    }
+
     fn refresh_single_code_view(
         &self,
         evaluated_resource: EvaluatedResource,
@@ -193,10 +291,14 @@ impl<'a> DispatchContextBackendExt for DispatchContext<'a, UiStore, FromBackendS
         let resource = evaluated_resource.resource;
         let store = Arc::clone(&self.data);
 
-        let name = view_meta.read_sync()?.get_unique_name();
+        let (name, render_profile) = {
+            let view_meta = view_meta.read_sync()?;
+            let render_profile = view_meta.get_render_profile().unwrap_or_default();
+            (view_meta.get_unique_name(), render_profile.to_string())
+        };
 
         self.call_on_name(&name, move |tv: &mut CachedTextView| {
-            let styled = store.lock_unwrap().highlight(&resource)?;
+            let styled = store.lock_unwrap().highlight(&resource, &render_profile)?;
             tv.set_content(styled);
             Ok::<(), anyhow::Error>(())
         });
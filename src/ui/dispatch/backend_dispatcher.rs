@@ -26,6 +26,9 @@ impl Dispatch<UiStore, FromBackendSignal> for FromBackendSignal {
             FromBackendSignal::PortForwardingStarted(pf_request) => {
                 context.dispatch_port_forwarding_started(pf_request)
             }
+            FromBackendSignal::MetricsUpdated(snapshot) => {
+                context.dispatch_response_metrics_updated(snapshot)
+            }
         };
 
         log_signal_result(result, &signal_name);
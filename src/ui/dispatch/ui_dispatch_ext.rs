@@ -1,44 +1,71 @@
+use std::collections::HashSet;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use anyhow::bail;
-use cursive::reexports::log::{info, warn};
+use chrono::{DateTime, Utc};
+use cursive::reexports::log::{error, info, warn};
 use cursive::traits::Nameable;
-use cursive::views::Dialog;
+use cursive::view::ScrollStrategy;
+use cursive::views::{Dialog, SelectView, TextView};
+use cursive_cached_text_view::CachedTextView;
 use cursive_flexi_logger_view::FlexiLoggerView;
 use cursive_table_view::TableView;
+use itertools::Itertools;
 use k8s_openapi::api::core::v1::{Container, Pod};
 use kube::api::GroupVersionKind;
 use kube::ResourceExt;
 
-use crate::config::extractor::ActionType;
-use crate::eval::engine_factory::build_engine;
+use crate::config::extractor::{ActionType, DefaultSort};
+use crate::config::port_forward_profiles::PortForwardProfiles;
+use crate::eval::rhai_objects::to_rhai_map;
+use crate::model::log_export_request::{LogExportRequest, LogExportTarget};
+use crate::model::port_forward_request::{PortForwardRequest, PortForwardTarget};
 use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
+use crate::model::resource_event::ResourceEvent;
 use crate::model::traits::SerializeExt;
+use crate::reexports::k8s::{Job, ReplicaSet};
 use crate::reexports::sync::RwLock;
+use crate::theme::load_theme;
 use crate::traits::ext::cursive::SivUtilExt;
 use crate::traits::ext::gvk::GvkExt;
 use crate::traits::ext::gvk::GvkNameExt;
+use crate::traits::ext::gvk::GvkStaticExt;
 use crate::traits::ext::mutex::MutexExt;
 use crate::traits::ext::pod::PodExt;
 use crate::traits::ext::rw_lock::RwLockExt;
 use crate::ui::components::code_view::build_code_view;
+use crate::ui::components::config_errors_view::{
+    build_config_errors_view, render_config_errors, CONFIG_ERRORS_DIALOG_NAME,
+};
+use crate::ui::components::deleted_history_view::build_deleted_history_view;
 use crate::ui::components::detail_view::build_detail_view;
+use crate::ui::components::eval_profiler_view::build_eval_profiler_view;
 use crate::ui::components::gvk_list_view::build_gvk_list_view_layout;
 use crate::ui::components::gvk_switcher::build_gvk_switcher;
-use crate::ui::components::log_view::build_log_view;
-use crate::ui::components::port_forwarding_dialog_view::build_port_forwarding_dialog_view;
+use crate::ui::components::log_export_dialog_view::build_log_export_dialog_view;
+use crate::ui::components::log_view::{build_log_view, LogScrollView};
+use crate::ui::components::menu::build_menu;
+use crate::ui::components::owner_tree_view::{build_owner_tree_view, refresh_owner_tree};
+use crate::ui::components::port_forwarding_dialog_view::{
+    build_port_forwarding_dialog_view, get_default_deployment_port, get_default_pod_port,
+    get_default_service_port,
+};
 use crate::ui::components::port_forwarding_view::build_port_forwarding_view;
+use crate::ui::components::related_view::build_related_view;
+use crate::ui::components::revision_diff_view::{build_revision_diff_view, update_revision_diff};
 use crate::ui::components::window_switcher::build_window_switcher;
 use crate::ui::dispatch::send_helper_ext::DispatchContextSendHelperExt;
 use crate::ui::dispatcher::DispatchContext;
 use crate::ui::interactive_command::InteractiveCommand;
 use crate::ui::signals::{InterUiSignal, ToBackendSignal};
 use crate::ui::ui_store::{UiStore, UiStoreDispatcherExt};
-use crate::ui::view_meta::{ViewMeta, ViewMetaLogExt};
+use crate::ui::view_meta::{TimestampFormat, ViewMeta, ViewMetaLogExt};
 use crate::util::error::{LogError, LogErrorOptionExt, LogErrorResultExt};
 use crate::util::panics::ResultExt;
+use crate::util::ui::copy_to_clipboard;
 use crate::util::view_with_data::ViewWithMeta;
+use crate::util::yaml::YamlFile;
 
 pub(crate) trait DispatchContextUiExt {
     fn dispatch_update_list_views_for_gvk(
@@ -62,14 +89,46 @@ pub(crate) trait DispatchContextUiExt {
     ) -> anyhow::Result<()>;
     fn dispatch_show_port_forwarding_view(self) -> anyhow::Result<()>;
     fn dispatch_show_port_forwarding_dialog(self) -> anyhow::Result<()>;
+    fn dispatch_start_port_forward_profile(self, name: String) -> anyhow::Result<()>;
+    fn dispatch_save_port_forward_profile(self, name: String) -> anyhow::Result<()>;
     fn dispatch_logs_apply_previous(
         self,
         view_id: usize,
         show_previous: bool,
     ) -> anyhow::Result<()>;
+    fn dispatch_logs_apply_since_time(
+        self,
+        view_id: usize,
+        since_time: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()>;
+    fn dispatch_logs_apply_until_time(
+        self,
+        view_id: usize,
+        until_time: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()>;
+    fn dispatch_logs_apply_timestamp_format(
+        self,
+        view_id: usize,
+        format: TimestampFormat,
+    ) -> anyhow::Result<()>;
+    fn dispatch_logs_jump_to_time(
+        self,
+        view_id: usize,
+        instant: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()>;
+
+    fn dispatch_show_log_export_dialog(self) -> anyhow::Result<()>;
+    fn dispatch_code_apply_render_profile(self, view_id: usize, name: String)
+        -> anyhow::Result<()>;
+    fn dispatch_logs_export(self, request: LogExportRequest) -> anyhow::Result<()>;
 
     fn dispatch_apply_namespace_filter(self, id: usize, namespace: String) -> anyhow::Result<()>;
     fn dispatch_apply_name_filter(self, id: usize, name: String) -> anyhow::Result<()>;
+    fn dispatch_list_sort_changed(
+        self,
+        gvk: GroupVersionKind,
+        sort: DefaultSort,
+    ) -> anyhow::Result<()>;
 
     fn dispatch_show_details(self, resource: ResourceView) -> anyhow::Result<()>;
     fn dispatch_show_gvk(self, gvk: GroupVersionKind) -> anyhow::Result<()>;
@@ -81,6 +140,15 @@ pub(crate) trait DispatchContextUiExt {
     fn dispatch_show_window_switcher(self) -> anyhow::Result<()>;
     fn dispatch_ctrl_s(self) -> anyhow::Result<()>;
     fn dispatch_show_yaml(self) -> anyhow::Result<()>;
+    fn dispatch_show_resource_yaml(self, resource: ResourceView) -> anyhow::Result<()>;
+    fn dispatch_show_deleted_history(self) -> anyhow::Result<()>;
+    fn dispatch_show_revisions(self) -> anyhow::Result<()>;
+    fn dispatch_show_owner_tree(self) -> anyhow::Result<()>;
+    fn dispatch_show_related(self) -> anyhow::Result<()>;
+    fn dispatch_show_config_errors(self) -> anyhow::Result<()>;
+    fn dispatch_config_errors_changed(self) -> anyhow::Result<()>;
+    fn dispatch_switch_theme(self, name: String) -> anyhow::Result<()>;
+    fn dispatch_show_eval_profiler(self) -> anyhow::Result<()>;
     fn dispatch_dump_resource_sample(self) -> anyhow::Result<()>;
     fn dispatch_refresh(self) -> anyhow::Result<()>;
     fn dispatch_pop_view(self) -> anyhow::Result<()>;
@@ -93,6 +161,7 @@ pub(crate) trait DispatchContextUiExt {
     fn get_selected_resource(&self) -> Result<EvaluatedResource, anyhow::Error>;
     fn get_view_by_id(&self, id: usize) -> anyhow::Result<Arc<RwLock<ViewMeta>>>;
     fn send_log_subscribe(&self, view: Arc<RwLock<ViewMeta>>) -> anyhow::Result<()>;
+    fn get_resource_events(&self, resource: &ResourceView) -> anyhow::Result<Vec<ResourceEvent>>;
 }
 
 impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
@@ -191,10 +260,40 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
     }
 
     fn dispatch_show_port_forwarding_dialog(self) -> anyhow::Result<()> {
-        let (pod, container) = self.get_active_container()?;
+        let resource = self.get_selected_resource()?.resource;
+        let (namespace, target, default_remote_port, title) = match &resource {
+            ResourceView::Service(service) => (
+                resource.namespace(),
+                PortForwardTarget::Service(service.name_any()),
+                get_default_service_port(service),
+                format!("service {}", service.name_any()),
+            ),
+            ResourceView::Deployment(deployment) => (
+                resource.namespace(),
+                PortForwardTarget::Deployment(deployment.name_any()),
+                get_default_deployment_port(deployment),
+                format!("deployment {}", deployment.name_any()),
+            ),
+            _ => {
+                let (pod, container) = self.get_active_container()?;
+                (
+                    pod.namespace().unwrap_or_default(),
+                    PortForwardTarget::Pod(pod.name_any()),
+                    get_default_pod_port(&pod, &container),
+                    format!("{}/{}", pod.name_any(), container.name),
+                )
+            }
+        };
+
         let store = Arc::clone(&self.data);
         self.send_wait(move |siv| {
-            let view = build_port_forwarding_dialog_view(&pod, &container, Arc::clone(&store))?;
+            let view = build_port_forwarding_dialog_view(
+                namespace,
+                target,
+                default_remote_port,
+                title,
+                Arc::clone(&store),
+            )?;
             store.register_view(&view);
             siv.add_layer(view);
             Ok::<_, anyhow::Error>(())
@@ -203,6 +302,73 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
         Ok(())
     }
 
+    fn dispatch_start_port_forward_profile(self, name: String) -> anyhow::Result<()> {
+        let mut store = self.data.lock_sync()?;
+        let profiles = PortForwardProfiles::load(&store.pf_profiles_path)?;
+        let specs = profiles
+            .profiles
+            .get(&name)
+            .to_log_warn(|| format!("Port forward profile {name} not found"))?;
+
+        // the started requests are only listed once the backend reports them
+        let mut started_ports = HashSet::new();
+        for spec in specs {
+            if store
+                .pf_requests
+                .iter()
+                .any(|request| request.host_port == spec.host_port)
+                || !started_ports.insert(spec.host_port)
+            {
+                warn!(
+                    "Port {} is already in use, skipping {} from profile {name}",
+                    spec.host_port, spec.target
+                );
+                continue;
+            }
+
+            let request = PortForwardRequest::from_spec(store.inc_counter(), spec.clone());
+            store
+                .to_backend_sender
+                .send(ToBackendSignal::PortForward(Arc::new(request)))?;
+        }
+
+        info!("Started port forward profile {name}");
+        Ok(())
+    }
+
+    fn dispatch_save_port_forward_profile(self, name: String) -> anyhow::Result<()> {
+        let store = self.data.lock_sync()?;
+        let mut profiles = PortForwardProfiles::load(&store.pf_profiles_path)?;
+        let specs = store
+            .pf_requests
+            .iter()
+            .map(|request| request.to_spec())
+            .collect::<Vec<_>>();
+        if specs.is_empty() {
+            return LogError::log_warn("No active port forwards to save");
+        }
+
+        profiles.profiles.insert(name.clone(), specs);
+        profiles.save(&store.pf_profiles_path)?;
+
+        info!(
+            "Saved port forward profile {name} to {}",
+            store.pf_profiles_path.display()
+        );
+
+        // rebuild the menu to list the new profile
+        let gvks = store.gvks.clone();
+        drop(store);
+        let store = Arc::clone(&self.data);
+        self.send(move |siv| {
+            let mut menubar = build_menu(gvks, store);
+            menubar.autohide = false;
+            *siv.menubar() = menubar;
+        });
+
+        Ok(())
+    }
+
     fn dispatch_logs_apply_previous(
         self,
         view_id: usize,
@@ -213,6 +379,145 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
         self.send_log_subscribe(view)
     }
 
+    fn dispatch_logs_apply_since_time(
+        self,
+        view_id: usize,
+        since_time: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(view_id)?;
+        let should_resubscribe = view.write_sync()?.set_log_since_time(since_time);
+        if should_resubscribe {
+            self.send_log_subscribe(view)?;
+        }
+        Ok(())
+    }
+
+    fn dispatch_logs_apply_until_time(
+        self,
+        view_id: usize,
+        until_time: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(view_id)?;
+        view.write_sync()?.set_log_until_time(until_time);
+        Ok(())
+    }
+
+    fn dispatch_logs_apply_timestamp_format(
+        self,
+        view_id: usize,
+        format: TimestampFormat,
+    ) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(view_id)?;
+        view.write_sync()?.set_log_timestamp_format(format);
+        Ok(())
+    }
+
+    fn dispatch_logs_jump_to_time(
+        self,
+        view_id: usize,
+        instant: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(view_id)?;
+        let instant = match instant {
+            Some(instant) => instant,
+            None => {
+                // an empty jump goes back to following the new lines
+                let scroll_name = view.read_sync()?.get_scroll_name();
+                self.call_on_name(&scroll_name, |sv: &mut LogScrollView| {
+                    sv.set_scroll_strategy(ScrollStrategy::StickToBottom);
+                });
+                return Ok(());
+            }
+        };
+        let (scroll_name, row) = {
+            let view = view.read_sync()?;
+            let row = view
+                .get_log_row_offset(instant)
+                .to_log_warn(|| format!("No log lines after {instant}"))?;
+            (view.get_scroll_name(), row)
+        };
+
+        self.call_on_name(&scroll_name, move |sv: &mut LogScrollView| {
+            sv.set_scroll_strategy(ScrollStrategy::KeepRow);
+            sv.set_offset((0, row));
+        });
+
+        Ok(())
+    }
+
+    fn dispatch_show_log_export_dialog(self) -> anyhow::Result<()> {
+        let last_view = self
+            .data
+            .lock_sync()?
+            .view_stack
+            .last()
+            .to_log_warn(|| "No view is selected")?;
+
+        let log_view_id = match last_view.read_sync()?.deref() {
+            ViewMeta::Logs { id, .. } => *id,
+            _ => {
+                return LogError::log_warn("Log export is available only for log views");
+            }
+        };
+
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_log_export_dialog_view(log_view_id, Arc::clone(&store))?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_logs_export(self, request: LogExportRequest) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(request.view_id)?;
+        let range = match (&request.target, request.range) {
+            (LogExportTarget::Clipboard, None) => {
+                // copying defaults to the lines visible in the log view
+                let scroll_name = view.read_sync()?.get_scroll_name();
+                let rows = self.send_wait(move |siv| {
+                    siv.call_on_name(&scroll_name, |sv: &mut LogScrollView| {
+                        let viewport = sv.content_viewport();
+                        viewport.top()..viewport.top() + viewport.height()
+                    })
+                });
+                match rows {
+                    Some(rows) => view.read_sync()?.get_log_line_range(rows, request.as_shown),
+                    None => None,
+                }
+            }
+            (_, range) => range,
+        };
+        let lines = view.read_sync()?.get_log_lines(request.as_shown, range);
+
+        let mut data = lines.join("\n");
+        data.push('\n');
+
+        match request.target {
+            LogExportTarget::File(path) => {
+                std::fs::write(&path, data)?;
+                info!("Saved {} log lines to {}", lines.len(), path.display());
+            }
+            LogExportTarget::Clipboard => {
+                let num_lines = lines.len();
+                self.send(move |_| match copy_to_clipboard(&data) {
+                    Ok(_) => info!("Copied {num_lines} log lines to the clipboard"),
+                    Err(err) => error!("Failed to copy log lines to the clipboard: {err}"),
+                });
+            }
+            LogExportTarget::Pipe(command) => {
+                self.data.lock_sync()?.interactive_command = InteractiveCommand::Pipe {
+                    command,
+                    input: data,
+                }
+                .into();
+                self.send(|siv| siv.quit());
+            }
+        }
+
+        Ok(())
+    }
+
     fn dispatch_apply_namespace_filter(self, id: usize, namespace: String) -> anyhow::Result<()> {
         self.data.locking(|store| {
             store
@@ -246,6 +551,20 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
         Ok(())
     }
 
+    fn dispatch_list_sort_changed(
+        self,
+        gvk: GroupVersionKind,
+        sort: DefaultSort,
+    ) -> anyhow::Result<()> {
+        let mut store = self.data.lock_sync()?;
+        if store.sort_state.get(&gvk) == Some(&sort) {
+            return Ok(());
+        }
+        store.sort_state.set(&gvk, sort);
+        store.sort_state.save(&store.sort_state_path)?;
+        Ok(())
+    }
+
     fn dispatch_show_details(self, resource: ResourceView) -> anyhow::Result<()> {
         let gvk = resource.gvk();
 
@@ -270,10 +589,11 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
                 let html = store.detail_view_renderer.render_html(&resource)?;
 
                 drop(store);
+                let events = self.get_resource_events(&resource)?;
                 let store = Arc::clone(&self.data);
 
                 self.send(move |siv| {
-                    let view = build_detail_view(Arc::clone(&store), resource, html);
+                    let view = build_detail_view(Arc::clone(&store), resource, html, &events);
                     store.register_view(&view);
                     siv.add_fullscreen_layer(view);
                 });
@@ -284,7 +604,14 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
     }
 
     fn dispatch_show_gvk(self, gvk: GroupVersionKind) -> anyhow::Result<()> {
-        self.data.lock_sync()?.selected_gvk = gvk;
+        self.data.locking(|mut store| {
+            // the events columns are filled from the events cache
+            if store.resource_manager.read_sync()?.has_event_columns(&gvk) {
+                store.register_event_gvks()?;
+            }
+            store.selected_gvk = gvk;
+            Ok(())
+        })?;
         let store = Arc::clone(&self.data);
 
         self.send(move |siv| {
@@ -397,10 +724,15 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
 
     fn dispatch_show_yaml(self) -> anyhow::Result<()> {
         let resource = self.get_selected_resource()?.resource;
+        self.dispatch_show_resource_yaml(resource)
+    }
+
+    fn dispatch_show_resource_yaml(self, resource: ResourceView) -> anyhow::Result<()> {
+        let events = self.get_resource_events(&resource)?;
 
         let store = Arc::clone(&self.data);
         self.send_wait(move |siv| {
-            let view = build_code_view(Arc::clone(&store), resource)?;
+            let view = build_code_view(Arc::clone(&store), resource, &events)?;
             store.register_view(&view);
             siv.add_layer(view);
             Ok(())
@@ -410,9 +742,7 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
     fn dispatch_dump_resource_sample(self) -> anyhow::Result<()> {
         let resource = self.get_selected_resource()?;
 
-        let engine = build_engine(&[]);
-        let json = resource.resource.to_json()?;
-        let json = engine.parse_json(json, true)?;
+        let json = to_rhai_map(&resource.resource)?;
 
         let json = format!("#{:#?}", json);
 
@@ -443,6 +773,22 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
         let view_meta = last_view.read_unwrap();
         let gvk = match view_meta.deref() {
             ViewMeta::List { gvk, .. } => gvk.clone(),
+            ViewMeta::Tree { uid, .. } => {
+                let name = view_meta.get_unique_name();
+                let uid = uid.clone();
+                drop(view_meta);
+                let tree = self
+                    .data
+                    .lock_sync()?
+                    .resource_manager
+                    .read_sync()?
+                    .get_owner_tree(&uid)
+                    .to_log_warn(|| format!("Resource {uid} is gone"))?;
+                self.call_on_name(&name, move |sv: &mut SelectView<EvaluatedResource>| {
+                    refresh_owner_tree(sv, &tree, &uid);
+                });
+                return Ok(());
+            }
             _ => {
                 return LogError::log_error("F5 not implemented for the current view");
             }
@@ -454,6 +800,263 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
         Ok(())
     }
 
+    fn dispatch_code_apply_render_profile(
+        self,
+        view_id: usize,
+        name: String,
+    ) -> anyhow::Result<()> {
+        let view = self.get_view_by_id(view_id)?;
+        let (view_name, uid) = {
+            let mut view = view.write_sync()?;
+            view.set_render_profile(name.clone());
+            (view.get_unique_name(), view.get_uid().unwrap_or_default())
+        };
+
+        let resource = self
+            .data
+            .lock_sync()?
+            .resource_manager
+            .read_sync()?
+            .get_resource_by_uid(&uid)
+            .to_log_warn(|| format!("Resource {uid} is no longer available"))?
+            .resource;
+
+        let styled = self.data.lock_sync()?.highlight(&resource, &name)?;
+        self.call_on_name(&view_name, move |tv: &mut CachedTextView| {
+            tv.set_content(styled);
+        });
+
+        Ok(())
+    }
+
+    fn dispatch_show_deleted_history(self) -> anyhow::Result<()> {
+        let last_view = self
+            .data
+            .lock_sync()?
+            .view_stack
+            .last()
+            .to_log_warn(|| "No view is selected")?;
+
+        let view_meta = last_view.read_unwrap();
+        let gvk = match view_meta.deref() {
+            ViewMeta::List { gvk, .. } => gvk.clone(),
+            _ => {
+                return LogError::log_warn("Deleted resources are only available for list views");
+            }
+        };
+        drop(view_meta);
+
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_deleted_history_view(Arc::clone(&store), &gvk)?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_show_revisions(self) -> anyhow::Result<()> {
+        let resource = self.get_selected_resource()?.resource;
+        if !self
+            .data
+            .lock_sync()?
+            .resource_manager
+            .read_sync()?
+            .is_revision_history_enabled()
+        {
+            return LogError::log_warn(
+                "Revision history is disabled, set revision_history_size or --revision-history-size",
+            );
+        }
+
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_revision_diff_view(Arc::clone(&store), &resource)?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_show_owner_tree(self) -> anyhow::Result<()> {
+        let resource = self.get_selected_resource()?.resource;
+
+        // owners and the usual children are loaded only when there is a reflector for them;
+        // the tree is re-rendered as the resources arrive
+        let owner_gvks = resource.owner_references().iter().map(|owner| {
+            let (group, version) = owner
+                .api_version
+                .rsplit_once('/')
+                .unwrap_or(("", &owner.api_version));
+            GroupVersionKind::gvk(group, version, &owner.kind)
+        });
+        let child_gvks = [
+            ReplicaSet::gvk_for_type(),
+            Pod::gvk_for_type(),
+            Job::gvk_for_type(),
+        ];
+        let to_backend_sender = self.data.lock_sync()?.to_backend_sender.clone();
+        for gvk in owner_gvks.chain(child_gvks) {
+            to_backend_sender.send(ToBackendSignal::RegisterGvk(gvk))?;
+        }
+
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_owner_tree_view(Arc::clone(&store), &resource)?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_show_related(self) -> anyhow::Result<()> {
+        let resource = self.get_selected_resource()?.resource;
+
+        let (resource_manager, to_backend_sender) = self.data.locking(|store| {
+            Ok((
+                Arc::clone(&store.resource_manager),
+                store.to_backend_sender.clone(),
+            ))
+        })?;
+        let related = resource_manager.read_sync()?.get_related(&resource);
+        if related.is_empty() {
+            return LogError::log_warn(format!(
+                "No related resources are configured for {}",
+                resource.gvk().full_name()
+            ));
+        }
+
+        // selectors are resolved against the cached resources only; resources of the
+        // target GVKs arriving later are shown the next time the dialog is opened
+        let gvks = related.iter().map(|related| related.gvk.clone()).unique();
+        for gvk in gvks {
+            to_backend_sender.send(ToBackendSignal::RegisterGvk(gvk))?;
+        }
+
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_related_view(Arc::clone(&store), &resource, related)?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_show_config_errors(self) -> anyhow::Result<()> {
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_config_errors_view(Arc::clone(&store))?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_show_eval_profiler(self) -> anyhow::Result<()> {
+        let store = Arc::clone(&self.data);
+        self.send_wait(move |siv| {
+            let view = build_eval_profiler_view(Arc::clone(&store))?;
+            store.register_view(&view);
+            siv.add_layer(view);
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn dispatch_config_errors_changed(self) -> anyhow::Result<()> {
+        let (gvks, errors, dialogs) = self.data.locking(|store| {
+            Ok((
+                store.gvks.clone(),
+                Arc::clone(&store.config_errors),
+                store
+                    .view_stack
+                    .find_dialogs_by_name(CONFIG_ERRORS_DIALOG_NAME),
+            ))
+        })?;
+
+        for dialog in dialogs {
+            let name = dialog.read_unwrap().get_unique_name();
+            let errors = Arc::clone(&errors);
+            self.call_on_name(&name, move |tv: &mut TextView| {
+                tv.set_content(render_config_errors(&errors));
+            });
+        }
+
+        // the menubar shows the number of errors
+        let store = Arc::clone(&self.data);
+        self.send(move |siv| {
+            let mut menubar = build_menu(gvks, store);
+            menubar.autohide = false;
+            *siv.menubar() = menubar;
+        });
+
+        Ok(())
+    }
+
+    fn dispatch_switch_theme(self, name: String) -> anyhow::Result<()> {
+        let (theme_dirs, syntax_set) = self.data.locking(|store| {
+            Ok((
+                store.theme_dirs.clone(),
+                Arc::clone(store.highlighter.syntax_set()),
+            ))
+        })?;
+        let (theme, highlighter) = load_theme(&theme_dirs, &name, &syntax_set)?;
+        let highlighter = Arc::new(highlighter);
+
+        let (gvks, code_views, log_views, dialogs, resource_manager) =
+            self.data.locking(|mut store| {
+                store.highlighter = Arc::clone(&highlighter);
+                store.theme_name = name;
+                Ok((
+                    store.gvks.clone(),
+                    store.view_stack.find_code_views(),
+                    store.view_stack.find_logs(),
+                    store.view_stack.find_dialogs(),
+                    Arc::clone(&store.resource_manager),
+                ))
+            })?;
+
+        // the log updater highlights the logs again from the first line
+        for log_view in log_views {
+            log_view.write_sync()?.get_log_filter_clearing_mut();
+        }
+
+        for code_view in code_views {
+            let (view_name, uid, render_profile) = {
+                let view = code_view.read_sync()?;
+                (
+                    view.get_unique_name(),
+                    view.get_uid().unwrap_or_default(),
+                    view.get_render_profile().unwrap_or_default().to_string(),
+                )
+            };
+            let resource = match resource_manager.read_sync()?.get_resource_by_uid(&uid) {
+                Some(evaluated_resource) => evaluated_resource.resource,
+                None => continue,
+            };
+            let styled = self
+                .data
+                .lock_sync()?
+                .highlight(&resource, &render_profile)?;
+            self.call_on_name(&view_name, move |tv: &mut CachedTextView| {
+                tv.set_content(styled);
+            });
+        }
+
+        // the menu marks the current theme
+        let store = Arc::clone(&self.data);
+        self.send(move |siv| {
+            for dialog in dialogs {
+                update_revision_diff(siv, &highlighter, dialog.read_unwrap().deref());
+            }
+            siv.set_theme(theme);
+            let mut menubar = build_menu(gvks, store);
+            menubar.autohide = false;
+            *siv.menubar() = menubar;
+        });
+
+        Ok(())
+    }
+
     fn dispatch_pop_view(self) -> anyhow::Result<()> {
         let store = Arc::clone(&self.data);
         self.send(move |siv| {
@@ -590,10 +1193,17 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
             let top_view = top_view.read_sync()?;
             let view_name = top_view.get_unique_name();
             let is_list = top_view.is_list();
+            let is_tree = top_view.is_tree();
             let uid = top_view.get_uid();
             drop(top_view);
 
-            let result = if is_list {
+            let result = if is_tree {
+                siv.call_on_name(&view_name, |sv: &mut SelectView<EvaluatedResource>| {
+                    sv.selection().map(|resource| resource.as_ref().clone())
+                })
+                .to_log_warn(|| format!("Could not find a view with name {view_name}"))?
+                .to_log_warn(|| format!("Could not find a selected item in view {view_name}"))?
+            } else if is_list {
                 siv.call_on_name(
                     &view_name,
                     |table: &mut TableView<EvaluatedResource, usize>| {
@@ -641,4 +1251,14 @@ impl<'a> DispatchContextUiExt for DispatchContext<'a, UiStore, InterUiSignal> {
             .send(ToBackendSignal::LogsSubscribe(log_request))?;
         Ok(())
     }
+
+    fn get_resource_events(&self, resource: &ResourceView) -> anyhow::Result<Vec<ResourceEvent>> {
+        let mut store = self.data.lock_sync()?;
+        store.register_event_gvks()?;
+        let events = store
+            .resource_manager
+            .read_sync()?
+            .get_events(&resource.uid_or_name());
+        Ok(events)
+    }
 }
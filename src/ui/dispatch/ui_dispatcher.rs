@@ -16,6 +16,9 @@ impl Dispatch<UiStore, InterUiSignal> for InterUiSignal {
             InterUiSignal::ApplyNameFilter(id, name) => {
                 context.dispatch_apply_name_filter(id, name)
             }
+            InterUiSignal::ListSortChanged(gvk, sort) => {
+                context.dispatch_list_sort_changed(gvk, sort)
+            }
             InterUiSignal::ShowDetails(resource) => context.dispatch_show_details(resource),
             InterUiSignal::ShowGvk(gvk) => context.dispatch_show_gvk(gvk),
             InterUiSignal::CtrlSPressed => context.dispatch_ctrl_s(),
@@ -40,6 +43,10 @@ impl Dispatch<UiStore, InterUiSignal> for InterUiSignal {
             InterUiSignal::CtrlPPressed => context.dispatch_dump_resource_sample(),
             InterUiSignal::AltPlusPressed => context.dispatch_show_window_switcher(),
             InterUiSignal::ShowWindow(id) => context.dispatch_bring_to_front(id),
+            InterUiSignal::ShowConfigErrors => context.dispatch_show_config_errors(),
+            InterUiSignal::ConfigErrorsChanged => context.dispatch_config_errors_changed(),
+            InterUiSignal::SwitchTheme(name) => context.dispatch_switch_theme(name),
+            InterUiSignal::ShowEvalProfiler => context.dispatch_show_eval_profiler(),
             InterUiSignal::CtrlSlashPressed => context.dispatch_ctrl_slash(),
             InterUiSignal::CtrlKPressed => context.dispatch_ctrl_k(),
 
@@ -58,8 +65,36 @@ impl Dispatch<UiStore, InterUiSignal> for InterUiSignal {
             InterUiSignal::LogsApplyPrevious(view_id, show_previous) => {
                 context.dispatch_logs_apply_previous(view_id, show_previous)
             }
+            InterUiSignal::LogsApplySinceTime(view_id, since_time) => {
+                context.dispatch_logs_apply_since_time(view_id, since_time)
+            }
+            InterUiSignal::LogsApplyUntilTime(view_id, until_time) => {
+                context.dispatch_logs_apply_until_time(view_id, until_time)
+            }
+            InterUiSignal::LogsApplyTimestampFormat(view_id, format) => {
+                context.dispatch_logs_apply_timestamp_format(view_id, format)
+            }
+            InterUiSignal::LogsJumpToTime(view_id, instant) => {
+                context.dispatch_logs_jump_to_time(view_id, instant)
+            }
+            InterUiSignal::LogsExport(request) => context.dispatch_logs_export(request),
+            InterUiSignal::CodeApplyRenderProfile(view_id, name) => {
+                context.dispatch_code_apply_render_profile(view_id, name)
+            }
+            InterUiSignal::CtrlEPressed => context.dispatch_show_log_export_dialog(),
+            InterUiSignal::StartPortForwardProfile(name) => {
+                context.dispatch_start_port_forward_profile(name)
+            }
+            InterUiSignal::SavePortForwardProfile(name) => {
+                context.dispatch_save_port_forward_profile(name)
+            }
             InterUiSignal::CtrlFPressed => context.dispatch_show_port_forwarding_dialog(),
             InterUiSignal::CtrlGPressed => context.dispatch_show_port_forwarding_view(),
+            InterUiSignal::CtrlDPressed => context.dispatch_show_deleted_history(),
+            InterUiSignal::CtrlRPressed => context.dispatch_show_revisions(),
+            InterUiSignal::CtrlTPressed => context.dispatch_show_owner_tree(),
+            InterUiSignal::CtrlOPressed => context.dispatch_show_related(),
+            InterUiSignal::ShowYaml(resource) => context.dispatch_show_resource_yaml(resource),
         };
 
         log_signal_result(result, &signal_name);
@@ -1,18 +1,46 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use anyhow::{Context, Error};
 use cursive::theme::Style;
 use cursive::utils::markup::StyledString;
 
+pub(crate) type SharedSyntaxSet = Arc<syntect::parsing::SyntaxSet>;
+
+/// The syntaxes are loaded once and shared by the highlighters of every theme.
+pub(crate) fn load_syntax_set() -> SharedSyntaxSet {
+    Arc::new(syntect::parsing::SyntaxSet::load_defaults_newlines())
+}
+
 pub(crate) struct Highlighter {
     theme: syntect::highlighting::Theme,
-    syntax_set: syntect::parsing::SyntaxSet,
+    syntax_set: SharedSyntaxSet,
 }
 
 impl Highlighter {
-    pub(crate) fn new(theme_name: &str) -> anyhow::Result<Self> {
-        let theme = Self::get_theme(theme_name)?;
-        let syntax_set = syntect::parsing::SyntaxSet::load_defaults_newlines();
+    pub(crate) fn new(theme_name: &str, syntax_set: &SharedSyntaxSet) -> anyhow::Result<Self> {
+        Ok(Self::with_theme(Self::get_theme(theme_name)?, syntax_set))
+    }
+
+    /// Loads a `.tmTheme` file.
+    pub(crate) fn from_theme_file(
+        path: &Path,
+        syntax_set: &SharedSyntaxSet,
+    ) -> anyhow::Result<Self> {
+        let theme = syntect::highlighting::ThemeSet::get_theme(path)
+            .with_context(|| format!("Could not load theme file {}", path.display()))?;
+        Ok(Self::with_theme(theme, syntax_set))
+    }
+
+    fn with_theme(theme: syntect::highlighting::Theme, syntax_set: &SharedSyntaxSet) -> Self {
+        Self {
+            theme,
+            syntax_set: Arc::clone(syntax_set),
+        }
+    }
 
-        Ok(Self { theme, syntax_set })
+    pub(crate) fn syntax_set(&self) -> &SharedSyntaxSet {
+        &self.syntax_set
     }
 
     fn get_theme(theme_name: &str) -> Result<syntect::highlighting::Theme, Error> {
@@ -76,7 +104,7 @@ mod tests {
 
     #[test]
     fn test() {
-        let hl = Highlighter::new("base16-eighties.dark").unwrap();
+        let hl = Highlighter::new("base16-eighties.dark", &load_syntax_set()).unwrap();
         let styled_string = hl.highlight("sample: true", "yaml");
         assert!(styled_string.is_ok());
     }
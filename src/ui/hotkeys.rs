@@ -59,6 +59,30 @@ pub(crate) fn register_hotkeys(ui: &mut Cursive, ui_to_ui_sender: kanal::Sender<
             event::Event::CtrlChar('g'),
             Arc::new(|| InterUiSignal::CtrlGPressed),
         ),
+        (
+            event::Event::CtrlChar('e'),
+            Arc::new(|| InterUiSignal::CtrlEPressed),
+        ),
+        (
+            event::Event::CtrlChar('d'),
+            Arc::new(|| InterUiSignal::CtrlDPressed),
+        ),
+        (
+            event::Event::CtrlChar('r'),
+            Arc::new(|| InterUiSignal::CtrlRPressed),
+        ),
+        (
+            event::Event::CtrlChar('t'),
+            Arc::new(|| InterUiSignal::CtrlTPressed),
+        ),
+        (
+            event::Event::CtrlChar('o'),
+            Arc::new(|| InterUiSignal::CtrlOPressed),
+        ),
+        (
+            event::Event::from(event::Key::F12),
+            Arc::new(|| InterUiSignal::ShowEvalProfiler),
+        ),
     ];
 
     hotkeys.into_iter().for_each(|(event, signal)| {
@@ -1,4 +1,5 @@
-use std::process::{Command, ExitStatus};
+use std::io::Write;
+use std::process::{Command, ExitStatus, Stdio};
 
 use anyhow::Context;
 use cursive::reexports::log::info;
@@ -8,6 +9,7 @@ use kube::ResourceExt;
 #[derive(Debug)]
 pub(crate) enum InteractiveCommand {
     Exec(Pod, String),
+    Pipe { command: String, input: String },
 }
 
 impl InteractiveCommand {
@@ -39,6 +41,28 @@ impl InteractiveCommand {
                 let exit_status = command.spawn()?.wait()?;
                 Ok(exit_status)
             }
+            InteractiveCommand::Pipe {
+                command: shell_command,
+                input,
+            } => {
+                info!("Piping {} bytes to: {}", input.len(), shell_command);
+
+                let mut command = Command::new("sh");
+                command.args(["-c", shell_command.as_str()]);
+                command.stdin(Stdio::piped());
+
+                info!("Prepared command: {:?}", command);
+                let mut child = command.spawn()?;
+                {
+                    let mut stdin = child.stdin.take().context("Failed to open stdin")?;
+                    // the command may exit before reading the whole input (i.e. `head`)
+                    if let Err(err) = stdin.write_all(input.as_bytes()) {
+                        info!("Failed to write the whole input to {shell_command}: {err}");
+                    }
+                }
+                let exit_status = child.wait()?;
+                Ok(exit_status)
+            }
         }
     }
 }
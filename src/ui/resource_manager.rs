@@ -1,5 +1,5 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use chrono::Utc;
@@ -7,32 +7,87 @@ use cursive::reexports::log::{error, info, warn};
 use kube::api::GroupVersionKind;
 
 use crate::config::extractor::{
-    ActionType, Column, EmbeddedExtractor, EvaluatorType, EventHandlerType, ExtractorConfig,
+    ActionType, Column, DefaultSort, EmbeddedExtractor, EvaluatorType, EventHandlerType,
+    ExtractorConfig, SortType,
 };
 use crate::eval::eval_result::EvalResult;
 use crate::eval::evaluator::Evaluator;
+use crate::model::metrics::{pod_key, MetricsSnapshot, SharedMetricsStore};
+use crate::model::related::{
+    resource_selector, selector_matches, RelatedRef, RelatedResource, RelatedTarget,
+};
 use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
+use crate::model::resource_event::ResourceEvent;
 use crate::traits::ext::gvk::GvkNameExt;
 use crate::traits::ext::gvk::{GvkExt, PseudoResourceGvkExt};
+use crate::traits::ext::rw_lock::RwLockExt;
 use crate::util::ui::ago;
 use crate::util::watcher::LazyWatcher;
 
+const MAX_DELETED_HISTORY_SIZE: usize = 100;
+
+/// The last known state of a deleted resource.
+#[derive(Debug, Clone)]
+pub(crate) struct DeletedResource {
+    pub(crate) resource: ResourceView,
+    pub(crate) deleted_at: chrono::DateTime<Utc>,
+}
+
+/// A snapshot of a resource taken on every update with a new `resourceVersion`.
+#[derive(Debug, Clone)]
+pub(crate) struct ResourceRevision {
+    pub(crate) resource_version: String,
+    pub(crate) resource: ResourceView,
+    pub(crate) observed_at: chrono::DateTime<Utc>,
+}
+
+/// A resource with the cached resources it owns according to `metadata.ownerReferences`.
+/// Pseudo resources are owned by their source resource.
+#[derive(Debug, Clone)]
+pub(crate) struct OwnerTree {
+    pub(crate) resource: EvaluatedResource,
+    pub(crate) children: Vec<OwnerTree>,
+}
+
 pub(crate) struct ResourceManager {
     resources_by_gvk: HashMap<GroupVersionKind, HashMap<String, EvaluatedResource>>,
     evaluator: Evaluator,
     config_watcher: Arc<LazyWatcher<ExtractorConfig>>,
     default_columns: Arc<Vec<Column>>,
     tombstones: HashMap<String, chrono::DateTime<Utc>>,
+    /// Deleted resources are shown for this long and then purged.
+    tombstone_retention: chrono::Duration,
+    deleted_history: HashMap<GroupVersionKind, VecDeque<DeletedResource>>,
+    /// Revisions by the resource uid, the oldest first. Disabled if the size is 0.
+    revisions: HashMap<String, VecDeque<ResourceRevision>>,
+    revision_history_size: usize,
+    /// Events by the involved object uid, then by the event uid.
+    events: HashMap<String, HashMap<String, ResourceEvent>>,
+    /// Usage from `metrics.k8s.io` joined with pod requests/limits and node allocatable.
+    metrics: SharedMetricsStore,
+    /// Columns that called the `metrics` module, only these are re-evaluated on a new
+    /// usage snapshot.
+    metrics_columns: HashMap<GroupVersionKind, HashSet<String>>,
 }
 
 impl ResourceManager {
     pub(crate) fn new(
         evaluator: Evaluator,
         config_watcher: &Arc<LazyWatcher<ExtractorConfig>>,
+        tombstone_retention: chrono::Duration,
+        revision_history_size: usize,
+        metrics: &SharedMetricsStore,
     ) -> Self {
         Self {
             evaluator,
+            metrics: Arc::clone(metrics),
+            metrics_columns: HashMap::default(),
             tombstones: HashMap::default(),
+            tombstone_retention,
+            deleted_history: HashMap::default(),
+            revisions: HashMap::default(),
+            revision_history_size,
+            events: HashMap::default(),
             config_watcher: Arc::clone(config_watcher),
             resources_by_gvk: HashMap::default(),
             default_columns: vec![
@@ -41,24 +96,32 @@ impl ResourceManager {
                     display_name: "Namespace".to_string(),
                     width: 0,
                     evaluator_type: EvaluatorType::Embedded(EmbeddedExtractor::Namespace),
+                    sort_type: SortType::Natural,
+                    styles: vec![],
                 },
                 Column {
                     name: "name".to_string(),
                     display_name: "Name".to_string(),
                     width: 0,
                     evaluator_type: EvaluatorType::Embedded(EmbeddedExtractor::Name),
+                    sort_type: SortType::Natural,
+                    styles: vec![],
                 },
                 Column {
                     name: "status".to_string(),
                     display_name: "Status".to_string(),
                     width: 0,
                     evaluator_type: EvaluatorType::Embedded(EmbeddedExtractor::Status),
+                    sort_type: SortType::String,
+                    styles: vec![],
                 },
                 Column {
                     name: "age".to_string(),
                     display_name: "Age".to_string(),
                     width: 4,
                     evaluator_type: EvaluatorType::Embedded(EmbeddedExtractor::Age),
+                    sort_type: SortType::Duration,
+                    styles: vec![],
                 },
             ]
             .into(),
@@ -108,17 +171,28 @@ impl ResourceManager {
         }
 
         let is_deleted = resource.deletion_timestamp().is_some();
+        let event = ResourceEvent::from_resource(&resource);
+        // requests, limits and allocatable have to be known before the columns are evaluated
+        self.remember_allocations(&resource);
 
-        let pseudo_resources = self.extract_pseudo_resources(&resource);
+        let mut pseudo_resources = self.extract_pseudo_resources(&resource);
         let key = resource.uid_or_name();
         let gvk = resource.gvk();
         let columns = self.get_columns(&gvk);
 
         let evaluated_resource = match self
             .evaluator
-            .evaluate_columns(resource.clone(), columns.as_ref())
+            .evaluate_columns_tracking_metrics(resource.clone(), &columns)
         {
-            Ok(evaluated_resource) => evaluated_resource,
+            Ok((evaluated_resource, metrics_columns)) => {
+                if !metrics_columns.is_empty() {
+                    self.metrics_columns
+                        .entry(gvk.clone())
+                        .or_default()
+                        .extend(metrics_columns);
+                }
+                evaluated_resource
+            }
             Err(err) => {
                 error!(
                     "Failed to evaluate resource {}: {}",
@@ -129,42 +203,386 @@ impl ResourceManager {
                 EvaluatedResource {
                     values: Arc::new(values),
                     resource,
+                    columns: Arc::clone(&columns),
                 }
             }
         };
+        let evaluated_resource = self.apply_event_columns(evaluated_resource, &columns);
 
         self.resources_by_gvk
             .entry(gvk)
             .or_default()
             .insert(key, evaluated_resource.clone());
+        self.remember_revision(&evaluated_resource.resource);
 
         if is_deleted {
             let iter = std::iter::once(&evaluated_resource).chain(pseudo_resources.iter());
             for resource in iter {
                 let key = resource.resource.uid_or_name();
-                self.tombstones.insert(key, Utc::now());
+                // keep the time of the first deletion event; terminating resources are
+                // updated a few more times before they are gone
+                self.tombstones.entry(key).or_insert_with(Utc::now);
             }
+            self.remember_deleted(&evaluated_resource.resource);
+        }
+
+        // the involved resource is returned along with pseudo resources to get its event
+        // columns re-rendered
+        if let Some(involved_resource) =
+            event.and_then(|event| self.replace_event(event, is_deleted))
+        {
+            pseudo_resources.push(involved_resource);
         }
 
         (evaluated_resource, pseudo_resources)
     }
 
+    /// Updates the event cache and returns the involved resource if it has event columns.
+    fn replace_event(
+        &mut self,
+        event: ResourceEvent,
+        is_deleted: bool,
+    ) -> Option<EvaluatedResource> {
+        let involved_uid = event.involved_uid.clone();
+        let events = self.events.entry(involved_uid.clone()).or_default();
+        if is_deleted {
+            events.remove(&event.uid);
+        } else {
+            events.insert(event.uid.clone(), event);
+        }
+        if events.is_empty() {
+            self.events.remove(&involved_uid);
+        }
+
+        let involved_resource = self.get_resource_by_uid(&involved_uid)?;
+        let gvk = involved_resource.resource.gvk();
+        let columns = self.get_columns(&gvk);
+        if !has_event_columns(&columns) {
+            return None;
+        }
+
+        let involved_resource = self.apply_event_columns(involved_resource, &columns);
+        self.resources_by_gvk
+            .entry(gvk)
+            .or_default()
+            .insert(involved_uid, involved_resource.clone());
+
+        Some(involved_resource)
+    }
+
+    fn apply_event_columns(
+        &self,
+        evaluated_resource: EvaluatedResource,
+        columns: &[Column],
+    ) -> EvaluatedResource {
+        if !has_event_columns(columns) {
+            return evaluated_resource;
+        }
+
+        let reason = self
+            .get_latest_warning(&evaluated_resource.resource.uid_or_name())
+            .map(|event| event.reason)
+            .unwrap_or_else(|| "-".to_string());
+
+        let mut values = evaluated_resource.values.as_ref().clone();
+        for (value, column) in values.iter_mut().zip(columns) {
+            if is_event_column(column) {
+                *value = EvalResult::String(reason.clone());
+            }
+        }
+
+        EvaluatedResource {
+            values: Arc::new(values),
+            ..evaluated_resource
+        }
+    }
+
+    /// Returns the events of the given resource sorted by the last timestamp.
+    pub(crate) fn get_events(&self, uid: &str) -> Vec<ResourceEvent> {
+        let mut events = self
+            .events
+            .get(uid)
+            .map(|events| events.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        events.sort_by_key(|event| event.last_timestamp);
+        events
+    }
+
+    pub(crate) fn get_latest_warning(&self, uid: &str) -> Option<ResourceEvent> {
+        self.events
+            .get(uid)?
+            .values()
+            .filter(|event| event.is_warning())
+            .max_by_key(|event| event.last_timestamp)
+            .cloned()
+    }
+
     pub(crate) fn reevaluate_all_for_gvk(&mut self, gvk: &GroupVersionKind) {
+        // the columns may have changed since they were tracked
+        self.metrics_columns.remove(gvk);
         if let Some(resource_map) = self.resources_by_gvk.remove(gvk) {
-            for (_, resource) in resource_map.into_iter() {
+            for (key, resource) in resource_map.into_iter() {
+                // deleted resources are dropped on refresh
+                if self.tombstones.remove(&key).is_some() {
+                    self.forget_resource(&key, &resource.resource);
+                    continue;
+                }
                 self.replace(resource.resource);
             }
         }
     }
 
+    /// Stores the latest usage and returns, grouped by GVK, the resources whose metrics
+    /// columns are outdated. They are re-evaluated by [`Self::evaluate_metrics_columns`]
+    /// under a read lock and stored back by [`Self::apply_metrics_columns`].
+    pub(crate) fn replace_metrics(
+        &mut self,
+        snapshot: MetricsSnapshot,
+    ) -> Vec<(GroupVersionKind, Vec<EvaluatedResource>)> {
+        let (changed_pods, changed_nodes) = self.metrics.write_unwrap().replace_usage(snapshot);
+        if changed_pods.is_empty() && changed_nodes.is_empty() {
+            return vec![];
+        }
+
+        self.metrics_columns
+            .keys()
+            .filter_map(|gvk| {
+                let outdated = self
+                    .resources_by_gvk
+                    .get(gvk)?
+                    .iter()
+                    .filter(|(key, _)| !self.tombstones.contains_key(*key))
+                    .filter(|(_, resource)| {
+                        is_usage_changed(&resource.resource, &changed_pods, &changed_nodes)
+                    })
+                    .map(|(_, resource)| resource.clone())
+                    .collect::<Vec<_>>();
+                (!outdated.is_empty()).then(|| (gvk.clone(), outdated))
+            })
+            .collect()
+    }
+
+    /// Re-evaluates the metrics columns of the resources, returns the outdated resources
+    /// along with the updated ones.
+    pub(crate) fn evaluate_metrics_columns(
+        &self,
+        gvk: &GroupVersionKind,
+        resources: Vec<EvaluatedResource>,
+    ) -> Vec<(EvaluatedResource, EvaluatedResource)> {
+        let metrics_columns = match self.metrics_columns.get(gvk) {
+            Some(metrics_columns) => metrics_columns,
+            None => return vec![],
+        };
+        resources
+            .into_iter()
+            .filter_map(|outdated| {
+                match self
+                    .evaluator
+                    .reevaluate_columns(&outdated, metrics_columns)
+                {
+                    Ok(updated) => Some((outdated, updated)),
+                    Err(err) => {
+                        error!(
+                            "Failed to evaluate metrics of resource {}: {err}",
+                            outdated.resource.full_unique_name()
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Stores the re-evaluated resources unless they were replaced in the meantime and
+    /// returns the stored ones.
+    pub(crate) fn apply_metrics_columns(
+        &mut self,
+        resources: Vec<(EvaluatedResource, EvaluatedResource)>,
+    ) -> Vec<EvaluatedResource> {
+        let mut result = vec![];
+        for (outdated, updated) in resources {
+            let gvk = updated.resource.gvk();
+            let key = updated.resource.uid_or_name();
+            let current = self
+                .resources_by_gvk
+                .get_mut(&gvk)
+                .and_then(|map| map.get_mut(&key));
+            if let Some(current) = current {
+                if Arc::ptr_eq(&current.values, &outdated.values) {
+                    *current = updated.clone();
+                    result.push(updated);
+                }
+            }
+        }
+        result
+    }
+
+    fn remember_allocations(&self, resource: &ResourceView) {
+        match resource {
+            ResourceView::Pod(pod) => self.metrics.write_unwrap().set_pod(pod),
+            ResourceView::Node(node) => self.metrics.write_unwrap().set_node(node),
+            _ => {}
+        }
+    }
+
+    /// Drops what is kept about a resource that left `resources_by_gvk`.
+    fn forget_resource(&mut self, key: &str, resource: &ResourceView) {
+        self.revisions.remove(key);
+        self.events.remove(key);
+        self.evaluator.rhai_objects().forget(key);
+        self.forget_allocations(resource);
+    }
+
+    /// A conversion racing with the removal of its resource may cache it again after it was
+    /// forgotten, such entries are evicted here.
+    fn evict_rhai_objects(&self) {
+        let known_keys = self
+            .resources_by_gvk
+            .values()
+            .flat_map(|map| map.keys().map(String::as_str))
+            .collect::<HashSet<_>>();
+        self.evaluator
+            .rhai_objects()
+            .retain(|uid| known_keys.contains(uid));
+    }
+
+    fn forget_allocations(&self, resource: &ResourceView) {
+        match (resource, metrics_key(resource)) {
+            (ResourceView::Pod(_), Some(key)) => self.metrics.write_unwrap().remove_pod(&key),
+            (ResourceView::Node(_), Some(key)) => self.metrics.write_unwrap().remove_node(&key),
+            _ => {}
+        }
+    }
+
+    fn remember_deleted(&mut self, resource: &ResourceView) {
+        if matches!(resource, ResourceView::PseudoResource(_)) {
+            return;
+        }
+
+        let key = resource.uid_or_name();
+        let deleted_at = self.tombstones.get(&key).cloned().unwrap_or_else(Utc::now);
+        let history = self.deleted_history.entry(resource.gvk()).or_default();
+        history.retain(|deleted| deleted.resource.uid_or_name() != key);
+        history.push_front(DeletedResource {
+            resource: resource.clone(),
+            deleted_at,
+        });
+        history.truncate(MAX_DELETED_HISTORY_SIZE);
+    }
+
+    fn remember_revision(&mut self, resource: &ResourceView) {
+        if self.revision_history_size == 0 || matches!(resource, ResourceView::PseudoResource(_)) {
+            return;
+        }
+        let resource_version = match resource.resource_version() {
+            Some(resource_version) => resource_version,
+            None => return,
+        };
+
+        let revisions = self.revisions.entry(resource.uid_or_name()).or_default();
+        if revisions
+            .iter()
+            .any(|revision| revision.resource_version == resource_version)
+        {
+            return;
+        }
+
+        revisions.push_back(ResourceRevision {
+            resource_version,
+            resource: resource.clone(),
+            observed_at: Utc::now(),
+        });
+        while revisions.len() > self.revision_history_size {
+            revisions.pop_front();
+        }
+    }
+
+    pub(crate) fn is_revision_history_enabled(&self) -> bool {
+        self.revision_history_size > 0
+    }
+
+    /// Recorded revisions of a resource, the oldest first.
+    pub(crate) fn get_revisions(&self, uid: &str) -> Vec<ResourceRevision> {
+        self.revisions
+            .get(uid)
+            .map(|revisions| revisions.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Recently deleted resources of the given GVK, the most recent first.
+    pub(crate) fn get_deleted_history(&self, gvk: &GroupVersionKind) -> Vec<DeletedResource> {
+        self.deleted_history
+            .get(gvk)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Removes resources deleted longer than the retention period ago and returns the GVKs
+    /// that were affected.
+    pub(crate) fn purge_tombstones(&mut self) -> HashSet<GroupVersionKind> {
+        self.evict_rhai_objects();
+
+        let now = Utc::now();
+        let retention = self.tombstone_retention;
+        let expired: HashSet<String> = self
+            .tombstones
+            .iter()
+            .filter(|(_, deleted_at)| now.signed_duration_since(**deleted_at) > retention)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if expired.is_empty() {
+            return HashSet::default();
+        }
+
+        let mut affected_gvks = HashSet::default();
+        let mut purged_resources = vec![];
+        for (gvk, map) in self.resources_by_gvk.iter_mut() {
+            let keys = map
+                .keys()
+                .filter(|key| expired.contains(*key))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !keys.is_empty() {
+                affected_gvks.insert(gvk.clone());
+            }
+            purged_resources.extend(keys.iter().filter_map(|key| map.remove(key)));
+        }
+        self.resources_by_gvk.retain(|_, map| !map.is_empty());
+        for purged_resource in purged_resources {
+            self.forget_resource(
+                &purged_resource.resource.uid_or_name(),
+                &purged_resource.resource,
+            );
+        }
+
+        for key in expired.iter() {
+            self.tombstones.remove(key);
+            self.revisions.remove(key);
+        }
+
+        info!(
+            "Purged {} tombstones from {} GVKs",
+            expired.len(),
+            affected_gvks.len()
+        );
+
+        affected_gvks
+    }
+
+    pub(crate) fn has_event_columns(&self, gvk: &GroupVersionKind) -> bool {
+        has_event_columns(&self.get_columns(gvk))
+    }
+
     pub(crate) fn get_columns(&self, gvk: &GroupVersionKind) -> Arc<Vec<Column>> {
-        if let Some(columns) = self.config_watcher.value().columns_map.get(gvk) {
+        let config = self.config_watcher.value();
+        if let Some(columns) = ExtractorConfig::find(&config.columns_map, gvk) {
             return Arc::clone(columns);
         }
 
         let mut gvk = gvk.clone();
         while let Some(parent_gvk) = gvk.get_pseudo_parent() {
-            if let Some(columns) = self.config_watcher.value().columns_map.get(&parent_gvk) {
+            if let Some(columns) = config.columns_map.get(&parent_gvk) {
                 return Arc::clone(columns);
             }
             gvk = parent_gvk;
@@ -176,7 +594,8 @@ impl ResourceManager {
 
     fn extract_pseudo_resources(&mut self, resource: &ResourceView) -> Vec<EvaluatedResource> {
         let config = self.config_watcher.value();
-        let extractors = if let Some(extractors) = config.pseudo_resources_map.get(&resource.gvk())
+        let extractors = if let Some(extractors) =
+            ExtractorConfig::find(&config.pseudo_resources_map, &resource.gvk())
         {
             extractors
         } else {
@@ -187,7 +606,18 @@ impl ResourceManager {
             .evaluator
             .evaluate_pseudo_resources(resource, extractors.as_ref());
 
-        if pseudo_resources.is_empty() {
+        let keyed_gvks = extractors
+            .iter()
+            .filter(|extractor| extractor.key.is_some())
+            .map(|extractor| resource.build_pseudo_gvk(&extractor.name))
+            .collect::<Vec<_>>();
+        let previous_keyed = keyed_gvks
+            .iter()
+            .filter_map(|gvk| self.resources_by_gvk.get(gvk))
+            .flat_map(|map| map.values().cloned())
+            .collect::<Vec<_>>();
+
+        if pseudo_resources.is_empty() && previous_keyed.is_empty() {
             return vec![];
         }
         info!(
@@ -205,23 +635,72 @@ impl ResourceManager {
         // Side-effects: if a resource is updated (and some rules are removed), they will silently
         // disappear from the table after a refresh.
         // Though, if there IS a natural key, we can mark non-existent resources in the new state
-        // as deleted and let them remain in the table.
+        // as deleted and let them remain in the table. That's what the extractor `key` script
+        // is for.
         for extractor in extractors.as_ref() {
-            let gvk = resource.build_pseudo_gvk(&extractor.name);
-            self.resources_by_gvk.remove(&gvk);
+            if extractor.key.is_none() {
+                let gvk = resource.build_pseudo_gvk(&extractor.name);
+                self.resources_by_gvk.remove(&gvk);
+            }
         }
 
+        let is_source_deleted = resource.deletion_timestamp().is_some();
+        let mut present_keys = HashSet::new();
         let mut result = vec![];
         for pseudo_resource in pseudo_resources {
-            let (resource, pseudo_resources) =
+            let (pseudo_resource, pseudo_resources) =
                 self.replace(ResourceView::PseudoResource(Arc::new(pseudo_resource)));
-            result.push(resource);
+            let key = pseudo_resource.resource.uid_or_name();
+            // a keyed pseudo resource may come back after it was tombstoned
+            if !is_source_deleted {
+                self.tombstones.remove(&key);
+            }
+            present_keys.insert(key);
+            result.push(pseudo_resource);
             result.extend(pseudo_resources);
         }
 
+        for previous in previous_keyed {
+            let key = previous.resource.uid_or_name();
+            if present_keys.contains(&key) || self.tombstones.contains_key(&key) {
+                continue;
+            }
+            if let Some(tombstoned) = self.tombstone_pseudo_resource(previous) {
+                result.push(tombstoned);
+            }
+        }
+
         result
     }
 
+    /// Marks a keyed pseudo resource that disappeared from its source as deleted. It stays
+    /// in the table until its tombstone is purged.
+    fn tombstone_pseudo_resource(
+        &mut self,
+        evaluated_resource: EvaluatedResource,
+    ) -> Option<EvaluatedResource> {
+        let pseudo_resource = match &evaluated_resource.resource {
+            ResourceView::PseudoResource(pseudo_resource) => pseudo_resource,
+            _ => return None,
+        };
+        let deleted_at = Utc::now();
+        let mut pseudo_resource = pseudo_resource.as_ref().clone();
+        pseudo_resource.deleted_at = Some(deleted_at);
+
+        let key = evaluated_resource.resource.uid_or_name();
+        info!("Pseudo resource {key} is gone, marking it as deleted");
+        let tombstoned = EvaluatedResource {
+            resource: ResourceView::PseudoResource(Arc::new(pseudo_resource)),
+            ..evaluated_resource
+        };
+        self.resources_by_gvk
+            .entry(tombstoned.resource.gvk())
+            .or_default()
+            .insert(key.clone(), tombstoned.clone());
+        self.tombstones.insert(key, deleted_at);
+        Some(tombstoned)
+    }
+
     pub(crate) fn get_resources_iter(
         &self,
         gvk: &GroupVersionKind,
@@ -231,23 +710,31 @@ impl ResourceManager {
             .map(|map| map.values())
             .into_iter()
             .flatten()
+            // deleted resources stay visible (marked as deleted) until their tombstones
+            // are purged
             .filter(|resource| {
-                // user must be able to see pseudo resources even if they are deleted
-                // (i.e. from the pod view when pod was deleted, containers should still be visible
-                let is_pseudo = matches!(resource.resource, ResourceView::PseudoResource(_));
-                if is_pseudo {
-                    return true;
-                }
                 let key = resource.resource.uid_or_name();
-                !self.tombstones.contains_key(&key)
+                !self.is_tombstone_expired(&key)
             })
     }
 
+    fn is_tombstone_expired(&self, key: &str) -> bool {
+        self.tombstones
+            .get(key)
+            .map(|deleted_at| {
+                Utc::now().signed_duration_since(*deleted_at) > self.tombstone_retention
+            })
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn get_default_sort(&self, gvk: &GroupVersionKind) -> Option<DefaultSort> {
+        let config = self.config_watcher.value();
+        ExtractorConfig::find(&config.default_sort_map, gvk).map(|sort| sort.as_ref().clone())
+    }
+
     pub(crate) fn get_submit_handler_type(&self, gvk: &GroupVersionKind) -> Option<ActionType> {
-        self.config_watcher
-            .value()
-            .event_handler_types_map
-            .get(gvk)
+        let config = self.config_watcher.value();
+        ExtractorConfig::find(&config.event_handler_types_map, gvk)
             .cloned()
             .iter()
             .flat_map(|handlers| handlers.iter())
@@ -257,6 +744,119 @@ impl ResourceManager {
             })
     }
 
+    /// Builds the tree of owned resources rooted at the topmost cached owner of the given
+    /// resource.
+    pub(crate) fn get_owner_tree(&self, uid: &str) -> Option<OwnerTree> {
+        let mut root = self.get_resource_by_uid(uid)?;
+        let mut visited = HashSet::from([root.resource.uid_or_name()]);
+        while let Some(owner) = root
+            .resource
+            .owner_references()
+            .iter()
+            .find_map(|owner| self.get_resource_by_uid(&owner.uid))
+        {
+            // ownerReferences are not validated, so cycles are possible
+            if !visited.insert(owner.resource.uid_or_name()) {
+                break;
+            }
+            root = owner;
+        }
+
+        let children_by_owner = self.get_children_by_owner();
+        let mut visited = HashSet::default();
+        Some(build_owner_tree(root, &children_by_owner, &mut visited))
+    }
+
+    fn get_children_by_owner(&self) -> HashMap<String, Vec<EvaluatedResource>> {
+        let mut children_by_owner: HashMap<String, Vec<EvaluatedResource>> = HashMap::default();
+        let resources = self
+            .resources_by_gvk
+            .values()
+            .flat_map(|map| map.values())
+            .filter(|resource| !self.is_tombstone_expired(&resource.resource.uid_or_name()));
+
+        for resource in resources {
+            let owner_uids = match &resource.resource {
+                ResourceView::PseudoResource(pseudo_resource) => {
+                    vec![pseudo_resource.source.uid_or_name()]
+                }
+                view => view
+                    .owner_references()
+                    .iter()
+                    .map(|owner| owner.uid.clone())
+                    .collect(),
+            };
+            for owner_uid in owner_uids {
+                children_by_owner
+                    .entry(owner_uid)
+                    .or_default()
+                    .push(resource.clone());
+            }
+        }
+
+        for children in children_by_owner.values_mut() {
+            children.sort_by_key(|child| (child.resource.gvk().kind, child.resource.name()));
+        }
+        children_by_owner
+    }
+
+    /// Evaluates the `related` scripts of the resource and resolves the references against
+    /// the cached resources. A reference that matches nothing is kept without a resource so
+    /// that its GVK can be opened.
+    pub(crate) fn get_related(&self, resource: &ResourceView) -> Vec<RelatedResource> {
+        let config = self.config_watcher.value();
+        let related = match ExtractorConfig::find(&config.related_map, &resource.gvk()) {
+            Some(related) => related,
+            None => return vec![],
+        };
+
+        let mut result = vec![];
+        for related_ref in self.evaluator.evaluate_related(resource, related.as_ref()) {
+            let matches = self.find_related(resource, &related_ref);
+            if matches.is_empty() {
+                result.push(RelatedResource {
+                    relation: related_ref.relation,
+                    gvk: related_ref.gvk,
+                    resource: None,
+                });
+                continue;
+            }
+            result.extend(matches.into_iter().map(|matched| RelatedResource {
+                relation: related_ref.relation.clone(),
+                gvk: related_ref.gvk.clone(),
+                resource: Some(matched),
+            }));
+        }
+        result
+    }
+
+    fn find_related(
+        &self,
+        source: &ResourceView,
+        related_ref: &RelatedRef,
+    ) -> Vec<EvaluatedResource> {
+        let source_labels = source.labels();
+        let mut matches = self
+            .get_resources_iter(&related_ref.gvk)
+            .filter(|resource| {
+                related_ref.namespace.is_empty()
+                    || resource.resource.namespace() == related_ref.namespace
+            })
+            .filter(|resource| match &related_ref.target {
+                RelatedTarget::Name(name) => &resource.resource.name() == name,
+                RelatedTarget::Selector(selector) => {
+                    selector_matches(selector, &resource.resource.labels())
+                }
+                RelatedTarget::Selecting => resource_selector(&resource.resource)
+                    .map(|selector| selector_matches(&selector, &source_labels))
+                    .unwrap_or(false),
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|resource| (resource.resource.namespace(), resource.resource.name()));
+        matches
+    }
+
     pub(crate) fn get_resource_by_uid(&self, uid: &str) -> Option<EvaluatedResource> {
         for map in self.resources_by_gvk.values() {
             if let Some(resource) = map.get(uid) {
@@ -267,6 +867,61 @@ impl ResourceManager {
     }
 }
 
+fn is_event_column(column: &Column) -> bool {
+    matches!(
+        column.evaluator_type,
+        EvaluatorType::Embedded(EmbeddedExtractor::Events)
+    )
+}
+
+fn has_event_columns(columns: &[Column]) -> bool {
+    columns.iter().any(is_event_column)
+}
+
+fn build_owner_tree(
+    resource: EvaluatedResource,
+    children_by_owner: &HashMap<String, Vec<EvaluatedResource>>,
+    visited: &mut HashSet<String>,
+) -> OwnerTree {
+    let key = resource.resource.uid_or_name();
+    visited.insert(key.clone());
+
+    let mut children = vec![];
+    for child in children_by_owner.get(&key).into_iter().flatten() {
+        if visited.contains(&child.resource.uid_or_name()) {
+            continue;
+        }
+        children.push(build_owner_tree(child.clone(), children_by_owner, visited));
+    }
+
+    OwnerTree { resource, children }
+}
+
+/// Whether the usage of the resource, or of the source of a pseudo resource, has changed.
+fn is_usage_changed(
+    resource: &ResourceView,
+    changed_pods: &HashSet<String>,
+    changed_nodes: &HashSet<String>,
+) -> bool {
+    match (resource, metrics_key(resource)) {
+        (ResourceView::Pod(_), Some(key)) => changed_pods.contains(&key),
+        (ResourceView::Node(_), Some(key)) => changed_nodes.contains(&key),
+        (ResourceView::PseudoResource(pseudo_resource), _) => {
+            is_usage_changed(&pseudo_resource.source, changed_pods, changed_nodes)
+        }
+        _ => false,
+    }
+}
+
+/// The key pods and nodes are stored under in the metrics store.
+fn metrics_key(resource: &ResourceView) -> Option<String> {
+    match resource {
+        ResourceView::Pod(_) => Some(pod_key(&resource.namespace(), &resource.name())),
+        ResourceView::Node(_) => Some(resource.name()),
+        _ => None,
+    }
+}
+
 fn compare_resource_versions(left: &Option<String>, right: &Option<String>) -> Ordering {
     if left.is_none() || right.is_none() {
         return left.cmp(right);
@@ -280,3 +935,101 @@ fn compare_resource_versions(left: &Option<String>, right: &Option<String>) -> O
 
     left.cmp(right)
 }
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::Pod;
+    use k8s_openapi::serde_json::json;
+
+    use crate::eval::engine_factory::{build_engine, ScriptLimits};
+
+    use super::*;
+
+    const POD_CONFIG: &str = r#"
+resource:
+  group: ''
+  version: v1
+  kind: Pod
+pseudo_resources:
+  - name: container
+    script_content: |
+      let resources = [];
+      for container in resource.spec.containers {
+          resources.push(PseudoResource(container.name, container));
+      }
+      resources
+    key: resource.name
+"#;
+
+    fn pod(resource_version: &str, containers: &[&str]) -> ResourceView {
+        let containers = containers
+            .iter()
+            .map(|name| json!({ "name": name, "image": "alpine" }))
+            .collect::<Vec<_>>();
+        let pod: Pod = k8s_openapi::serde_json::from_value(json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": {
+                "name": "example",
+                "namespace": "default",
+                "uid": "example-uid",
+                "resourceVersion": resource_version,
+            },
+            "spec": { "containers": containers },
+        }))
+        .unwrap();
+        ResourceView::Pod(Arc::new(pod))
+    }
+
+    #[test]
+    fn test_removed_container_is_tombstoned() {
+        let extractor_dir = tempfile::tempdir().unwrap();
+        std::fs::write(extractor_dir.path().join("pod.yaml"), POD_CONFIG).unwrap();
+
+        let config_watcher = Arc::new(
+            LazyWatcher::new(vec![extractor_dir.path().to_path_buf()], |paths| {
+                ExtractorConfig::new(paths)
+            })
+            .unwrap(),
+        );
+        let engine_watcher = Arc::new(
+            LazyWatcher::new(vec![], |paths| {
+                build_engine(
+                    paths,
+                    &SharedMetricsStore::default(),
+                    &ScriptLimits::default(),
+                )
+            })
+            .unwrap(),
+        );
+        let mut resource_manager = ResourceManager::new(
+            Evaluator::new(2, &engine_watcher).unwrap(),
+            &config_watcher,
+            chrono::Duration::minutes(5),
+            0,
+            &SharedMetricsStore::default(),
+        );
+
+        let first = pod("1", &["app", "sidecar"]);
+        let gvk = first.build_pseudo_gvk("container");
+        resource_manager.replace(first);
+        resource_manager.replace(pod("2", &["app"]));
+
+        let containers = resource_manager
+            .get_resources_iter(&gvk)
+            .map(|evaluated| (evaluated.resource.name(), evaluated.resource.clone()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(containers.len(), 2);
+
+        match &containers["app"] {
+            ResourceView::PseudoResource(container) => assert!(container.deleted_at.is_none()),
+            resource => panic!("{resource:?} is not a pseudo resource"),
+        }
+        let sidecar = &containers["sidecar"];
+        match sidecar {
+            ResourceView::PseudoResource(container) => assert!(container.deleted_at.is_some()),
+            resource => panic!("{resource:?} is not a pseudo resource"),
+        }
+        assert_eq!(sidecar.status(), "Deleted");
+    }
+}
@@ -1,12 +1,17 @@
+use chrono::{DateTime, Utc};
 use kube::api::GroupVersionKind;
 use std::sync::Arc;
 use strum_macros::AsRefStr;
 
+use crate::config::extractor::DefaultSort;
+use crate::model::log_export_request::LogExportRequest;
 use crate::model::log_request::LogRequest;
+use crate::model::metrics::MetricsSnapshot;
 use crate::model::port_forward_request::PortForwardRequest;
 use crate::model::resource::resource_view::ResourceView;
 use crate::ui::dispatcher::DispatchContext;
 use crate::ui::ui_store::UiStore;
+use crate::ui::view_meta::TimestampFormat;
 
 #[derive(Debug)]
 pub(crate) enum ToBackendSignal {
@@ -34,6 +39,7 @@ pub(crate) enum FromBackendSignal {
     ResourceDeleted(ResourceView),
     DiscoveredGvks(Vec<GroupVersionKind>),
     PortForwardingStarted(Arc<PortForwardRequest>),
+    MetricsUpdated(MetricsSnapshot),
 }
 
 #[derive(AsRefStr)]
@@ -43,16 +49,32 @@ pub(crate) enum InterUiSignal {
     LogsApplyTailLines(usize, usize),
     LogsApplyTimestamps(usize, bool),
     LogsApplyPrevious(usize, bool),
+    LogsApplySinceTime(usize, Option<DateTime<Utc>>),
+    LogsApplyUntilTime(usize, Option<DateTime<Utc>>),
+    LogsApplyTimestampFormat(usize, TimestampFormat),
+    LogsJumpToTime(usize, Option<DateTime<Utc>>),
+    CodeApplyRenderProfile(usize, String),
+    LogsExport(LogExportRequest),
 
     ApplyNamespaceFilter(usize, String),
     ApplyNameFilter(usize, String),
+    ListSortChanged(GroupVersionKind, DefaultSort),
+
+    StartPortForwardProfile(String),
+    SavePortForwardProfile(String),
 
     ShowGvk(GroupVersionKind),
     ShowDetails(ResourceView),
+    ShowYaml(ResourceView),
 
     UpdateListViewForGvk(GroupVersionKind, bool),
     ReplaceTableItems(usize),
     ShowWindow(usize),
+    ShowConfigErrors,
+    /// The configs were reloaded and the list of config errors changed.
+    ConfigErrorsChanged,
+    SwitchTheme(String),
+    ShowEvalProfiler,
 
     Chain(Vec<Box<ToUiChainDispatch>>),
 
@@ -65,6 +87,11 @@ pub(crate) enum InterUiSignal {
     CtrlYPressed,
     CtrlSlashPressed,
     CtrlPPressed,
+    CtrlEPressed,
+    CtrlDPressed,
+    CtrlRPressed,
+    CtrlTPressed,
+    CtrlOPressed,
     F5Pressed,
     EscPressed,
     ShowDebugLog,
@@ -1,34 +1,50 @@
-use std::borrow::Cow;
 use std::ops::DerefMut;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::config::detail_templates::DetailTemplates;
+use crate::config::extractor::{ConfigError, ExtractorConfig};
+use crate::config::kgv_configuration::LogDefaults;
+use crate::config::list_sort_state::ListSortState;
+use crate::config::render_profiles::RenderProfiles;
 use crate::model::port_forward_request::PortForwardRequest;
 use cursive::reexports::crossbeam_channel::Sender;
 use cursive::reexports::log::warn;
 use cursive::theme::Style;
 use cursive::utils::markup::StyledString;
+use cursive::views::SelectView;
 use cursive::Cursive;
 use cursive_cached_text_view::CachedTextView;
 use itertools::Itertools;
 use k8s_openapi::serde_json;
 use kube::api::GroupVersionKind;
+use rhai::Engine;
 
+use crate::eval::engine_factory::compile_modules;
+use crate::eval::profiler::SharedEvalProfiler;
 use crate::model::resource::resource_view::{EvaluatedResource, ResourceView};
 use crate::model::traits::SerializeExt;
+use crate::reexports::k8s::{CoreEvent, Event};
 use crate::reexports::sync::{Mutex, RwLock};
 use crate::traits::ext::cursive::SivExt;
-use crate::traits::ext::gvk::GvkNameExt;
+use crate::traits::ext::gvk::{GvkNameExt, GvkStaticExt};
+use crate::traits::ext::kanal_sender::KanalSenderExt;
 use crate::traits::ext::mutex::MutexExt;
 use crate::traits::ext::rw_lock::RwLockExt;
+use crate::ui::components::port_forwarding_view::{fill_items, PORT_FORWARDING_DIALOG_NAME};
 use crate::ui::detail_view_renderer::DetailViewRenderer;
 use crate::ui::interactive_command::InteractiveCommand;
 use crate::ui::resource_manager::ResourceManager;
 use crate::ui::signals::{InterUiSignal, ToBackendSignal};
-use crate::ui::view_meta::{ListViewFilter, ViewMeta};
+use crate::ui::view_meta::{ListViewFilter, TimestampFormat, ViewMeta};
 use crate::ui::view_stack::ViewStack;
 use crate::util::panics::ResultExt;
 use crate::util::view_with_data::ViewWithMeta;
+use crate::util::watcher::LazyWatcher;
+
+const RELATIVE_TIMESTAMP_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+const PORT_FORWARD_STATS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
 
 pub(crate) type SinkSender = Sender<Box<dyn FnOnce(&mut Cursive) + Send>>;
 
@@ -49,11 +65,46 @@ pub(crate) struct UiStore {
     pub(crate) detail_view_renderer: DetailViewRenderer,
     pub(crate) gvks: Vec<GroupVersionKind>,
     pub(crate) pf_requests: Vec<Arc<PortForwardRequest>>,
+    pub(crate) pf_profiles_path: PathBuf,
+    pub(crate) render_profiles: Arc<RenderProfiles>,
+    pub(crate) sort_state: ListSortState,
+    pub(crate) sort_state_path: PathBuf,
+    /// Errors of the latest extractor configs and rhai modules.
+    pub(crate) config_errors: Arc<Vec<ConfigError>>,
+    pub(crate) log_defaults: LogDefaults,
+    pub(crate) theme_dirs: Vec<PathBuf>,
+    /// The theme chosen in the menu or in config.yaml.
+    pub(crate) theme_name: String,
+    pub(crate) eval_profiler: SharedEvalProfiler,
+    /// Whether the event reflectors of the events panes were requested.
+    pub(crate) events_registered: bool,
 }
 
 impl UiStore {
-    pub(crate) fn highlight(&self, resource: &ResourceView) -> anyhow::Result<StyledString> {
-        let yaml = resource.to_yaml()?;
+    /// Events are collected only when there are reflectors for them; both APIs serve the
+    /// same events, they are deduplicated by uid.
+    pub(crate) fn register_event_gvks(&mut self) -> anyhow::Result<()> {
+        if self.events_registered {
+            return Ok(());
+        }
+        for gvk in [CoreEvent::gvk_for_type(), Event::gvk_for_type()] {
+            self.to_backend_sender
+                .send(ToBackendSignal::RegisterGvk(gvk))?;
+        }
+        self.events_registered = true;
+        Ok(())
+    }
+
+    pub(crate) fn highlight(
+        &self,
+        resource: &ResourceView,
+        render_profile: &str,
+    ) -> anyhow::Result<StyledString> {
+        let mut yaml = resource.to_yaml()?;
+        match self.render_profiles.get(render_profile) {
+            Some(profile) => yaml = profile.apply(yaml)?,
+            None => warn!("Render profile {render_profile} was not found"),
+        }
         self.highlighter.highlight(&yaml, "yaml")
     }
 
@@ -111,6 +162,14 @@ pub(crate) trait UiStoreDispatcherExt {
     fn inc_counter(&self) -> usize;
     fn register_view(&self, view_meta: &ViewWithMeta<ViewMeta>);
     fn spawn_log_updater_thread(&self);
+    fn spawn_tombstone_reaper_thread(&self);
+    fn spawn_port_forward_stats_thread(&self);
+    fn spawn_config_status_thread(
+        &self,
+        extractor_config_watcher: Arc<LazyWatcher<ExtractorConfig>>,
+        engine_watcher: Arc<LazyWatcher<Engine>>,
+        detail_templates_watcher: Arc<LazyWatcher<DetailTemplates>>,
+    );
 }
 
 impl UiStoreDispatcherExt for Arc<Mutex<UiStore>> {
@@ -130,84 +189,227 @@ impl UiStoreDispatcherExt for Arc<Mutex<UiStore>> {
 
         std::thread::Builder::new()
             .name("log-updater".to_string())
-            .spawn(move || loop {
-                let (sink, log_views, highlighter) = store.get_locking(|store| {
-                    (
-                        store.sink.clone(),
-                        store.view_stack.find_logs(),
-                        Arc::clone(&store.highlighter),
-                    )
-                });
+            .spawn(move || {
+                let mut relative_rendered_at = Instant::now();
+                loop {
+                    let (sink, log_views, highlighter) = store.get_locking(|store| {
+                        (
+                            store.sink.clone(),
+                            store.view_stack.find_logs(),
+                            Arc::clone(&store.highlighter),
+                        )
+                    });
 
-                for log_view in log_views {
-                    let mut log_view = log_view.write().unwrap_or_log();
-
-                    match log_view.deref_mut() {
-                        ViewMeta::Logs {
-                            filter,
-                            log_items,
-                            next_index,
-                            ..
-                        } => {
-                            let mut highlighted_lines = vec![];
-                            let should_clear = *next_index == 0;
-                            for log_item in log_items.iter().skip(*next_index) {
-                                if log_item.is_placeholder {
-                                    break;
+                    // relative timestamps ("3s ago") are rendered again from time to time
+                    let rerender_relative =
+                        relative_rendered_at.elapsed() >= RELATIVE_TIMESTAMP_REFRESH_INTERVAL;
+                    if rerender_relative {
+                        relative_rendered_at = Instant::now();
+                    }
+
+                    for log_view in log_views {
+                        let mut log_view = log_view.write().unwrap_or_log();
+
+                        match log_view.deref_mut() {
+                            ViewMeta::Logs {
+                                filter,
+                                log_items,
+                                next_index,
+                                rendered_rows,
+                                ..
+                            } => {
+                                if rerender_relative
+                                    && filter.show_timestamps
+                                    && filter.timestamp_format == TimestampFormat::Relative
+                                {
+                                    *next_index = 0;
+                                    rendered_rows.clear();
                                 }
 
-                                let mut line = StyledString::new();
+                                // older lines are skipped like the ones out of the time range
+                                let first_shown = filter.first_shown_index(log_items.len());
+
+                                let mut highlighted_lines = vec![];
+                                let mut num_processed = 0;
+                                let should_clear = *next_index == 0;
+                                for (index, log_item) in
+                                    log_items.iter().enumerate().skip(*next_index)
+                                {
+                                    if log_item.is_placeholder {
+                                        break;
+                                    }
+                                    num_processed += 1;
+
+                                    if index < first_shown || !filter.is_in_time_range(log_item) {
+                                        rendered_rows.push(0);
+                                        continue;
+                                    }
 
-                                if filter.show_timestamps {
-                                    line.append(StyledString::styled(
-                                        format!("{} ", log_item.timestamp),
-                                        Style::secondary(),
-                                    ));
+                                    let mut line = StyledString::new();
+
+                                    if filter.show_timestamps {
+                                        line.append(StyledString::styled(
+                                            format!(
+                                                "{} ",
+                                                log_item.format_timestamp(filter.timestamp_format)
+                                            ),
+                                            Style::secondary(),
+                                        ));
+                                    }
+
+                                    let prettified_line = log_item.prettified_value();
+
+                                    rendered_rows
+                                        .push(prettified_line.matches('\n').count().max(1));
+
+                                    line.append(
+                                        highlighter
+                                            .highlight_substring(
+                                                &prettified_line,
+                                                &filter.value,
+                                                "js",
+                                            )
+                                            .unwrap_or_log(),
+                                    );
+                                    highlighted_lines.push(line);
                                 }
 
-                                let prettified_line = if let Ok(Ok(mut line)) =
-                                    serde_json::from_str::<serde_json::Value>(&log_item.value)
-                                        .map(|value| serde_json::to_string_pretty(&value))
+                                *next_index += num_processed;
+
+                                if num_processed == 0
+                                    || (highlighted_lines.is_empty() && !should_clear)
                                 {
-                                    line.push('\n');
-                                    Cow::Owned(line)
-                                } else {
-                                    Cow::Borrowed(&log_item.value)
-                                };
-
-                                line.append(
-                                    highlighter
-                                        .highlight_substring(&prettified_line, &filter.value, "js")
-                                        .unwrap_or_log(),
+                                    continue;
+                                }
+
+                                warn!("Going to render {} items", highlighted_lines.len());
+
+                                sink.call_on_name(
+                                    &log_view.get_unique_name(),
+                                    move |tv: &mut CachedTextView| {
+                                        if should_clear {
+                                            tv.set_content("");
+                                        }
+                                        for h in highlighted_lines {
+                                            tv.append(h);
+                                        }
+                                    },
                                 );
-                                highlighted_lines.push(line);
                             }
+                            _ => continue,
+                        }
+                    }
 
-                            if highlighted_lines.is_empty() {
-                                continue;
-                            }
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            })
+            .unwrap_or_log();
+    }
+
+    fn spawn_tombstone_reaper_thread(&self) {
+        let store = Arc::clone(self);
 
-                            *next_index += highlighted_lines.len();
+        std::thread::Builder::new()
+            .name("tombstone-reaper".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(Duration::from_secs(10));
 
-                            warn!("Going to render {} items", highlighted_lines.len());
+                let (resource_manager, sender) = store.get_locking(|store| {
+                    (
+                        Arc::clone(&store.resource_manager),
+                        store.inter_ui_sender.clone(),
+                    )
+                });
 
-                            sink.call_on_name(
-                                &log_view.get_unique_name(),
-                                move |tv: &mut CachedTextView| {
-                                    if should_clear {
-                                        tv.set_content("");
-                                    }
-                                    for h in highlighted_lines {
-                                        tv.append(h);
-                                    }
-                                },
-                            );
+                let gvks = resource_manager
+                    .write_sync()
+                    .unwrap_or_log()
+                    .purge_tombstones();
+                for gvk in gvks {
+                    sender.send_unwrap(InterUiSignal::UpdateListViewForGvk(gvk, false));
+                }
+            })
+            .unwrap_or_log();
+    }
+
+    fn spawn_port_forward_stats_thread(&self) {
+        let store = Arc::clone(self);
+
+        std::thread::Builder::new()
+            .name("port-forward-stats".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(PORT_FORWARD_STATS_REFRESH_INTERVAL);
+
+                let (sink, dialogs, pf_requests) = store.get_locking(|store| {
+                    (
+                        store.sink.clone(),
+                        store
+                            .view_stack
+                            .find_dialogs_by_name(PORT_FORWARDING_DIALOG_NAME),
+                        store.pf_requests.clone(),
+                    )
+                });
+
+                for dialog in dialogs {
+                    let pf_requests = pf_requests.clone();
+                    sink.call_on_name(
+                        &dialog.read_unwrap().get_unique_name(),
+                        move |sv: &mut SelectView<Arc<PortForwardRequest>>| {
+                            fill_items(sv, pf_requests);
+                        },
+                    );
+                }
+            })
+            .unwrap_or_log();
+    }
+
+    fn spawn_config_status_thread(
+        &self,
+        extractor_config_watcher: Arc<LazyWatcher<ExtractorConfig>>,
+        engine_watcher: Arc<LazyWatcher<Engine>>,
+        detail_templates_watcher: Arc<LazyWatcher<DetailTemplates>>,
+    ) {
+        let store = Arc::clone(self);
+
+        std::thread::Builder::new()
+            .name("config-status".to_string())
+            .spawn(move || {
+                let mut module_errors = vec![];
+                let mut engine_version = None;
+                loop {
+                    // reloads the configs, the engine and the templates if they were edited
+                    let extractor_config = extractor_config_watcher.value();
+                    let detail_templates = detail_templates_watcher.value();
+                    engine_watcher.value();
+
+                    let version = engine_watcher.version();
+                    if engine_version != Some(version) {
+                        module_errors = compile_modules(engine_watcher.watch_paths());
+                        engine_version = Some(version);
+                    }
+
+                    let errors = extractor_config
+                        .errors
+                        .iter()
+                        .chain(module_errors.iter())
+                        .chain(detail_templates.errors.iter())
+                        .cloned()
+                        .chain(extractor_config.missing_helpers(&detail_templates))
+                        .collect::<Vec<_>>();
+
+                    let (changed, sender) = store.get_locking(|mut store| {
+                        let changed = *store.config_errors != errors;
+                        if changed {
+                            store.config_errors = Arc::new(errors);
                         }
-                        _ => continue,
+                        (changed, store.inter_ui_sender.clone())
+                    });
+                    if changed {
+                        sender.send_unwrap(InterUiSignal::ConfigErrorsChanged);
                     }
-                }
 
-                std::thread::sleep(Duration::from_millis(100));
+                    std::thread::sleep(Duration::from_secs(1));
+                }
             })
             .unwrap_or_log();
     }
@@ -1,11 +1,18 @@
-use chrono::FixedOffset;
+use std::borrow::Cow;
+use std::ops::Range;
+
+use chrono::{FixedOffset, Utc};
 use kube::api::GroupVersionKind;
 use strum_macros::AsRefStr;
 
 use crate::model::log_request::LogRequest;
-use crate::traits::ext::gvk::GvkNameExt;
+use crate::traits::ext::gvk::{GvkNameExt, PseudoResourceGvkExt};
 use crate::util::error::{LogError, LogErrorOptionExt, LogErrorResultExt};
 use crate::util::panics::OptionExt;
+use crate::util::ui::format_timestamp;
+
+/// Relative timestamps are rendered again on each refresh, so only the most recent lines are shown.
+const RELATIVE_TIMESTAMP_MAX_LINES: usize = 1000;
 
 #[derive(Debug, Default, Clone, Hash)]
 pub(crate) struct ListViewFilter {
@@ -19,10 +26,57 @@ impl ListViewFilter {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, AsRefStr)]
+pub(crate) enum TimestampFormat {
+    #[default]
+    Original,
+    Utc,
+    Local,
+    Relative,
+}
+
+impl TimestampFormat {
+    pub(crate) fn all() -> [Self; 4] {
+        [Self::Original, Self::Utc, Self::Local, Self::Relative]
+    }
+}
+
 #[derive(Debug, Default, Clone, Hash)]
 pub(crate) struct LogFilter {
     pub(crate) value: String,
     pub(crate) show_timestamps: bool,
+    pub(crate) timestamp_format: TimestampFormat,
+    pub(crate) since_time: Option<chrono::DateTime<Utc>>,
+    pub(crate) until_time: Option<chrono::DateTime<Utc>>,
+    /// `since_seconds` of the request before `since_time` narrowed it, restored once
+    /// `since_time` is cleared.
+    pub(crate) since_seconds_before_since_time: Option<Option<i64>>,
+}
+
+impl LogFilter {
+    pub(crate) fn is_in_time_range(&self, log_item: &LogItem) -> bool {
+        if let Some(since_time) = self.since_time {
+            if log_item.timestamp < since_time {
+                return false;
+            }
+        }
+        if let Some(until_time) = self.until_time {
+            if log_item.timestamp > until_time {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The index of the first log item a view shows, older ones are skipped with relative
+    /// timestamps.
+    pub(crate) fn first_shown_index(&self, num_log_items: usize) -> usize {
+        if self.show_timestamps && self.timestamp_format == TimestampFormat::Relative {
+            num_log_items.saturating_sub(RELATIVE_TIMESTAMP_MAX_LINES)
+        } else {
+            0
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -50,6 +104,36 @@ impl LogItem {
             is_placeholder: false,
         })
     }
+
+    pub(crate) fn format_timestamp(&self, format: TimestampFormat) -> String {
+        format_timestamp(&self.timestamp, format)
+    }
+
+    /// The value as a log view renders it, JSON values are pretty-printed.
+    pub(crate) fn prettified_value(&self) -> Cow<str> {
+        if let Ok(Ok(mut value)) = serde_json::from_str::<serde_json::Value>(&self.value)
+            .map(|value| serde_json::to_string_pretty(&value))
+        {
+            value.push('\n');
+            Cow::Owned(value)
+        } else {
+            Cow::Borrowed(&self.value)
+        }
+    }
+
+    pub(crate) fn to_line(&self, filter: &LogFilter, as_shown: bool) -> String {
+        let value = if as_shown {
+            self.prettified_value()
+        } else {
+            Cow::Borrowed(self.value.as_str())
+        };
+        let value = value.trim_end_matches('\n');
+        if filter.show_timestamps {
+            format!("{} {value}", self.format_timestamp(filter.timestamp_format))
+        } else {
+            value.to_string()
+        }
+    }
 }
 
 #[derive(Debug, AsRefStr)]
@@ -70,6 +154,7 @@ pub(crate) enum ViewMeta {
         gvk: GroupVersionKind,
         title: String,
         uid: String,
+        render_profile: String,
     },
     Logs {
         id: usize,
@@ -77,11 +162,21 @@ pub(crate) enum ViewMeta {
         request: LogRequest,
         log_items: Vec<LogItem>,
         next_index: usize,
+        /// The number of rendered rows for each log item before `next_index`; used to find
+        /// the scroll offset of a given log item.
+        rendered_rows: Vec<usize>,
     },
     Dialog {
         id: usize,
         name: String,
     },
+    /// Resources related through `ownerReferences` to the resource with the given uid.
+    Tree {
+        id: usize,
+        gvk: GroupVersionKind,
+        name: String,
+        uid: String,
+    },
     WindowSwitcher {
         id: usize,
     },
@@ -95,7 +190,8 @@ impl ViewMeta {
         let title = format!("{: >4} {: >7}", self.get_id(), self.as_ref());
         let unique_part = match self {
             ViewMeta::List { gvk, filter, .. } => {
-                let mut repr = gvk.full_name();
+                // pseudo resource tables show the drill-down path instead of the joined kind
+                let mut repr = gvk.breadcrumb().unwrap_or_else(|| gvk.full_name());
                 if !filter.is_empty() {
                     repr.push_str(" (");
                     if !filter.namespace.is_empty() {
@@ -115,6 +211,9 @@ impl ViewMeta {
                 format!("{} {title}", gvk.full_name())
             }
             ViewMeta::Dialog { name, .. } => name.clone(),
+            ViewMeta::Tree { gvk, name, .. } => {
+                format!("{} {name}", gvk.full_name())
+            }
             ViewMeta::WindowSwitcher { .. } => "Window Switcher".to_string(),
             ViewMeta::GvkSwitcher { .. } => "Gvk Switcher".to_string(),
             ViewMeta::Logs {
@@ -147,6 +246,9 @@ impl ViewMeta {
                 format!("gvk-code-view-{id}-{}-{uid}", gvk.full_name())
             }
             ViewMeta::Dialog { id, name } => format!("dialog-{id}-{name}"),
+            ViewMeta::Tree { id, gvk, uid, .. } => {
+                format!("gvk-tree-{id}-{}-{uid}", gvk.full_name())
+            }
             ViewMeta::WindowSwitcher { id } => format!("window-switcher-list-{id}"),
             ViewMeta::GvkSwitcher { id } => format!("gvk-switcher-list-{id}"),
             ViewMeta::Logs { id, .. } => format!("logs-{id}"),
@@ -157,6 +259,10 @@ impl ViewMeta {
         matches!(self, ViewMeta::List { .. })
     }
 
+    pub(crate) fn is_tree(&self) -> bool {
+        matches!(self, ViewMeta::Tree { .. })
+    }
+
     pub(crate) fn get_uid(&self) -> Option<String> {
         match self {
             ViewMeta::Details { uid, .. } | ViewMeta::Code { uid, .. } => Some(uid.to_string()),
@@ -172,10 +278,36 @@ impl ViewMeta {
         format!("{}-checkbox-{checkbox_type}", self.get_unique_name())
     }
 
+    pub(crate) fn get_select_name(&self, select_type: &str) -> String {
+        format!("{}-select-{select_type}", self.get_unique_name())
+    }
+
     pub(crate) fn get_panel_name(&self) -> String {
         format!("{}-panel", self.get_unique_name())
     }
 
+    pub(crate) fn get_events_name(&self) -> String {
+        format!("{}-events", self.get_unique_name())
+    }
+
+    pub(crate) fn get_scroll_name(&self) -> String {
+        format!("{}-scroll", self.get_unique_name())
+    }
+
+    pub(crate) fn get_render_profile(&self) -> Option<&str> {
+        match self {
+            ViewMeta::Code { render_profile, .. } => Some(render_profile),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_render_profile(&mut self, name: String) {
+        match self {
+            ViewMeta::Code { render_profile, .. } => *render_profile = name,
+            this => panic!("Setting render profile {name} on {:?}", this),
+        }
+    }
+
     pub(crate) fn set_namespace(&mut self, namespace: String) {
         match self {
             ViewMeta::List { filter, .. } => filter.namespace = namespace,
@@ -195,6 +327,7 @@ impl ViewMeta {
             Self::List { id, .. }
             | Self::Details { id, .. }
             | Self::Dialog { id, .. }
+            | Self::Tree { id, .. }
             | Self::Code { id, .. }
             | Self::GvkSwitcher { id, .. }
             | Self::WindowSwitcher { id }
@@ -213,7 +346,8 @@ impl ViewMeta {
         match self {
             ViewMeta::List { gvk, .. }
             | ViewMeta::Code { gvk, .. }
-            | ViewMeta::Details { gvk, .. } => gvk,
+            | ViewMeta::Details { gvk, .. }
+            | ViewMeta::Tree { gvk, .. } => gvk,
             this => panic!("{:?} does not have GVK", this),
         }
     }
@@ -230,6 +364,12 @@ pub(crate) trait ViewMetaLogExt {
     fn set_log_show_previous(&mut self, show_previous: bool);
     fn set_log_search_text(&mut self, text: String);
     fn set_log_show_timestamps(&mut self, show: bool);
+    fn set_log_timestamp_format(&mut self, format: TimestampFormat);
+    fn set_log_since_time(&mut self, since_time: Option<chrono::DateTime<Utc>>) -> bool;
+    fn set_log_until_time(&mut self, until_time: Option<chrono::DateTime<Utc>>);
+    fn get_log_lines(&self, as_shown: bool, range: Option<Range<usize>>) -> Vec<String>;
+    fn get_log_line_range(&self, rows: Range<usize>, as_shown: bool) -> Option<Range<usize>>;
+    fn get_log_row_offset(&self, instant: chrono::DateTime<Utc>) -> Option<usize>;
 }
 
 impl ViewMetaLogExt for ViewMeta {
@@ -243,9 +383,13 @@ impl ViewMetaLogExt for ViewMeta {
     fn get_log_filter_clearing_mut(&mut self) -> &mut LogFilter {
         match self {
             ViewMeta::Logs {
-                filter, next_index, ..
+                filter,
+                next_index,
+                rendered_rows,
+                ..
             } => {
                 *next_index = 0;
+                rendered_rows.clear();
                 filter
             }
             this => panic!("{:?} is not Logs", this),
@@ -265,10 +409,12 @@ impl ViewMetaLogExt for ViewMeta {
                 request,
                 log_items,
                 next_index,
+                rendered_rows,
                 ..
             } => {
                 *next_index = 0;
                 log_items.clear();
+                rendered_rows.clear();
                 request
             }
             this => panic!("{:?} is not Logs", this),
@@ -294,6 +440,9 @@ impl ViewMetaLogExt for ViewMeta {
         if self.get_log_request().log_params.since_seconds == num_seconds {
             return;
         }
+        // an explicit range wins over the one narrowed by `since_time`
+        self.get_log_filter_clearing_mut()
+            .since_seconds_before_since_time = None;
         self.get_log_request_clearing_mut().log_params.since_seconds = num_seconds;
     }
 
@@ -325,4 +474,124 @@ impl ViewMetaLogExt for ViewMeta {
         }
         self.get_log_filter_clearing_mut().show_timestamps = show;
     }
+
+    fn set_log_timestamp_format(&mut self, format: TimestampFormat) {
+        if self.get_log_filter().timestamp_format == format {
+            return;
+        }
+        self.get_log_filter_clearing_mut().timestamp_format = format;
+    }
+
+    /// Returns `true` if the log stream must be re-requested to cover the new time range.
+    fn set_log_since_time(&mut self, since_time: Option<chrono::DateTime<Utc>>) -> bool {
+        if self.get_log_filter().since_time == since_time {
+            return false;
+        }
+        self.get_log_filter_clearing_mut().since_time = since_time;
+
+        // LogParams in this kube version has no since_time, so we request a bit more
+        // and cut the rest on our side
+        match since_time {
+            Some(since_time) => {
+                let num_seconds = (Utc::now() - since_time).num_seconds().max(1);
+                let since_seconds = self.get_log_request().log_params.since_seconds;
+                self.get_log_filter_clearing_mut()
+                    .since_seconds_before_since_time
+                    .get_or_insert(since_seconds);
+                self.get_log_request_clearing_mut().log_params.since_seconds = Some(num_seconds);
+                true
+            }
+            None => {
+                let since_seconds = self
+                    .get_log_filter_clearing_mut()
+                    .since_seconds_before_since_time
+                    .take();
+                match since_seconds {
+                    Some(since_seconds) => {
+                        self.get_log_request_clearing_mut().log_params.since_seconds =
+                            since_seconds;
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    fn set_log_until_time(&mut self, until_time: Option<chrono::DateTime<Utc>>) {
+        if self.get_log_filter().until_time == until_time {
+            return;
+        }
+        self.get_log_filter_clearing_mut().until_time = until_time;
+    }
+
+    fn get_log_lines(&self, as_shown: bool, range: Option<Range<usize>>) -> Vec<String> {
+        match self {
+            ViewMeta::Logs {
+                filter, log_items, ..
+            } => {
+                let range = range.unwrap_or(0..usize::MAX);
+                let first_shown = filter.first_shown_index(log_items.len());
+                log_items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, log_item)| !log_item.is_placeholder)
+                    .filter(|(index, log_item)| {
+                        !as_shown || (*index >= first_shown && filter.is_in_time_range(log_item))
+                    })
+                    .enumerate()
+                    .filter(|(index, _)| range.contains(index))
+                    .map(|(_, (_, log_item))| log_item.to_line(filter, as_shown))
+                    .collect()
+            }
+            this => panic!("{:?} is not Logs", this),
+        }
+    }
+
+    /// The range of exported lines rendered in the given rows of the view.
+    fn get_log_line_range(&self, rows: Range<usize>, as_shown: bool) -> Option<Range<usize>> {
+        match self {
+            ViewMeta::Logs {
+                log_items,
+                rendered_rows,
+                ..
+            } => {
+                let mut row = 0;
+                let mut line = 0;
+                let mut lines: Option<Range<usize>> = None;
+                for (log_item, num_rows) in log_items.iter().zip(rendered_rows) {
+                    let is_shown = *num_rows > 0;
+                    if !log_item.is_placeholder && (is_shown || !as_shown) {
+                        if is_shown && row + num_rows > rows.start && row < rows.end {
+                            let start = lines.map_or(line, |lines| lines.start);
+                            lines = Some(start..line + 1);
+                        }
+                        line += 1;
+                    }
+                    row += num_rows;
+                }
+                lines
+            }
+            this => panic!("{:?} is not Logs", this),
+        }
+    }
+
+    fn get_log_row_offset(&self, instant: chrono::DateTime<Utc>) -> Option<usize> {
+        match self {
+            ViewMeta::Logs {
+                log_items,
+                rendered_rows,
+                ..
+            } => {
+                let index = log_items
+                    .iter()
+                    .take(rendered_rows.len())
+                    .position(|log_item| {
+                        !log_item.is_placeholder && log_item.timestamp >= instant
+                    })?;
+                Some(rendered_rows.iter().take(index).sum())
+            }
+            this => panic!("{:?} is not Logs", this),
+        }
+    }
 }
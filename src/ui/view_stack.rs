@@ -30,6 +30,30 @@ impl ViewStack {
             .collect()
     }
 
+    pub(crate) fn find_code_views(&self) -> Vec<Arc<RwLock<ViewMeta>>> {
+        self.stack
+            .iter()
+            .filter(|view| matches!(view.read_unwrap().deref(), ViewMeta::Code { .. }))
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn find_dialogs(&self) -> Vec<Arc<RwLock<ViewMeta>>> {
+        self.stack
+            .iter()
+            .filter(|view| matches!(view.read_unwrap().deref(), ViewMeta::Dialog { .. }))
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn find_trees(&self) -> Vec<Arc<RwLock<ViewMeta>>> {
+        self.stack
+            .iter()
+            .filter(|view| matches!(view.read_unwrap().deref(), ViewMeta::Tree { .. }))
+            .cloned()
+            .collect()
+    }
+
     pub(crate) fn find_all_by_gvk(&self, gvk: &GroupVersionKind) -> Vec<Arc<RwLock<ViewMeta>>> {
         self.stack
             .iter()
@@ -46,6 +70,14 @@ impl ViewStack {
             .collect()
     }
 
+    pub(crate) fn find_all_by_uid(&self, uid: &str) -> Vec<Arc<RwLock<ViewMeta>>> {
+        self.stack
+            .iter()
+            .filter(|view| view.read_unwrap().get_uid().as_deref() == Some(uid))
+            .cloned()
+            .collect()
+    }
+
     pub(crate) fn find_window_switchers(&self) -> Vec<Arc<RwLock<ViewMeta>>> {
         self.stack
             .iter()
@@ -54,6 +86,16 @@ impl ViewStack {
             .collect()
     }
 
+    pub(crate) fn find_dialogs_by_name(&self, name: &str) -> Vec<Arc<RwLock<ViewMeta>>> {
+        self.stack
+            .iter()
+            .filter(|view| {
+                matches!(view.read_unwrap().deref(), ViewMeta::Dialog { name: self_name, .. } if self_name == name)
+            })
+            .cloned()
+            .collect()
+    }
+
     pub(crate) fn find_gvk_switchers(&self) -> Vec<Arc<RwLock<ViewMeta>>> {
         self.stack
             .iter()
@@ -0,0 +1,221 @@
+use serde_yaml::Value;
+
+use crate::util::yaml::remove_path;
+
+/// Fields that change without any meaningful modification of a resource.
+const NOISY_PATHS: &[&[&str]] = &[
+    &["metadata", "managedFields"],
+    &["metadata", "resourceVersion"],
+    &["status", "conditions", "*", "lastHeartbeatTime"],
+    &["status", "conditions", "*", "lastProbeTime"],
+];
+
+/// The largest LCS table of the changed middle sections, 16 MB of `u32` cells; larger
+/// sections are diffed as a whole-block replacement.
+const MAX_LCS_CELLS: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Parses a YAML document and drops the fields listed in [`NOISY_PATHS`].
+pub(crate) fn strip_noisy_fields(yaml: &str) -> anyhow::Result<String> {
+    let mut value: Value = serde_yaml::from_str(yaml)?;
+    for path in NOISY_PATHS {
+        remove_path(&mut value, path);
+    }
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Longest common subsequence of lines; common prefix and suffix are skipped to keep the
+/// table small for typical resource updates. Middle sections too large for the table (see
+/// [`MAX_LCS_CELLS`]) are replaced as a whole.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    let (n, m) = (old_mid.len(), new_mid.len());
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|i| DiffOp::Equal(i, i)).collect();
+    if (n + 1).saturating_mul(m + 1) > MAX_LCS_CELLS {
+        ops.extend((0..n).map(|i| DiffOp::Delete(prefix + i)));
+        ops.extend((0..m).map(|j| DiffOp::Insert(prefix + j)));
+        ops.extend((0..suffix).map(|k| DiffOp::Equal(prefix + n + k, prefix + m + k)));
+        return ops;
+    }
+
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            ops.push(DiffOp::Equal(prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            ops.push(DiffOp::Insert(prefix + j));
+            j += 1;
+        } else {
+            ops.push(DiffOp::Delete(prefix + i));
+            i += 1;
+        }
+    }
+    ops.extend((0..suffix).map(|k| DiffOp::Equal(prefix + n + k, prefix + m + k)));
+
+    ops
+}
+
+/// Renders a unified diff with `context` lines around every change. Returns an empty string
+/// if there are no changes.
+pub(crate) fn unified_diff(
+    old: &str,
+    new: &str,
+    old_name: &str,
+    new_name: &str,
+    context: usize,
+) -> String {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let changed = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, DiffOp::Equal(..)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // group changes that are close enough to share the context into hunks
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for index in changed {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut result = format!("--- {old_name}\n+++ {new_name}\n");
+    for (start, end) in hunks {
+        let hunk = &ops[start..end];
+        let old_start = hunk.iter().find_map(|op| match op {
+            DiffOp::Equal(i, _) | DiffOp::Delete(i) => Some(*i),
+            DiffOp::Insert(_) => None,
+        });
+        let new_start = hunk.iter().find_map(|op| match op {
+            DiffOp::Equal(_, j) | DiffOp::Insert(j) => Some(*j),
+            DiffOp::Delete(_) => None,
+        });
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count();
+
+        result.push_str(&format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            old_start.map(|i| i + 1).unwrap_or(0),
+            new_start.map(|j| j + 1).unwrap_or(0),
+        ));
+
+        for op in hunk {
+            let line = match op {
+                DiffOp::Equal(i, _) => format!(" {}", old_lines[*i]),
+                DiffOp::Delete(i) => format!("-{}", old_lines[*i]),
+                DiffOp::Insert(j) => format!("+{}", new_lines[*j]),
+            };
+            result.push_str(&line);
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nD\ne\nf\ng\nh\ni\n";
+
+        let diff = unified_diff(old, new, "old", "new", 1);
+
+        assert_eq!(
+            diff,
+            "--- old\n+++ new\n@@ -3,3 +3,3 @@\n c\n-d\n+D\n e\n@@ -8,1 +8,2 @@\n h\n+i\n"
+        );
+        assert!(unified_diff(old, old, "old", "new", 3).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines_replaces_large_sections() {
+        let old = (0..3000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let new = (0..3000).map(|i| (i * 2).to_string()).collect::<Vec<_>>();
+        let old = old.iter().map(String::as_str).collect::<Vec<_>>();
+        let new = new.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let ops = diff_lines(&old, &new);
+
+        // only the first line is common
+        assert_eq!(ops[0], DiffOp::Equal(0, 0));
+        assert!(ops[1..2999]
+            .iter()
+            .all(|op| matches!(op, DiffOp::Delete(_))));
+        assert!(ops[2999..].iter().all(|op| matches!(op, DiffOp::Insert(_))));
+        assert_eq!(ops.len(), 1 + 2 * 2999);
+    }
+
+    #[test]
+    fn test_strip_noisy_fields() {
+        let yaml = r#"
+metadata:
+  name: node
+  resourceVersion: "42"
+  managedFields:
+    - manager: kubelet
+status:
+  conditions:
+    - type: Ready
+      lastHeartbeatTime: "2023-05-01T10:00:00Z"
+"#;
+        let stripped = strip_noisy_fields(yaml).unwrap();
+
+        assert!(!stripped.contains("managedFields"));
+        assert!(!stripped.contains("resourceVersion"));
+        assert!(!stripped.contains("lastHeartbeatTime"));
+        assert!(stripped.contains("type: Ready"));
+    }
+}
@@ -15,9 +15,6 @@ pub(crate) enum EvalError {
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum KgvError {
-    #[error("Engine JSON parse error {0}: {1}")]
-    EngineJsonParseError(String, EvalAltResult),
-
     #[error("Type conversion error: {0}")]
     TypeConversionError(String),
 
@@ -41,6 +38,9 @@ pub(crate) enum KgvError {
 
     #[error("Template render error : {0}")]
     TemplateRenderError(#[from] TemplateError),
+
+    #[error("Profile {0} is not found in config.yaml")]
+    UnknownProfile(String),
 }
 
 impl From<&'static str> for KgvError {
@@ -1,6 +1,8 @@
-use kube::api::GroupVersionKind;
+use kube::api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams};
 use kube::discovery::verbs;
-use kube::{Client, Discovery};
+use kube::{Client, Discovery, ResourceExt};
+
+use crate::model::metrics::{pod_key, MetricsSnapshot, ResourceUsage};
 
 pub(crate) fn gvk_sort_key(gvk: &GroupVersionKind) -> (String, String, String) {
     (gvk.group.clone(), gvk.version.clone(), gvk.kind.clone())
@@ -22,3 +24,52 @@ pub(crate) async fn discover_gvk(client: Client) -> anyhow::Result<Vec<GroupVers
     }
     Ok(result)
 }
+
+/// Lists `PodMetrics` and `NodeMetrics` from `metrics.k8s.io/v1beta1`. Fails if the metrics
+/// API is not served, i.e. `metrics-server` is not installed.
+pub(crate) async fn fetch_metrics(client: Client) -> anyhow::Result<MetricsSnapshot> {
+    let mut snapshot = MetricsSnapshot::default();
+
+    let pod_metrics = list_metrics(client.clone(), "PodMetrics", "pods").await?;
+    for pod_metric in pod_metrics {
+        let key = pod_key(
+            &pod_metric.namespace().unwrap_or_default(),
+            &pod_metric.name_any(),
+        );
+        let containers = pod_metric
+            .data
+            .get("containers")
+            .and_then(|containers| containers.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|container| {
+                let name = container.get("name")?.as_str()?.to_string();
+                let usage = ResourceUsage::from_json(container.get("usage")?);
+                Some((name, usage))
+            })
+            .collect();
+        snapshot.pods.insert(key, containers);
+    }
+
+    let node_metrics = list_metrics(client, "NodeMetrics", "nodes").await?;
+    for node_metric in node_metrics {
+        if let Some(usage) = node_metric.data.get("usage") {
+            snapshot
+                .nodes
+                .insert(node_metric.name_any(), ResourceUsage::from_json(usage));
+        }
+    }
+
+    Ok(snapshot)
+}
+
+async fn list_metrics(
+    client: Client,
+    kind: &str,
+    plural: &str,
+) -> anyhow::Result<Vec<DynamicObject>> {
+    let gvk = GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", kind);
+    let api_resource = ApiResource::from_gvk_with_plural(&gvk, plural);
+    let api: Api<DynamicObject> = Api::all_with(client, &api_resource);
+    Ok(api.list(&ListParams::default()).await?.items)
+}
@@ -1,3 +1,4 @@
+pub(crate) mod diff;
 pub(crate) mod error;
 pub(crate) mod fs;
 pub(crate) mod k8s;
@@ -6,3 +7,4 @@ pub(crate) mod paths;
 pub(crate) mod ui;
 pub(crate) mod view_with_data;
 pub(crate) mod watcher;
+pub(crate) mod yaml;
@@ -1,5 +1,10 @@
 use std::collections::BTreeMap;
+use std::io::Write;
 
+use base64::engine::general_purpose;
+use base64::Engine;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use cruet::Inflector;
 use cursive::reexports::log::error;
 use cursive::traits::Nameable;
@@ -7,6 +12,7 @@ use cursive::views::{EditView, NamedView};
 use cursive::Cursive;
 use kube::api::GroupVersionKind;
 
+use crate::ui::view_meta::TimestampFormat;
 use crate::util::k8s::gvk_sort_key;
 use crate::util::panics::{OptionExt, ResultExt};
 
@@ -51,6 +57,84 @@ pub(crate) fn ago(duration: chrono::Duration) -> String {
     }
 }
 
+pub(crate) fn format_timestamp(ts: &DateTime<FixedOffset>, format: TimestampFormat) -> String {
+    match format {
+        TimestampFormat::Original => ts.to_string(),
+        TimestampFormat::Utc => ts
+            .with_timezone(&Utc)
+            .format("%Y-%m-%d %H:%M:%S%.3f")
+            .to_string(),
+        TimestampFormat::Local => ts
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S%.3f")
+            .to_string(),
+        TimestampFormat::Relative => format!("{} ago", ago(Utc::now().signed_duration_since(*ts))),
+    }
+}
+
+/// Parses an instant in one of the forms:
+///  - RFC 3339: `2023-05-01T10:00:00Z`
+///  - local date and time: `2023-05-01 10:00[:00]`
+///  - local time of today: `10:00[:00]`
+///  - relative to `now`: `-15m`, `2h`, `30s`, `1d`
+pub(crate) fn parse_instant(text: &str, now: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+    let text = text.trim();
+
+    if let Ok(ts) = DateTime::parse_from_rfc3339(text) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(text, fmt).ok())
+        .or_else(|| {
+            ["%H:%M:%S", "%H:%M"]
+                .iter()
+                .find_map(|fmt| NaiveTime::parse_from_str(text, fmt).ok())
+                .map(|time| now.with_timezone(&Local).date_naive().and_time(time))
+        });
+    if let Some(naive) = naive {
+        return Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|ts| ts.with_timezone(&Utc))
+            .ok_or_else(|| anyhow::anyhow!("Local time {text} does not exist"));
+    }
+
+    let relative = text.trim_start_matches('-');
+    if relative.len() < 2 {
+        anyhow::bail!("Failed to parse instant: {text}");
+    }
+    let (amount, unit) = relative.split_at(relative.len() - 1);
+    let amount = amount
+        .parse::<i64>()
+        .map_err(|_| anyhow::anyhow!("Failed to parse instant: {text}"))?;
+    let duration = match unit {
+        "s" => chrono::Duration::seconds(amount),
+        "m" => chrono::Duration::minutes(amount),
+        "h" => chrono::Duration::hours(amount),
+        "d" => chrono::Duration::days(amount),
+        _ => anyhow::bail!("Unknown time unit {unit} in {text}"),
+    };
+
+    Ok(now - duration)
+}
+
+pub(crate) fn format_bytes(num_bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = num_bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{num_bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
 pub(crate) fn group_gvks(gvks: Vec<GroupVersionKind>) -> Vec<(String, Vec<GroupVersionKind>)> {
     let mut misc = vec![];
     let mut map = BTreeMap::new();
@@ -123,6 +207,19 @@ where
         .with_name(name)
 }
 
+/// Copies the data to the system clipboard using the OSC 52 terminal escape sequence.
+/// Must be called from the UI thread, otherwise the sequence may interleave with the output.
+pub(crate) fn copy_to_clipboard(data: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(build_osc52_sequence(data).as_bytes())?;
+    stdout.flush()
+}
+
+pub(crate) fn build_osc52_sequence(data: &str) -> String {
+    let encoded = general_purpose::STANDARD.encode(data);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +228,38 @@ mod tests {
         let result = compute_age("2022-03-14T11:02:59.739144-04:00");
         assert_ne!(result, "E:TS");
     }
+
+    #[test]
+    fn test_parse_instant() {
+        let now = DateTime::parse_from_rfc3339("2023-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_instant("2023-05-01T09:00:00+02:00", now).unwrap(),
+            now - chrono::Duration::hours(3)
+        );
+        assert_eq!(
+            parse_instant("-15m", now).unwrap(),
+            now - chrono::Duration::minutes(15)
+        );
+        assert_eq!(
+            parse_instant("2h", now).unwrap(),
+            now - chrono::Duration::hours(2)
+        );
+        assert!(parse_instant("15x", now).is_err());
+        assert!(parse_instant("", now).is_err());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(10), "10B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(build_osc52_sequence("kgv"), "\x1b]52;c;a2d2\x07");
+    }
 }
@@ -62,6 +62,15 @@ impl<T: Send + Sync> LazyWatcher<T> {
         self.data.read_unwrap().clone()
     }
 
+    /// Incremented every time `value` rebuilds the data.
+    pub(crate) fn version(&self) -> usize {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn watch_paths(&self) -> &[PathBuf] {
+        &self.watch_paths
+    }
+
     pub(crate) fn build(&self) -> T {
         (self.builder.lock_unwrap())(&self.watch_paths)
     }
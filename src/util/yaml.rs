@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_yaml::Value;
+
+/// A YAML file of the kgv home dir, i.e. profiles or a remembered state; a missing file
+/// loads as the default value.
+pub(crate) trait YamlFile: Serialize + DeserializeOwned + Default {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Splits a JSON pointer (RFC 6901) into unescaped segments, i.e.
+/// `/metadata/annotations/kubectl.kubernetes.io~1last-applied-configuration`.
+/// `*` matches every item of a sequence.
+pub(crate) fn parse_json_pointer(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Removes the value at the given path; missing intermediate values are ignored.
+pub(crate) fn remove_path<S: AsRef<str>>(value: &mut Value, path: &[S]) {
+    let (head, tail) = match path.split_first() {
+        Some(parts) => parts,
+        None => return,
+    };
+    let head = head.as_ref();
+
+    if let Value::Sequence(items) = value {
+        if head == "*" {
+            for item in items.iter_mut() {
+                remove_path(item, tail);
+            }
+        } else if let Ok(index) = head.parse::<usize>() {
+            if tail.is_empty() {
+                if index < items.len() {
+                    items.remove(index);
+                }
+            } else if let Some(item) = items.get_mut(index) {
+                remove_path(item, tail);
+            }
+        }
+        return;
+    }
+
+    let mapping = match value {
+        Value::Mapping(mapping) => mapping,
+        _ => return,
+    };
+
+    if tail.is_empty() {
+        mapping.remove(head);
+    } else if let Some(child) = mapping.get_mut(head) {
+        remove_path(child, tail);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_json_pointer() {
+        let mut value: Value = serde_yaml::from_str(
+            r#"
+metadata:
+  annotations:
+    kubectl.kubernetes.io/last-applied-configuration: "{}"
+    app: x
+spec:
+  containers:
+    - name: a
+      env: []
+"#,
+        )
+        .unwrap();
+
+        let pointer = "/metadata/annotations/kubectl.kubernetes.io~1last-applied-configuration";
+        remove_path(&mut value, &parse_json_pointer(pointer));
+        remove_path(&mut value, &parse_json_pointer("/spec/containers/*/env"));
+
+        let yaml = serde_yaml::to_string(&value).unwrap();
+        assert!(!yaml.contains("last-applied-configuration"));
+        assert!(!yaml.contains("env"));
+        assert!(yaml.contains("app: x"));
+    }
+}